@@ -0,0 +1,453 @@
+//! Area, centroid and orientation of paths.
+//!
+//! The area and centroid are computed exactly from the curves (up to floating point
+//! precision) using Green's theorem, without flattening the path.
+//!
+//! Arcs don't appear in paths: the path builders approximate them with bézier curves, so
+//! the results correspond to these curves.
+//!
+//! # Example
+//!
+//! ```
+//! use lyon_algorithms::area::{centroid, signed_area, sub_path_windings};
+//! use lyon_algorithms::path::{Path, Winding};
+//! use lyon_algorithms::math::point;
+//!
+//! let mut builder = Path::builder();
+//! builder.begin(point(0.0, 0.0));
+//! builder.line_to(point(2.0, 0.0));
+//! builder.quadratic_bezier_to(point(2.0, 2.0), point(0.0, 2.0));
+//! builder.end(true);
+//! let path = builder.build();
+//!
+//! let area = signed_area(path.iter());
+//! let center = centroid(path.iter()).unwrap();
+//! assert_eq!(sub_path_windings(path.iter()), vec![Some(Winding::Positive)]);
+//! ```
+
+use crate::geom::{point as point64, CubicBezierSegment, LineSegment, QuadraticBezierSegment};
+use crate::hit_test::path_winding_number_at_position;
+use crate::math::{point, Point};
+use crate::path::{Path, PathEvent, PathSlice, Winding};
+
+type Point64 = crate::geom::Point<f64>;
+type Vector64 = crate::geom::Vector<f64>;
+
+/// Returns the area of the path, positive for sub-paths in the positive orientation and
+/// negative for the others.
+///
+/// Open sub-paths are considered closed, like when filling them. The areas of overlapping
+/// parts add up or cancel out depending on their orientations, so this is the area that
+/// the path covers only if its sub-paths don't overlap each other, as in a shape with
+/// holes that have the opposite orientation of the outer contour
+/// (see [`make_winding_consistent`](fn.make_winding_consistent.html)).
+pub fn signed_area<Iter>(path: Iter) -> f32
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut moments = Moments::new();
+    for evt in path {
+        moments.add_event(evt);
+    }
+
+    (moments.area * 0.5) as f32
+}
+
+/// Returns the center of mass of the path, or `None` if its signed area is zero.
+///
+/// Like [`signed_area`](fn.signed_area.html), sub-paths with a negative orientation count
+/// as holes.
+pub fn centroid<Iter>(path: Iter) -> Option<Point>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut moments = Moments::new();
+    for evt in path {
+        moments.add_event(evt);
+    }
+
+    if moments.area == 0.0 {
+        return None;
+    }
+
+    let origin = moments.origin?;
+    // The area accumulator is twice the area.
+    let k = 2.0 / (3.0 * moments.area);
+
+    Some(point(
+        (origin.x + moments.x * k) as f32,
+        (origin.y + moments.y * k) as f32,
+    ))
+}
+
+/// Returns the orientation of each sub-path, or `None` for the ones that have no area.
+pub fn sub_path_windings<Iter>(path: Iter) -> Vec<Option<Winding>>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut result = Vec::new();
+    let mut moments = Moments::new();
+    for evt in path {
+        moments.add_event(evt);
+        if let PathEvent::End { .. } = evt {
+            result.push(moments.winding());
+            moments = Moments::new();
+        }
+    }
+
+    result
+}
+
+/// Reverses sub-paths so that outer contours have the requested orientation, and holes the
+/// opposite one.
+///
+/// A sub-path is considered a hole if it is inside an odd number of other sub-paths,
+/// based on the position of its first point. After this, filling the path with the
+/// non-zero rule gives the same result as with the even-odd rule, as long as sub-paths
+/// don't intersect each other.
+///
+/// Sub-paths that have no area are kept as is. The winding numbers are computed within
+/// `tolerance`.
+pub fn make_winding_consistent(path: PathSlice, winding: Winding, tolerance: f32) -> Path {
+    let mut sub_paths: Vec<Vec<PathEvent>> = Vec::new();
+    let mut current = Vec::new();
+    for evt in path.iter() {
+        current.push(evt);
+        if let PathEvent::End { .. } = evt {
+            sub_paths.push(std::mem::replace(&mut current, Vec::new()));
+        }
+    }
+
+    let mut builder = Path::builder();
+    for (i, sub_path) in sub_paths.iter().enumerate() {
+        let mut moments = Moments::new();
+        for evt in sub_path {
+            moments.add_event(*evt);
+        }
+
+        let orientation = match moments.winding() {
+            Some(orientation) => orientation,
+            None => {
+                add_events(sub_path.iter().cloned(), &mut builder);
+                continue;
+            }
+        };
+
+        let first = match sub_path[0] {
+            PathEvent::Begin { at } => at,
+            _ => unreachable!(),
+        };
+
+        let mut depth = 0;
+        for (j, other) in sub_paths.iter().enumerate() {
+            if i != j
+                && path_winding_number_at_position(&first, other.iter().cloned(), tolerance) != 0
+            {
+                depth += 1;
+            }
+        }
+
+        let expected = if depth % 2 == 0 {
+            winding
+        } else {
+            match winding {
+                Winding::Positive => Winding::Negative,
+                Winding::Negative => Winding::Positive,
+            }
+        };
+
+        if orientation == expected {
+            add_events(sub_path.iter().cloned(), &mut builder);
+        } else {
+            add_reversed_sub_path(sub_path, &mut builder);
+        }
+    }
+
+    builder.build()
+}
+
+fn add_events(events: impl Iterator<Item = PathEvent>, builder: &mut crate::path::path::Builder) {
+    for evt in events {
+        builder.path_event(evt);
+    }
+}
+
+/// Adds a sub-path with its edges in the opposite order, starting from its last endpoint.
+fn add_reversed_sub_path(events: &[PathEvent], builder: &mut crate::path::path::Builder) {
+    let (last, close) = match events[events.len() - 1] {
+        PathEvent::End { last, close, .. } => (last, close),
+        _ => unreachable!(),
+    };
+
+    builder.begin(last);
+    for evt in events.iter().rev() {
+        match *evt {
+            PathEvent::Line { from, .. } => {
+                builder.line_to(from);
+            }
+            PathEvent::Quadratic { from, ctrl, .. } => {
+                builder.quadratic_bezier_to(ctrl, from);
+            }
+            PathEvent::Cubic {
+                from, ctrl1, ctrl2, ..
+            } => {
+                builder.cubic_bezier_to(ctrl2, ctrl1, from);
+            }
+            PathEvent::Begin { .. } | PathEvent::End { .. } => {}
+        }
+    }
+    builder.end(close);
+}
+
+/// Accumulates the integrals of Green's theorem along the edges of a path.
+///
+/// Positions are relative to the first point to reduce the loss of precision.
+struct Moments {
+    origin: Option<Point64>,
+    /// Twice the signed area.
+    area: f64,
+    /// Three times the first moments of area.
+    x: f64,
+    y: f64,
+}
+
+impl Moments {
+    fn new() -> Self {
+        Moments {
+            origin: None,
+            area: 0.0,
+            x: 0.0,
+            y: 0.0,
+        }
+    }
+
+    fn winding(&self) -> Option<Winding> {
+        if self.area > 0.0 {
+            Some(Winding::Positive)
+        } else if self.area < 0.0 {
+            Some(Winding::Negative)
+        } else {
+            None
+        }
+    }
+
+    fn to_local(&mut self, p: Point) -> Point64 {
+        let p = p.to_f64();
+        let origin = *self.origin.get_or_insert(p);
+        point64(p.x - origin.x, p.y - origin.y)
+    }
+
+    fn add_event(&mut self, evt: PathEvent) {
+        match evt {
+            PathEvent::Begin { at } => {
+                self.to_local(at);
+            }
+            PathEvent::Line { from, to } => {
+                self.add_line(from, to);
+            }
+            PathEvent::Quadratic { from, ctrl, to } => {
+                let curve = QuadraticBezierSegment {
+                    from: self.to_local(from),
+                    ctrl: self.to_local(ctrl),
+                    to: self.to_local(to),
+                };
+                self.integrate(|t| (curve.sample(t), curve.derivative(t)));
+            }
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let curve = CubicBezierSegment {
+                    from: self.to_local(from),
+                    ctrl1: self.to_local(ctrl1),
+                    ctrl2: self.to_local(ctrl2),
+                    to: self.to_local(to),
+                };
+                self.integrate(|t| (curve.sample(t), curve.derivative(t)));
+            }
+            PathEvent::End { last, first, .. } => {
+                // Open sub-paths are implicitly closed.
+                self.add_line(last, first);
+            }
+        }
+    }
+
+    fn add_line(&mut self, from: Point, to: Point) {
+        let line = LineSegment {
+            from: self.to_local(from),
+            to: self.to_local(to),
+        };
+        self.integrate(|t| (line.sample(t), line.to_vector()));
+    }
+
+    /// Integrates `p × p'`, `x (p × p')` and `y (p × p')` along a curve.
+    ///
+    /// These are polynomials of degree at most 8 for cubic bézier curves, which the five
+    /// points Gauss-Legendre quadrature integrates exactly.
+    fn integrate(&mut self, curve: impl Fn(f64) -> (Point64, Vector64)) {
+        const WEIGHTS: [f64; 5] = [
+            0.568_888_888_888_888_9,
+            0.478_628_670_499_366_47,
+            0.478_628_670_499_366_47,
+            0.236_926_885_056_189_08,
+            0.236_926_885_056_189_08,
+        ];
+        const ABSCISSAS: [f64; 5] = [
+            0.0,
+            -0.538_469_310_105_683_1,
+            0.538_469_310_105_683_1,
+            -0.906_179_845_938_664,
+            0.906_179_845_938_664,
+        ];
+
+        for (w, x) in WEIGHTS.iter().zip(ABSCISSAS.iter()) {
+            // Map from [-1, 1] to [0, 1].
+            let (p, d) = curve(x * 0.5 + 0.5);
+            let w = w * 0.5;
+            let cross = p.to_vector().cross(d) * w;
+            self.area += cross;
+            self.x += p.x * cross;
+            self.y += p.y * cross;
+        }
+    }
+}
+
+#[test]
+fn area_and_centroid() {
+    use crate::path::builder::PathBuilder;
+    use crate::path::iterator::PathIterator;
+
+    let mut builder = Path::builder();
+    builder.add_rectangle(&crate::math::rect(1.0, 2.0, 4.0, 2.0), Winding::Positive);
+    let path = builder.build();
+    assert!((signed_area(path.iter()) - 8.0).abs() < 1e-5);
+    let c = centroid(path.iter()).unwrap();
+    assert!((c - point(3.0, 3.0)).length() < 1e-5);
+    assert_eq!(
+        sub_path_windings(path.iter()),
+        vec![Some(Winding::Positive)]
+    );
+
+    // Reversed paths have the opposite area and the same centroid.
+    let reversed = path.reversed();
+    assert!((signed_area(reversed.iter()) + 8.0).abs() < 1e-5);
+    let c = centroid(reversed.iter()).unwrap();
+    assert!((c - point(3.0, 3.0)).length() < 1e-5);
+
+    // A circle made of cubic bézier curves.
+    let mut builder = Path::builder();
+    builder.add_circle(point(10.0, 5.0), 2.0, Winding::Negative);
+    let path = builder.build();
+    let area = signed_area(path.iter());
+    // The approximation of the circle is slightly bigger than the circle.
+    assert!(area < -4.0 * std::f32::consts::PI);
+    assert!(area > -4.0 * std::f32::consts::PI * 1.001);
+    let c = centroid(path.iter()).unwrap();
+    assert!((c - point(10.0, 5.0)).length() < 1e-4);
+
+    // A parabolic segment has two thirds of the area of its bounding triangle.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.quadratic_bezier_to(point(1.0, -2.0), point(2.0, 0.0));
+    builder.end(false);
+    let path = builder.build();
+    assert!((signed_area(path.iter()) - 4.0 / 3.0).abs() < 1e-5);
+    let c = centroid(path.iter()).unwrap();
+    assert!((c - point(1.0, -0.4)).length() < 1e-5);
+
+    // Compare with the flattened path.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.cubic_bezier_to(point(10.0, -5.0), point(20.0, 15.0), point(10.0, 10.0));
+    builder.quadratic_bezier_to(point(0.0, 20.0), point(-5.0, 5.0));
+    builder.end(true);
+    let path = builder.build();
+    let flattened: Vec<PathEvent> = path.iter().flattened(0.0001).collect();
+    assert!((signed_area(path.iter()) - signed_area(flattened.iter().cloned())).abs() < 0.01);
+    let c1 = centroid(path.iter()).unwrap();
+    let c2 = centroid(flattened.iter().cloned()).unwrap();
+    assert!((c1 - c2).length() < 0.001);
+
+    // Shapes with no area.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(1.0, 1.0));
+    builder.end(false);
+    builder.begin(point(1.0, 0.0));
+    builder.line_to(point(2.0, 0.0));
+    builder.line_to(point(2.0, 1.0));
+    builder.end(true);
+    let path = builder.build();
+    assert_eq!(
+        sub_path_windings(path.iter()),
+        vec![None, Some(Winding::Positive)]
+    );
+    assert!(centroid(Path::new().iter()).is_none());
+}
+
+#[test]
+fn consistent_winding() {
+    use crate::math::rect;
+    use crate::path::builder::PathBuilder;
+
+    // Three nested squares, all with the same orientation.
+    let mut builder = Path::builder();
+    builder.add_rectangle(&rect(0.0, 0.0, 10.0, 10.0), Winding::Negative);
+    builder.add_rectangle(&rect(1.0, 1.0, 8.0, 8.0), Winding::Negative);
+    builder.add_rectangle(&rect(2.0, 2.0, 6.0, 6.0), Winding::Negative);
+    // A separate triangle.
+    builder.begin(point(20.0, 0.0));
+    builder.line_to(point(22.0, 0.0));
+    builder.line_to(point(22.0, 2.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let result = make_winding_consistent(path.as_slice(), Winding::Positive, 0.01);
+    assert_eq!(
+        sub_path_windings(result.iter()),
+        vec![
+            Some(Winding::Positive),
+            Some(Winding::Negative),
+            Some(Winding::Positive),
+            Some(Winding::Positive),
+        ]
+    );
+    assert!((signed_area(result.iter()) - (100.0 - 64.0 + 36.0 + 2.0)).abs() < 1e-4);
+
+    // Open sub-paths stay open.
+    let last: Vec<PathEvent> = result.iter().skip(15).collect();
+    assert_eq!(
+        last,
+        vec![
+            PathEvent::Begin {
+                at: point(20.0, 0.0)
+            },
+            PathEvent::Line {
+                from: point(20.0, 0.0),
+                to: point(22.0, 0.0)
+            },
+            PathEvent::Line {
+                from: point(22.0, 0.0),
+                to: point(22.0, 2.0)
+            },
+            PathEvent::End {
+                last: point(22.0, 2.0),
+                first: point(20.0, 0.0),
+                close: false
+            },
+        ]
+    );
+
+    let result = make_winding_consistent(path.as_slice(), Winding::Negative, 0.01);
+    assert_eq!(
+        sub_path_windings(result.iter()),
+        vec![
+            Some(Winding::Negative),
+            Some(Winding::Positive),
+            Some(Winding::Negative),
+            Some(Winding::Negative),
+        ]
+    );
+}
@@ -0,0 +1,722 @@
+//! Boolean operations (union, intersection, difference and exclusive or) on paths.
+//!
+//! # Example
+//!
+//! ```
+//! use lyon_algorithms::boolean::{boolean_op, BooleanOp};
+//! use lyon_algorithms::path::{Path, FillRule};
+//! use lyon_algorithms::math::point;
+//!
+//! let mut builder = Path::builder();
+//! builder.begin(point(0.0, 0.0));
+//! builder.line_to(point(10.0, 0.0));
+//! builder.line_to(point(10.0, 10.0));
+//! builder.line_to(point(0.0, 10.0));
+//! builder.end(true);
+//! let square = builder.build();
+//!
+//! let mut builder = Path::builder();
+//! builder.begin(point(5.0, 5.0));
+//! builder.quadratic_bezier_to(point(15.0, 5.0), point(15.0, 15.0));
+//! builder.line_to(point(5.0, 15.0));
+//! builder.end(true);
+//! let other = builder.build();
+//!
+//! // The outline of the area covered by both shapes.
+//! let union = boolean_op(
+//!     square.as_slice(),
+//!     FillRule::NonZero,
+//!     other.as_slice(),
+//!     FillRule::NonZero,
+//!     BooleanOp::Union,
+//!     0.01,
+//! );
+//! ```
+
+use crate::geom::euclid::default::Point2D;
+use crate::geom::predicates::{orient2d, segment_intersection, SegmentIntersection};
+use crate::geom::LineSegment;
+use crate::path::iterator::PathIterator;
+use crate::path::{self, FillRule, Path, PathEvent, PathSlice};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+type Point = Point2D<f64>;
+
+/// The operation applied by `boolean_op`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// The areas covered by either path.
+    Union,
+    /// The areas covered by both paths.
+    Intersection,
+    /// The areas covered by the first path but not by the second one.
+    Difference,
+    /// The areas covered by exactly one of the paths.
+    Xor,
+}
+
+impl BooleanOp {
+    #[inline]
+    pub fn is_in(&self, in_a: bool, in_b: bool) -> bool {
+        match *self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+/// Computes the outline of the result of a boolean operation between the fill areas of two
+/// paths.
+///
+/// Each path is interpreted with its own fill rule and open sub-paths are implicitly closed,
+/// like they would be by the fill tessellator. Curves are flattened with the provided
+/// tolerance, so the result only contains line segments.
+///
+/// The produced sub-paths are closed and don't cross each other. Sub-paths surrounding filled
+/// areas all have the same orientation and holes have the opposite one, so the result can be
+/// filled with either fill rule.
+pub fn boolean_op(
+    a: PathSlice,
+    a_fill_rule: FillRule,
+    b: PathSlice,
+    b_fill_rule: FillRule,
+    op: BooleanOp,
+    tolerance: f32,
+) -> Path {
+    let fill_rules = [a_fill_rule, b_fill_rule];
+    outline(a, b, tolerance, &|winding| {
+        op.is_in(
+            fill_rules[0].is_in(winding[0] as i16),
+            fill_rules[1].is_in(winding[1] as i16),
+        )
+    })
+}
+
+/// Computes the outline of the areas where the winding number of the path is positive.
+///
+/// Unlike with the non-zero fill rule, the areas that the path goes around negatively are not
+/// included, which is how the self-intersections of offset contours are resolved.
+pub(crate) fn positive_outline(path: PathSlice, tolerance: f32) -> Path {
+    let empty = Path::new();
+    outline(path, empty.as_slice(), tolerance, &|winding| winding[0] > 0)
+}
+
+/// Computes the outline of the areas where the winding numbers of both paths satisfy `is_in`.
+fn outline(
+    a: PathSlice,
+    b: PathSlice,
+    tolerance: f32,
+    is_in: &dyn Fn([i32; 2]) -> bool,
+) -> Path {
+    // Points closer than this are merged, which is negligible compared to the error introduced
+    // by flattening.
+    let mut vertices = Vertices::new(tolerance as f64 * 0.001);
+    let mut edges = Vec::new();
+    add_edges(a, 0, tolerance, &mut vertices, &mut edges);
+    add_edges(b, 1, tolerance, &mut vertices, &mut edges);
+
+    // Splitting edges at their intersections can create new intersections because the
+    // intersection points are rounded, so repeat until the edges only meet at their endpoints.
+    //
+    // Edges are only split at snapped vertices, of which there is a finite number since they
+    // are further than the snap distance from each other and within the bounds of the input.
+    // Each edge is split at most once at a given vertex, otherwise tiny triangles of nearly
+    // aligned vertices could split each other's edges back and forth, so this terminates.
+    let mut applied_splits = HashSet::new();
+    while split_edges(&mut edges, &mut vertices, &mut applied_splits) {
+        edges = subdivide(&edges);
+    }
+
+    let groups = group_edges(&edges);
+
+    // Keep the edges that separate the inside from the outside of the result, oriented so
+    // that the inside is on the positive side.
+    let mut output = Vec::new();
+    for (group, [negative, positive]) in groups.iter().zip(group_windings(&groups)) {
+        match (is_in(negative), is_in(positive)) {
+            (false, true) => output.push((group.from, group.to)),
+            (true, false) => output.push((group.to, group.from)),
+            _ => {}
+        }
+    }
+
+    build_path(&output)
+}
+
+struct Edge {
+    from: Point,
+    to: Point,
+    source: usize,
+    splits: Vec<(f64, Point)>,
+}
+
+/// Edges of the arrangement with the number of times each path goes through them.
+struct EdgeGroup {
+    from: Point,
+    to: Point,
+    winding: [i32; 2],
+}
+
+/// Merges points that are very close to each other so that rounding errors don't produce
+/// tiny edges and slivers.
+struct Vertices {
+    snap_distance: f64,
+    cells: HashMap<(i64, i64), Vec<Point>>,
+}
+
+impl Vertices {
+    fn new(snap_distance: f64) -> Self {
+        Vertices {
+            snap_distance: snap_distance.max(std::f64::MIN_POSITIVE),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Returns a previously added point close to `p` if there is one, otherwise adds `p`.
+    fn snap(&mut self, p: Point) -> Point {
+        let cell_x = (p.x / self.snap_distance).floor() as i64;
+        let cell_y = (p.y / self.snap_distance).floor() as i64;
+        for x in cell_x - 1..=cell_x + 1 {
+            for y in cell_y - 1..=cell_y + 1 {
+                if let Some(points) = self.cells.get(&(x, y)) {
+                    for point in points {
+                        if (*point - p).length() <= self.snap_distance {
+                            return *point;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.cells.entry((cell_x, cell_y)).or_default().push(p);
+        p
+    }
+}
+
+fn add_edges(
+    path: PathSlice,
+    source: usize,
+    tolerance: f32,
+    vertices: &mut Vertices,
+    edges: &mut Vec<Edge>,
+) {
+    let mut add = |from: crate::math::Point, to: crate::math::Point| {
+        // Edges with non-finite coordinates can't be part of the outline of an area.
+        let finite = |p: crate::math::Point| p.x.is_finite() && p.y.is_finite();
+        if !finite(from) || !finite(to) {
+            return;
+        }
+
+        let from = vertices.snap(from.to_f64());
+        let to = vertices.snap(to.to_f64());
+        if from != to {
+            edges.push(Edge {
+                from,
+                to,
+                source,
+                splits: Vec::new(),
+            });
+        }
+    };
+
+    for evt in path.iter().flattened(tolerance) {
+        match evt {
+            PathEvent::Line { from, to } => add(from, to),
+            PathEvent::End { last, first, .. } => add(last, first),
+            _ => {}
+        }
+    }
+}
+
+/// Finds the intersections between all pairs of edges and records where the edges must be
+/// split.
+///
+/// Splits that were already applied in previous passes are ignored.
+///
+/// Returns whether any edge must be split.
+fn split_edges(
+    edges: &mut [Edge],
+    vertices: &mut Vertices,
+    applied_splits: &mut HashSet<SplitKey>,
+) -> bool {
+    let mut sorted: Vec<usize> = (0..edges.len()).collect();
+    sorted.sort_by(|a, b| {
+        let a = edges[*a].from.x.min(edges[*a].to.x);
+        let b = edges[*b].from.x.min(edges[*b].to.x);
+        a.partial_cmp(&b).unwrap()
+    });
+
+    for (i, &e1) in sorted.iter().enumerate() {
+        let max_x = edges[e1].from.x.max(edges[e1].to.x) + vertices.snap_distance;
+        let min_y = edges[e1].from.y.min(edges[e1].to.y) - vertices.snap_distance;
+        let max_y = edges[e1].from.y.max(edges[e1].to.y) + vertices.snap_distance;
+        for &e2 in &sorted[i + 1..] {
+            if edges[e2].from.x.min(edges[e2].to.x) > max_x {
+                break;
+            }
+            if edges[e2].from.y.max(edges[e2].to.y) < min_y
+                || edges[e2].from.y.min(edges[e2].to.y) > max_y
+            {
+                continue;
+            }
+            intersect(edges, e1, e2, vertices);
+        }
+    }
+
+    // Filter all edges before recording the new splits so that identical edges are split
+    // identically.
+    for edge in edges.iter_mut() {
+        let segment = segment_key(&LineSegment {
+            from: edge.from,
+            to: edge.to,
+        });
+        edge.splits.retain(|(_, p)| !applied_splits.contains(&(segment, point_key(*p))));
+    }
+    for edge in edges.iter() {
+        let segment = segment_key(&LineSegment {
+            from: edge.from,
+            to: edge.to,
+        });
+        for (_, p) in &edge.splits {
+            applied_splits.insert((segment, point_key(*p)));
+        }
+    }
+
+    edges.iter().any(|edge| !edge.splits.is_empty())
+}
+
+/// Identifies the split of an edge at a point.
+type SplitKey = (((u64, u64), (u64, u64)), (u64, u64));
+
+/// Splits the edges at the recorded positions.
+fn subdivide(edges: &[Edge]) -> Vec<Edge> {
+    let mut result = Vec::with_capacity(edges.len());
+    let mut points = Vec::new();
+    for edge in edges {
+        points.clear();
+        points.push((0.0, edge.from));
+        points.extend_from_slice(&edge.splits);
+        points.push((1.0, edge.to));
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        for pair in points.windows(2) {
+            let (from, to) = (pair[0].1, pair[1].1);
+            if from != to {
+                result.push(Edge {
+                    from,
+                    to,
+                    source: edge.source,
+                    splits: Vec::new(),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+fn intersect(edges: &mut [Edge], e1: usize, e2: usize, vertices: &mut Vertices) {
+    let s1 = LineSegment {
+        from: edges[e1].from,
+        to: edges[e1].to,
+    };
+    let s2 = LineSegment {
+        from: edges[e2].from,
+        to: edges[e2].to,
+    };
+
+    // Split the edges at the endpoints of the other edge that are on them or almost on them.
+    // This handles the edges that touch or overlap.
+    let mut touching = false;
+    for p in &[s2.from, s2.to] {
+        if let Some(t) = near_interior_t(&s1, *p, vertices.snap_distance) {
+            edges[e1].splits.push((t, *p));
+            touching = true;
+        }
+    }
+    for p in &[s1.from, s1.to] {
+        if let Some(t) = near_interior_t(&s2, *p, vertices.snap_distance) {
+            edges[e2].splits.push((t, *p));
+            touching = true;
+        }
+    }
+
+    if touching || segment_intersection(&s1, &s2) != SegmentIntersection::Crossing {
+        return;
+    }
+
+    // Compute the intersection in a way that doesn't depend on the order and direction of the
+    // segments, so that identical edges are split at identical points.
+    let (c1, c2) = if segment_key(&s1) < segment_key(&s2) {
+        (canonical(&s1), canonical(&s2))
+    } else {
+        (canonical(&s2), canonical(&s1))
+    };
+    if let Some(p) = c1.intersection(&c2) {
+        let p = vertices.snap(p);
+        for (idx, segment) in &[(e1, s1), (e2, s2)] {
+            if p != segment.from && p != segment.to {
+                edges[*idx].splits.push((projected_t(segment, p), p));
+            }
+        }
+    }
+}
+
+fn canonical(segment: &LineSegment<f64>) -> LineSegment<f64> {
+    if point_key(segment.from) < point_key(segment.to) {
+        *segment
+    } else {
+        segment.flip()
+    }
+}
+
+fn segment_key(segment: &LineSegment<f64>) -> ((u64, u64), (u64, u64)) {
+    let segment = canonical(segment);
+    (point_key(segment.from), point_key(segment.to))
+}
+
+fn projected_t(segment: &LineSegment<f64>, p: Point) -> f64 {
+    let v = segment.to_vector();
+    (p - segment.from).dot(v) / v.square_length()
+}
+
+/// If the point is within `distance` of the segment and not on one of its endpoints, returns
+/// its position along the segment.
+fn near_interior_t(segment: &LineSegment<f64>, p: Point, distance: f64) -> Option<f64> {
+    if p == segment.from || p == segment.to {
+        return None;
+    }
+
+    let t = projected_t(segment, p);
+    if t > 0.0 && t < 1.0 && (segment.sample(t) - p).length() <= distance {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+/// Merges the edges that connect the same points.
+fn group_edges(edges: &[Edge]) -> Vec<EdgeGroup> {
+    let mut groups: Vec<EdgeGroup> = Vec::new();
+    let mut indices = HashMap::new();
+    for edge in edges {
+        let (from, to, winding) = if point_key(edge.from) < point_key(edge.to) {
+            (edge.from, edge.to, 1)
+        } else {
+            (edge.to, edge.from, -1)
+        };
+
+        let idx = *indices
+            .entry((point_key(from), point_key(to)))
+            .or_insert_with(|| {
+                groups.push(EdgeGroup {
+                    from,
+                    to,
+                    winding: [0, 0],
+                });
+                groups.len() - 1
+            });
+        groups[idx].winding[edge.source] += winding;
+    }
+
+    groups
+}
+
+fn point_key(p: Point) -> (u64, u64) {
+    // Adding zero turns negative zeros into positive ones.
+    ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits())
+}
+
+/// Computes the winding numbers of both paths on the negative and positive sides of each edge
+/// group.
+///
+/// A sweep line goes up through the groups, keeping the ones it crosses sorted from left to
+/// right. Since the groups only meet at their endpoints, their order doesn't change while they
+/// are crossed, and the winding numbers next to a group are the sum of the crossings on its
+/// left.
+fn group_windings(groups: &[EdgeGroup]) -> Vec<[[i32; 2]; 2]> {
+    // The lowest and highest endpoints of each group.
+    let ends: Vec<(Point, Point)> = groups
+        .iter()
+        .map(|g| {
+            if (g.from.y, g.from.x) < (g.to.y, g.to.x) {
+                (g.from, g.to)
+            } else {
+                (g.to, g.from)
+            }
+        })
+        .collect();
+
+    // How much the winding numbers change when crossing each group from right to left.
+    let crossing = |idx: usize| {
+        let group = &groups[idx];
+        let dir = if group.to.y > group.from.y { 1 } else { -1 };
+        [dir * group.winding[0], dir * group.winding[1]]
+    };
+
+    // Going from the negative to the positive side of a group changes the winding numbers by
+    // the number of times each path goes through it.
+    let sides = |idx: usize, negative: [i32; 2]| {
+        let w = groups[idx].winding;
+        [negative, [negative[0] + w[0], negative[1] + w[1]]]
+    };
+
+    let mut starts: Vec<usize> = (0..groups.len()).collect();
+    starts.sort_by(|a, b| ends[*a].0.y.partial_cmp(&ends[*b].0.y).unwrap());
+
+    let mut windings = vec![[[0; 2]; 2]; groups.len()];
+    let mut active: Vec<usize> = Vec::new();
+    // The winding numbers on the left of each active group.
+    let mut on_left: Vec<[i32; 2]> = Vec::new();
+    let mut horizontal = Vec::new();
+    let mut next = 0;
+    while next < starts.len() {
+        let y = ends[starts[next]].0.y;
+        active.retain(|a| ends[*a].1.y > y);
+
+        horizontal.clear();
+        while next < starts.len() && ends[starts[next]].0.y == y {
+            let idx = starts[next];
+            next += 1;
+            if ends[idx].1.y == y {
+                horizontal.push(idx);
+                continue;
+            }
+
+            let pos = partition(&active, |a| !is_left(ends[idx], ends[a]));
+            active.insert(pos, idx);
+        }
+
+        on_left.clear();
+        let mut winding = [0, 0];
+        for &idx in &active {
+            on_left.push(winding);
+            let c = crossing(idx);
+            winding = [winding[0] - c[0], winding[1] - c[1]];
+        }
+        on_left.push(winding);
+
+        for (pos, &idx) in active.iter().enumerate() {
+            if ends[idx].0.y != y {
+                continue;
+            }
+            let left = on_left[pos];
+            let c = crossing(idx);
+            let right = [left[0] - c[0], left[1] - c[1]];
+            // The positive side of an edge going up is on its left.
+            let negative = if groups[idx].to.y > groups[idx].from.y {
+                right
+            } else {
+                left
+            };
+            windings[idx] = sides(idx, negative);
+        }
+
+        // Horizontal groups are between the groups crossed by the sweep line just above them.
+        for &idx in &horizontal {
+            let (from, to) = ends[idx];
+            let m = from.lerp(to, 0.5);
+            let above = on_left[partition(&active, |a| orient2d(ends[a].0, ends[a].1, m) < 0.0)];
+            // The positive side of an edge going right is above it.
+            let w = groups[idx].winding;
+            let negative = if groups[idx].to.x > groups[idx].from.x {
+                [above[0] - w[0], above[1] - w[1]]
+            } else {
+                above
+            };
+            windings[idx] = sides(idx, negative);
+        }
+    }
+
+    windings
+}
+
+/// Whether `edge` is on the left of `other` just above the lowest endpoint of `edge`, for
+/// edges going up that don't cross.
+fn is_left(edge: (Point, Point), other: (Point, Point)) -> bool {
+    let mut side = 0.0;
+    if edge.0 != other.0 {
+        side = orient2d(other.0, other.1, edge.0);
+    }
+    if side == 0.0 {
+        side = orient2d(other.0, other.1, edge.1);
+    }
+
+    side > 0.0
+}
+
+/// Returns the number of leading items of `sorted` for which `pred` is true, assuming it is
+/// true for a prefix of the slice.
+fn partition(sorted: &[usize], pred: impl Fn(usize) -> bool) -> usize {
+    sorted
+        .binary_search_by(|idx| {
+            if pred(*idx) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_err()
+}
+
+/// Chains the edges into closed sub-paths.
+fn build_path(edges: &[(Point, Point)]) -> Path {
+    let mut builder = Path::builder();
+    path::private::chain_edges(edges, &mut |points| add_polygon(points, &mut builder));
+
+    builder.build()
+}
+
+fn add_polygon(points: &[Point], builder: &mut path::path::Builder) {
+    // Skip the points in the middle of straight lines, which were introduced when splitting
+    // the edges.
+    let n = points.len();
+    let mut kept = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = points[(i + n - 1) % n];
+        let next = points[(i + 1) % n];
+        let p = points[i];
+        if (p - prev).cross(next - p) != 0.0 || (p - prev).dot(next - p) < 0.0 {
+            kept.push(p.to_f32());
+        }
+    }
+
+    if kept.len() < 3 {
+        return;
+    }
+
+    builder.begin(kept[0]);
+    for p in &kept[1..] {
+        builder.line_to(*p);
+    }
+    builder.end(true);
+}
+
+#[test]
+fn boolean_ops() {
+    use crate::hit_test::hit_test_path;
+    use crate::math::point;
+
+    let rect = |x0: f32, y0: f32, x1: f32, y1: f32| {
+        let mut builder = Path::builder();
+        builder.begin(point(x0, y0));
+        builder.line_to(point(x1, y0));
+        builder.line_to(point(x1, y1));
+        builder.line_to(point(x0, y1));
+        builder.end(true);
+        builder.build()
+    };
+
+    let area = |path: &Path| {
+        let mut area = 0.0;
+        for evt in path.iter() {
+            match evt {
+                PathEvent::Line { from, to } | PathEvent::End { last: from, first: to, .. } => {
+                    area += from.x * to.y - to.x * from.y;
+                }
+                _ => {}
+            }
+        }
+        area * 0.5
+    };
+
+    let a = rect(0.0, 0.0, 10.0, 10.0);
+    let b = rect(5.0, 5.0, 15.0, 15.0);
+    let nz = FillRule::NonZero;
+
+    let union = boolean_op(a.as_slice(), nz, b.as_slice(), nz, BooleanOp::Union, 0.01);
+    assert_eq!(area(&union), 175.0);
+    assert_eq!(union.iter().count(), 9);
+
+    let inter = boolean_op(a.as_slice(), nz, b.as_slice(), nz, BooleanOp::Intersection, 0.01);
+    assert_eq!(area(&inter), 25.0);
+    assert_eq!(inter.iter().count(), 5);
+
+    let diff = boolean_op(a.as_slice(), nz, b.as_slice(), nz, BooleanOp::Difference, 0.01);
+    assert_eq!(area(&diff), 75.0);
+
+    let xor = boolean_op(a.as_slice(), nz, b.as_slice(), nz, BooleanOp::Xor, 0.01);
+    assert_eq!(area(&xor), 150.0);
+    // The two parts of the result touch at two points but don't cross.
+    assert_eq!(xor.iter().filter(|evt| matches!(evt, PathEvent::Begin { .. })).count(), 2);
+
+    for (x, y) in &[(2.0, 2.0), (7.0, 7.0), (12.0, 12.0), (2.0, 12.0), (7.0, 2.0)] {
+        let p = point(*x, *y);
+        let in_a = hit_test_path(&p, a.iter(), nz, 0.01);
+        let in_b = hit_test_path(&p, b.iter(), nz, 0.01);
+        for (op, result) in &[
+            (BooleanOp::Union, &union),
+            (BooleanOp::Intersection, &inter),
+            (BooleanOp::Difference, &diff),
+            (BooleanOp::Xor, &xor),
+        ] {
+            for fill_rule in &[FillRule::NonZero, FillRule::EvenOdd] {
+                let in_result = hit_test_path(&p, result.iter(), *fill_rule, 0.01);
+                assert_eq!(in_result, op.is_in(in_a, in_b));
+            }
+        }
+    }
+
+    // Overlapping edges and holes.
+    let c = rect(0.0, 0.0, 10.0, 5.0);
+    let union = boolean_op(a.as_slice(), nz, c.as_slice(), nz, BooleanOp::Union, 0.01);
+    assert_eq!(area(&union), 100.0);
+    assert_eq!(union.iter().count(), 5);
+
+    let d = rect(2.0, 2.0, 8.0, 8.0);
+    let diff = boolean_op(a.as_slice(), nz, d.as_slice(), nz, BooleanOp::Difference, 0.01);
+    assert_eq!(area(&diff), 64.0);
+    assert!(!hit_test_path(&point(5.0, 5.0), diff.iter(), nz, 0.01));
+    assert!(hit_test_path(&point(1.0, 5.0), diff.iter(), nz, 0.01));
+
+    // Fill rules are taken into account.
+    let mut builder = Path::builder();
+    for evt in a.iter().chain(d.iter()) {
+        builder.path_event(evt);
+    }
+    let nested = builder.build();
+    let eo = FillRule::EvenOdd;
+    let union = boolean_op(nested.as_slice(), eo, c.as_slice(), nz, BooleanOp::Union, 0.01);
+    assert_eq!(area(&union), 100.0 - 18.0);
+
+    // Non-finite coordinates are ignored. The builder doesn't accept them but transforms
+    // can produce them.
+    let nan_transform = crate::math::Transform::scale(std::f32::NAN, 1.0);
+    let nan = rect(1.0, 1.0, 2.0, 2.0).transformed(&nan_transform);
+    let union = boolean_op(nan.as_slice(), nz, b.as_slice(), nz, BooleanOp::Union, 0.01);
+    assert_eq!(area(&union), 100.0);
+
+    // Many edges crossing near the same points, which requires several splitting passes.
+    let mut builder = Path::builder();
+    for i in 0..40 {
+        let angle = i as f32 * 0.0773;
+        let (sin, cos) = angle.sin_cos();
+        let v = crate::math::vector(cos, sin);
+        let n = crate::math::vector(-sin, cos) * 0.001;
+        let center = point(5.0 + i as f32 * 1e-5, 5.0);
+        builder.begin(center - v * 10.0 - n);
+        builder.line_to(center + v * 10.0 - n);
+        builder.line_to(center + v * 10.0 + n);
+        builder.line_to(center - v * 10.0 + n);
+        builder.end(true);
+    }
+    let star = builder.build();
+    let union = boolean_op(star.as_slice(), nz, a.as_slice(), nz, BooleanOp::Union, 0.01);
+    let mut edges = Vec::new();
+    for evt in union.iter() {
+        match evt {
+            PathEvent::Line { from, to } | PathEvent::End { last: from, first: to, .. } => {
+                edges.push(LineSegment { from, to });
+            }
+            _ => {}
+        }
+    }
+    for (i, e1) in edges.iter().enumerate() {
+        for e2 in &edges[i + 1..] {
+            assert_ne!(segment_intersection(e1, e2), SegmentIntersection::Crossing);
+        }
+    }
+}
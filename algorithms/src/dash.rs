@@ -0,0 +1,343 @@
+//! Split paths into dashes.
+//!
+//! # Example
+//!
+//! ```
+//! use lyon_algorithms::dash::dash_path;
+//! use lyon_algorithms::path::Path;
+//! use lyon_algorithms::math::point;
+//!
+//! let mut builder = Path::builder();
+//! builder.begin(point(0.0, 0.0));
+//! builder.line_to(point(100.0, 0.0));
+//! builder.quadratic_bezier_to(point(100.0, 100.0), point(0.0, 100.0));
+//! builder.end(true);
+//! let path = builder.build();
+//!
+//! // Dashes of 10 units separated by gaps of 5 units, each dash being an open
+//! // sub-path that can be stroked with the stroke tessellator.
+//! let dashes = dash_path(path.as_slice(), &[10.0, 5.0], 0.0, 0.01);
+//! ```
+
+use crate::measure::PathMeasure;
+use crate::path::{Path, PathSlice};
+
+/// Splits a path into dashes, producing one open sub-path per dash.
+///
+/// The values of the dash `pattern` alternate between the lengths of dashes and gaps,
+/// following the conventions of SVG's `stroke-dasharray`:
+///
+/// - If the pattern has an odd number of values, it is repeated to get an even number.
+/// - If the pattern is empty, its sum is zero, or it contains negative or non-finite values,
+///   the path is not dashed and is returned as is.
+/// - The pattern restarts at the beginning of each sub-path.
+///
+/// `offset` is the distance into the pattern at which dashing starts (like SVG's
+/// `stroke-dashoffset`). Curves are kept as curves, only split at the extremities of the
+/// dashes. For closed sub-paths, a dash crossing the start point is produced as a single
+/// sub-path, and a dash covering a whole closed sub-path produces the closed sub-path.
+///
+/// The lengths along the path are measured within `tolerance`.
+///
+/// Zero-length dashes produce sub-paths made of a single point, on which the stroke
+/// tessellator draws round or square caps, like SVG does for dotted lines.
+///
+/// To avoid running out of memory, the path is not dashed either if the pattern is so small
+/// compared to the path that it would produce more than `MAX_DASHES` dashes.
+pub fn dash_path(path: PathSlice, pattern: &[f32], offset: f32, tolerance: f32) -> Path {
+    let undashed = || {
+        let mut builder = Path::builder();
+        for evt in path.iter() {
+            builder.path_event(evt);
+        }
+        builder.build()
+    };
+
+    let pattern_length: f32 = pattern.iter().sum();
+    let valid = pattern.iter().all(|v| *v >= 0.0) && pattern_length > 0.0;
+    if !valid || !pattern_length.is_finite() {
+        return undashed();
+    }
+
+    let pattern: Vec<f32> = if pattern.len() % 2 == 1 {
+        pattern.iter().chain(pattern.iter()).cloned().collect()
+    } else {
+        pattern.to_vec()
+    };
+
+    let measure = PathMeasure::new(path, tolerance);
+
+    // Each sub-path starts a new repetition of the pattern.
+    let repetitions =
+        measure.length() as f64 / pattern_length as f64 + measure.num_sub_paths() as f64;
+    if repetitions * (pattern.len() / 2) as f64 > MAX_DASHES as f64 {
+        return undashed();
+    }
+
+    let mut builder = Path::builder();
+    let mut dashes = Vec::new();
+    for sub_path in 0..measure.num_sub_paths() {
+        let range = measure.sub_path_range(sub_path);
+        let length = range.end - range.start;
+        if length <= 0.0 {
+            continue;
+        }
+
+        let closed = measure.is_sub_path_closed(sub_path);
+        dashes.clear();
+        collect_dashes(&pattern, offset, length, closed, &mut dashes);
+
+        if closed {
+            if let [(start, end)] = dashes[..] {
+                if start <= 0.0 && end >= length {
+                    // The dash covers the whole sub-path.
+                    let mut started = false;
+                    measure.add_range(sub_path, range.clone(), true, &mut builder, &mut started);
+                    builder.end(true);
+                    continue;
+                }
+            }
+        }
+
+        // On closed sub-paths, the last dash continues into the first one if they meet at
+        // the start point.
+        let wraps = closed
+            && dashes.len() > 1
+            && dashes[0].0 <= 0.0
+            && dashes[dashes.len() - 1].1 >= length;
+        let (first, last) = if wraps {
+            (1, dashes.len() - 1)
+        } else {
+            (0, dashes.len())
+        };
+
+        for &(start, end) in &dashes[first..last] {
+            if start == end {
+                let (position, _) = measure.sample_at(range.start + start);
+                builder.begin(position);
+                builder.end(false);
+                continue;
+            }
+
+            let mut started = false;
+            let dash_range = (range.start + start)..(range.start + end);
+            measure.add_range(sub_path, dash_range, false, &mut builder, &mut started);
+            if started {
+                builder.end(false);
+            }
+        }
+
+        if wraps {
+            let mut started = false;
+            let (start, _) = dashes[dashes.len() - 1];
+            let (_, end) = dashes[0];
+            let tail = (range.start + start)..range.end;
+            let head = range.start..(range.start + end);
+            measure.add_range(sub_path, tail, false, &mut builder, &mut started);
+            measure.add_range(sub_path, head, false, &mut builder, &mut started);
+            if started {
+                builder.end(false);
+            }
+        }
+    }
+
+    builder.build()
+}
+
+/// The maximum number of dashes produced by `dash_path`.
+pub const MAX_DASHES: usize = 1_000_000;
+
+/// Computes the ranges of the dashes along a sub-path of a given length.
+///
+/// Zero-length dashes are included, and on open sub-paths they are included at the end as
+/// well.
+fn collect_dashes(
+    pattern: &[f32],
+    offset: f32,
+    length: f32,
+    closed: bool,
+    output: &mut Vec<(f32, f32)>,
+) {
+    // Positions are accumulated in double precision so that small dashes keep advancing
+    // along long paths.
+    let pattern_length: f64 = pattern.iter().map(|v| *v as f64).sum();
+    let length = length as f64;
+
+    // Find where the offset falls in the pattern.
+    let offset = if offset.is_finite() { offset as f64 } else { 0.0 };
+    let mut remaining = offset.rem_euclid(pattern_length);
+    let mut index = 0;
+    for _ in 0..pattern.len() {
+        if remaining < pattern[index] as f64 || remaining == 0.0 {
+            break;
+        }
+        remaining -= pattern[index] as f64;
+        index = (index + 1) % pattern.len();
+    }
+
+    let mut position = -remaining;
+    while position < length || (position == length && !closed) {
+        let end = position + pattern[index] as f64;
+        let is_dash = index % 2 == 0;
+        let start = position.max(0.0);
+        let clamped_end = end.min(length);
+        let is_dot = pattern[index] == 0.0 && position >= 0.0;
+        if is_dash && (clamped_end > start || is_dot) {
+            output.push((start as f32, clamped_end.max(start) as f32));
+        }
+
+        if end <= position && pattern[index] > 0.0 {
+            // The position doesn't advance anymore.
+            break;
+        }
+
+        position = end;
+        index = (index + 1) % pattern.len();
+    }
+}
+
+#[test]
+fn dashes() {
+    use crate::math::point;
+    use crate::path::PathEvent;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let sub_paths = |path: &Path| -> Vec<Vec<PathEvent>> {
+        let mut result = Vec::new();
+        let mut current = Vec::new();
+        for evt in path.iter() {
+            current.push(evt);
+            if let PathEvent::End { .. } = evt {
+                result.push(std::mem::replace(&mut current, Vec::new()));
+            }
+        }
+        result
+    };
+
+    // Dashes of length 3 every 5 units along a square of perimeter 40.
+    let dashed = dash_path(path.as_slice(), &[3.0, 2.0], 0.0, 0.01);
+    let dashes = sub_paths(&dashed);
+    assert_eq!(dashes.len(), 8);
+    assert_eq!(
+        dashes[0][0],
+        PathEvent::Begin {
+            at: point(0.0, 0.0)
+        }
+    );
+    assert_eq!(
+        dashes[2],
+        vec![
+            PathEvent::Begin {
+                at: point(10.0, 0.0)
+            },
+            PathEvent::Line {
+                from: point(10.0, 0.0),
+                to: point(10.0, 3.0)
+            },
+            PathEvent::End {
+                last: point(10.0, 3.0),
+                first: point(10.0, 0.0),
+                close: false
+            },
+        ]
+    );
+
+    // With an offset of 1, the last dash continues across the start point.
+    let dashed = dash_path(path.as_slice(), &[3.0, 2.0], 1.0, 0.01);
+    let dashes = sub_paths(&dashed);
+    assert_eq!(dashes.len(), 8);
+    // A dash crossing a corner keeps the corner.
+    assert_eq!(dashes[1].len(), 4);
+    let wrapped = &dashes[7];
+    assert_eq!(wrapped.len(), 4);
+    match wrapped[0] {
+        PathEvent::Begin { at } => assert!((at - point(0.0, 1.0)).length() < 0.001),
+        evt => panic!("{:?}", evt),
+    }
+    assert_eq!(
+        wrapped[2],
+        PathEvent::Line {
+            from: point(0.0, 0.0),
+            to: point(2.0, 0.0)
+        }
+    );
+
+    // Odd patterns are repeated.
+    let dashed = dash_path(path.as_slice(), &[4.0], 0.0, 0.01);
+    assert_eq!(sub_paths(&dashed).len(), 5);
+
+    // A dash longer than the sub-path produces the closed sub-path.
+    let dashed = dash_path(path.as_slice(), &[50.0, 1.0], 0.0, 0.01);
+    let dashes = sub_paths(&dashed);
+    assert_eq!(dashes.len(), 1);
+    assert_eq!(dashes[0], path.iter().collect::<Vec<_>>());
+
+    // Invalid patterns don't dash the path.
+    let dashed = dash_path(path.as_slice(), &[], 0.0, 0.01);
+    assert_eq!(sub_paths(&dashed)[0], path.iter().collect::<Vec<_>>());
+    let dashed = dash_path(path.as_slice(), &[1.0, -1.0], 0.0, 0.01);
+    assert_eq!(sub_paths(&dashed)[0], path.iter().collect::<Vec<_>>());
+
+    // Patterns that would produce too many dashes don't dash the path.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(100.0, 0.0));
+    builder.end(false);
+    let line = builder.build();
+    let dashed = dash_path(line.as_slice(), &[1e-6, 1e-6], 0.0, 0.01);
+    assert_eq!(sub_paths(&dashed)[0], line.iter().collect::<Vec<_>>());
+
+    // Patterns smaller than the tolerance are fine otherwise.
+    let dashed = dash_path(line.as_slice(), &[0.005, 0.005], 0.0, 0.01);
+    assert!(sub_paths(&dashed).len() > 9000);
+
+    // Zero-length dashes produce dots, including at the end of open sub-paths.
+    let dashed = dash_path(line.as_slice(), &[0.0, 25.0], 0.0, 0.01);
+    let dots = sub_paths(&dashed);
+    assert_eq!(dots.len(), 5);
+    for (i, dot) in dots.iter().enumerate() {
+        let at = point(i as f32 * 25.0, 0.0);
+        assert_eq!(
+            dot[..],
+            [
+                PathEvent::Begin { at },
+                PathEvent::End {
+                    last: at,
+                    first: at,
+                    close: false
+                },
+            ]
+        );
+    }
+    // On closed sub-paths, the start point is only dotted once.
+    let dashed = dash_path(path.as_slice(), &[0.0, 4.0], 0.0, 0.01);
+    assert_eq!(sub_paths(&dashed).len(), 10);
+
+    // Stop when the position doesn't advance anymore.
+    let mut dashes = Vec::new();
+    collect_dashes(&[1e12, 1.0], 0.0, 1e17, false, &mut dashes);
+    assert!(!dashes.is_empty());
+    assert!(dashes.len() < 100_000);
+
+    // Curves are kept.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.cubic_bezier_to(point(10.0, 10.0), point(20.0, -10.0), point(30.0, 0.0));
+    builder.end(false);
+    let path = builder.build();
+    let dashed = dash_path(path.as_slice(), &[5.0, 5.0], 0.0, 0.01);
+    for dash in sub_paths(&dashed) {
+        assert_eq!(dash.len(), 3);
+        match dash[1] {
+            PathEvent::Cubic { .. } => {}
+            evt => panic!("{:?}", evt),
+        }
+    }
+}
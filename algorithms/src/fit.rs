@@ -1,9 +1,11 @@
-//! Fit paths into rectangles.
+//! Fit paths into rectangles, and sequences of points into curves.
 
 use crate::aabb::bounding_rect;
+use crate::geom::CubicBezierSegment;
 use crate::math::*;
+use crate::path::builder::PathBuilder;
 use crate::path::iterator::*;
-use crate::path::Path;
+use crate::path::{Path, PathEvent};
 
 /// The strategy to use when fitting (stretching, overflow, etc.)
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -62,6 +64,445 @@ pub fn fit_path(path: &Path, output_rect: &Rect, style: FitStyle) -> Path {
     builder.build()
 }
 
+/// Parameters for the curve fitting functions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct CurveFitOptions {
+    /// Maximum allowed distance between the input points and the fitted curves.
+    ///
+    /// Default value: `CurveFitOptions::DEFAULT_TOLERANCE`.
+    pub tolerance: f32,
+    /// Points where the direction changes by more than this angle are treated as corners,
+    /// where the fitted curves meet without tangent continuity.
+    ///
+    /// Default value: `CurveFitOptions::DEFAULT_CORNER_ANGLE`.
+    pub corner_angle: Angle,
+    /// Maximum number of attempts at improving the parameterization of a set of points
+    /// before splitting it into two curves.
+    ///
+    /// Default value: `CurveFitOptions::DEFAULT_MAX_ITERATIONS`.
+    pub max_iterations: u32,
+
+    // To be able to add fields without making it a breaking change, add an empty private field
+    // which makes it impossible to create a CurveFitOptions without calling the constructor.
+    _private: (),
+}
+
+impl Default for CurveFitOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl CurveFitOptions {
+    /// Default fitting tolerance.
+    pub const DEFAULT_TOLERANCE: f32 = 0.1;
+    /// Default corner angle (60 degrees).
+    pub const DEFAULT_CORNER_ANGLE: Angle = Angle {
+        radians: std::f32::consts::FRAC_PI_3,
+    };
+    /// Default number of reparameterization iterations.
+    pub const DEFAULT_MAX_ITERATIONS: u32 = 4;
+
+    pub const DEFAULT: Self = CurveFitOptions {
+        tolerance: Self::DEFAULT_TOLERANCE,
+        corner_angle: Self::DEFAULT_CORNER_ANGLE,
+        max_iterations: Self::DEFAULT_MAX_ITERATIONS,
+        _private: (),
+    };
+
+    #[inline]
+    pub fn tolerance(tolerance: f32) -> Self {
+        Self::DEFAULT.with_tolerance(tolerance)
+    }
+
+    #[inline]
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    #[inline]
+    pub fn with_corner_angle(mut self, angle: Angle) -> Self {
+        self.corner_angle = angle;
+        self
+    }
+
+    #[inline]
+    pub fn with_max_iterations(mut self, iterations: u32) -> Self {
+        self.max_iterations = iterations;
+        self
+    }
+}
+
+/// Approximates a polyline with a sequence of cubic bézier curves and adds it to a builder
+/// as a sub-path.
+///
+/// This is the reverse of flattening. The points are split at corners, and each smooth
+/// section is fitted using Philip J. Schneider's algorithm ("An Algorithm for Automatically
+/// Fitting Digitized Curves", Graphics Gems, 1990), subdividing until all points are within
+/// the tolerance of the curves.
+///
+/// Direction changes are measured between points that are at least `tolerance` apart, so
+/// that noise in densely sampled input (for example pen strokes) isn't mistaken for corners.
+///
+/// There must be no sub-path in progress when this function is called.
+/// No sub-path is in progress after the function is called.
+///
+/// ```
+/// use lyon_algorithms::fit::{fit_cubic_beziers, CurveFitOptions};
+/// use lyon_algorithms::math::point;
+/// use lyon_algorithms::path::Path;
+///
+/// // Points along a half circle.
+/// let points: Vec<_> = (0..=100)
+///     .map(|i| {
+///         let angle = std::f32::consts::PI * i as f32 / 100.0;
+///         point(angle.cos() * 50.0, angle.sin() * 50.0)
+///     })
+///     .collect();
+///
+/// let mut builder = Path::builder();
+/// fit_cubic_beziers(&points, false, &CurveFitOptions::tolerance(0.1), &mut builder);
+/// let path = builder.build();
+///
+/// // A couple of curves are enough to represent the hundred line segments.
+/// assert!(path.iter().count() < 10);
+/// ```
+pub fn fit_cubic_beziers<Builder: PathBuilder>(
+    points: &[Point],
+    closed: bool,
+    options: &CurveFitOptions,
+    output: &mut Builder,
+) {
+    // Remove consecutive duplicates, which don't have a tangent.
+    let mut pts: Vec<Point> = Vec::with_capacity(points.len() + 1);
+    for p in points {
+        if pts.last().map_or(true, |last| (*p - *last).square_length() > EPSILON_SQUARED) {
+            pts.push(*p);
+        }
+    }
+    if closed {
+        while pts.len() > 1 && (pts[0] - pts[pts.len() - 1]).square_length() <= EPSILON_SQUARED {
+            pts.pop();
+        }
+    }
+
+    if pts.is_empty() {
+        return;
+    }
+
+    if pts.len() < 3 {
+        output.begin(pts[0]);
+        for p in &pts[1..] {
+            output.line_to(*p);
+        }
+        output.end(closed);
+        return;
+    }
+
+    let tolerance = options.tolerance.max(EPSILON);
+    let mut corners: Vec<usize> = Vec::new();
+    let n = pts.len();
+    let range = if closed { 0..n } else { 1..(n - 1) };
+    for i in range {
+        if is_corner(&pts, i, closed, tolerance, options.corner_angle) {
+            corners.push(i);
+        }
+    }
+
+    // Closed sub-paths without corners have a continuous tangent at their first point.
+    let smooth_loop = closed && corners.is_empty();
+
+    // Closed sub-paths start at a corner if there is one, and are then
+    // fitted like open ones ending at their first point.
+    if closed {
+        let start = corners.first().cloned().unwrap_or(0);
+        pts.rotate_left(start);
+        for corner in &mut corners {
+            *corner -= start;
+        }
+        if corners.is_empty() {
+            corners.push(0);
+        }
+        let first = pts[0];
+        pts.push(first);
+    } else {
+        corners.insert(0, 0);
+    }
+    corners.push(pts.len() - 1);
+
+    let mut fitter = Fitter {
+        output,
+        tolerance,
+        max_iterations: options.max_iterations,
+    };
+
+    fitter.output.begin(pts[0]);
+    for range in corners.windows(2) {
+        let section = &pts[range[0]..=range[1]];
+        let (start_tangent, end_tangent) = if smooth_loop {
+            let n = pts.len();
+            let t = (pts[1] - pts[n - 2]).normalize();
+            (t, -t)
+        } else {
+            (start_tangent(section), end_tangent(section))
+        };
+        fitter.fit(section, start_tangent, end_tangent);
+    }
+    fitter.output.end(closed);
+}
+
+/// Approximates the sub-paths of a path with sequences of cubic bézier curves.
+///
+/// Curves in the input are flattened first, so this is mostly useful with paths made of
+/// line segments, for example the result of flattening.
+///
+/// See [fit_cubic_beziers](fn.fit_cubic_beziers.html).
+pub fn fit_path_to_curves<Iter>(path: Iter, options: &CurveFitOptions) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut builder = Path::builder();
+    let mut points = Vec::new();
+    for evt in path.into_iter().flattened(options.tolerance * 0.25) {
+        match evt {
+            PathEvent::Begin { at } => {
+                points.clear();
+                points.push(at);
+            }
+            PathEvent::Line { to, .. } => {
+                points.push(to);
+            }
+            PathEvent::End { close, .. } => {
+                fit_cubic_beziers(&points, close, options, &mut builder);
+            }
+            _ => {}
+        }
+    }
+
+    builder.build()
+}
+
+const EPSILON: f32 = 1e-4;
+const EPSILON_SQUARED: f32 = EPSILON * EPSILON;
+
+/// Whether the direction changes by more than `corner_angle` at the point of index `idx`.
+fn is_corner(
+    pts: &[Point],
+    idx: usize,
+    closed: bool,
+    min_distance: f32,
+    corner_angle: Angle,
+) -> bool {
+    let n = pts.len() as isize;
+    let p = pts[idx];
+    let min_sq = min_distance * min_distance;
+    let neighbor = |step: isize| -> Option<Vector> {
+        let mut i = idx as isize;
+        let mut last = None;
+        for _ in 1..n {
+            i += step;
+            if !closed && (i < 0 || i >= n) {
+                break;
+            }
+            let v = pts[i.rem_euclid(n) as usize] - p;
+            last = Some(v);
+            if v.square_length() >= min_sq {
+                break;
+            }
+        }
+
+        last
+    };
+
+    match (neighbor(-1), neighbor(1)) {
+        (Some(prev), Some(next)) => {
+            // prev and next point away from p, a straight line has an angle of pi between them.
+            let angle = prev.angle_to(next).radians.abs();
+            std::f32::consts::PI - angle > corner_angle.radians
+        }
+        _ => false,
+    }
+}
+
+fn start_tangent(pts: &[Point]) -> Vector {
+    (pts[1] - pts[0]).normalize()
+}
+
+fn end_tangent(pts: &[Point]) -> Vector {
+    let n = pts.len();
+    (pts[n - 2] - pts[n - 1]).normalize()
+}
+
+struct Fitter<'l, Builder> {
+    output: &'l mut Builder,
+    tolerance: f32,
+    max_iterations: u32,
+}
+
+impl<'l, Builder: PathBuilder> Fitter<'l, Builder> {
+    /// Fits a cubic bézier curve to the points, with the given unit tangents pointing inward
+    /// at each end, or recursively subdivides them if it isn't within the tolerance.
+    fn fit(&mut self, pts: &[Point], start_tangent: Vector, end_tangent: Vector) {
+        let n = pts.len();
+        let from = pts[0];
+        let to = pts[n - 1];
+
+        if n == 2 {
+            let d = (to - from).length() / 3.0;
+            self.output.cubic_bezier_to(
+                from + start_tangent * d,
+                to + end_tangent * d,
+                to,
+            );
+            return;
+        }
+
+        let mut params = chord_length_parameterize(pts);
+        let mut curve = generate_bezier(pts, &params, start_tangent, end_tangent);
+        let tolerance_sq = self.tolerance * self.tolerance;
+        let (mut max_error, mut split) = max_error(pts, &params, &curve);
+
+        // If the error isn't too large, try improving the parameterization.
+        if max_error < tolerance_sq * 16.0 {
+            for _ in 0..self.max_iterations {
+                if max_error <= tolerance_sq {
+                    break;
+                }
+                reparameterize(pts, &mut params, &curve);
+                curve = generate_bezier(pts, &params, start_tangent, end_tangent);
+                let (err, idx) = self::max_error(pts, &params, &curve);
+                max_error = err;
+                split = idx;
+            }
+        }
+
+        if max_error <= tolerance_sq {
+            self.output.cubic_bezier_to(curve.ctrl1, curve.ctrl2, curve.to);
+            return;
+        }
+
+        let split = split.max(1).min(n - 2);
+        let mut center_tangent = pts[split - 1] - pts[split + 1];
+        if center_tangent.square_length() <= EPSILON_SQUARED {
+            center_tangent = pts[split - 1] - pts[split];
+        }
+        let center_tangent = center_tangent.normalize();
+
+        self.fit(&pts[..=split], start_tangent, center_tangent);
+        self.fit(&pts[split..], -center_tangent, end_tangent);
+    }
+}
+
+/// Assigns a parameter to each point proportional to the distance along the polyline.
+fn chord_length_parameterize(pts: &[Point]) -> Vec<f32> {
+    let mut params = Vec::with_capacity(pts.len());
+    let mut length = 0.0;
+    params.push(0.0);
+    for i in 1..pts.len() {
+        length += (pts[i] - pts[i - 1]).length();
+        params.push(length);
+    }
+    for p in &mut params {
+        *p /= length;
+    }
+
+    params
+}
+
+/// Least squares fit of a cubic bézier curve with fixed endpoints and tangent directions.
+fn generate_bezier(
+    pts: &[Point],
+    params: &[f32],
+    start_tangent: Vector,
+    end_tangent: Vector,
+) -> CubicBezierSegment<f32> {
+    let from = pts[0];
+    let to = pts[pts.len() - 1];
+
+    let mut c = [[0.0f32; 2]; 2];
+    let mut x = [0.0f32; 2];
+    for (p, &t) in pts.iter().zip(params) {
+        let mt = 1.0 - t;
+        let b0 = mt * mt * mt;
+        let b1 = 3.0 * t * mt * mt;
+        let b2 = 3.0 * t * t * mt;
+        let b3 = t * t * t;
+        let a0 = start_tangent * b1;
+        let a1 = end_tangent * b2;
+
+        c[0][0] += a0.dot(a0);
+        c[0][1] += a0.dot(a1);
+        c[1][1] += a1.dot(a1);
+
+        let tmp = *p - (from.to_vector() * (b0 + b1) + to.to_vector() * (b2 + b3));
+        x[0] += a0.dot(tmp.to_vector());
+        x[1] += a1.dot(tmp.to_vector());
+    }
+    c[1][0] = c[0][1];
+
+    let det_c0_c1 = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+    let det_c0_x = c[0][0] * x[1] - c[1][0] * x[0];
+    let det_x_c1 = x[0] * c[1][1] - x[1] * c[0][1];
+
+    let (alpha_l, alpha_r) = if det_c0_c1.abs() > std::f32::EPSILON {
+        (det_x_c1 / det_c0_c1, det_c0_x / det_c0_c1)
+    } else {
+        (0.0, 0.0)
+    };
+
+    // Negative or tiny values don't produce good curves, fall back to a
+    // heuristic in that case.
+    let length = (to - from).length();
+    let epsilon = 1e-6 * length;
+    let (alpha_l, alpha_r) = if alpha_l < epsilon || alpha_r < epsilon {
+        (length / 3.0, length / 3.0)
+    } else {
+        (alpha_l, alpha_r)
+    };
+
+    CubicBezierSegment {
+        from,
+        ctrl1: from + start_tangent * alpha_l,
+        ctrl2: to + end_tangent * alpha_r,
+        to,
+    }
+}
+
+/// Returns the maximum square distance between the points and the curve at their parameter,
+/// and the index of the farthest point.
+fn max_error(pts: &[Point], params: &[f32], curve: &CubicBezierSegment<f32>) -> (f32, usize) {
+    let mut max = 0.0;
+    let mut idx = pts.len() / 2;
+    for i in 1..(pts.len() - 1) {
+        let d = (curve.sample(params[i]) - pts[i]).square_length();
+        if d > max {
+            max = d;
+            idx = i;
+        }
+    }
+
+    (max, idx)
+}
+
+/// Improves the parameters of the points with a newton iteration on the
+/// distance to the curve.
+fn reparameterize(pts: &[Point], params: &mut [f32], curve: &CubicBezierSegment<f32>) {
+    let d2_a = (curve.ctrl2 - curve.ctrl1) - (curve.ctrl1 - curve.from);
+    let d2_b = (curve.to - curve.ctrl2) - (curve.ctrl2 - curve.ctrl1);
+    for (p, t) in pts.iter().zip(params.iter_mut()) {
+        let diff = curve.sample(*t) - *p;
+        let d1 = curve.derivative(*t);
+        let d2 = (d2_a * (1.0 - *t) + d2_b * *t) * 6.0;
+        let numerator = diff.dot(d1);
+        let denominator = d1.dot(d1) + diff.dot(d2);
+        if denominator.abs() > std::f32::EPSILON {
+            *t = (*t - numerator / denominator).max(0.0).min(1.0);
+        }
+    }
+}
+
 #[test]
 fn simple_fit() {
     fn approx_eq(a: &Rect, b: &Rect) -> bool {
@@ -117,3 +558,136 @@ fn simple_fit() {
         &rect(0.0, -1.0, 4.0, 4.0)
     ));
 }
+
+#[test]
+fn fit_cubic_beziers_within_tolerance() {
+    use crate::geom::{Arc, LineSegment};
+
+    fn check(points: &[Point], closed: bool, tolerance: f32) -> Path {
+        let mut builder = Path::builder();
+        fit_cubic_beziers(points, closed, &CurveFitOptions::tolerance(tolerance), &mut builder);
+        let path = builder.build();
+
+        // All points are within the tolerance of the path.
+        for p in points {
+            let mut d = std::f32::MAX;
+            for evt in path.iter() {
+                match evt {
+                    PathEvent::Cubic { from, ctrl1, ctrl2, to } => {
+                        let curve = CubicBezierSegment { from, ctrl1, ctrl2, to };
+                        let mut prev = from;
+                        curve.for_each_flattened(0.001, &mut |to| {
+                            d = d.min(LineSegment { from: prev, to }.distance_to_point(*p));
+                            prev = to;
+                        });
+                    }
+                    PathEvent::Line { from, to } | PathEvent::End { last: from, first: to, .. } => {
+                        d = d.min(LineSegment { from, to }.distance_to_point(*p));
+                    }
+                    PathEvent::Begin { at } => {
+                        d = d.min((at - *p).length());
+                    }
+                    _ => {}
+                }
+            }
+            assert!(d <= tolerance * 1.01, "{:?} is {} away from the path", p, d);
+        }
+
+        path
+    }
+
+    let arc = Arc {
+        center: point(0.0, 0.0),
+        radii: vector(100.0, 50.0),
+        start_angle: Angle::radians(0.0),
+        sweep_angle: Angle::radians(4.0),
+        x_rotation: Angle::radians(0.3),
+    };
+    let mut points = vec![arc.from()];
+    arc.for_each_flattened(0.001, &mut |p| points.push(p));
+    let path = check(&points, false, 0.1);
+    let curves = path.iter().filter(|evt| matches!(evt, PathEvent::Cubic { .. })).count();
+    assert!(curves >= 2 && curves <= 8, "{} curves", curves);
+
+    // Noisy input.
+    let noisy: Vec<Point> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| *p + vector(((i * 7) % 5) as f32 * 0.02, ((i * 3) % 4) as f32 * 0.02))
+        .collect();
+    let path = check(&noisy, false, 0.2);
+    assert!(path.iter().count() < points.len() / 4);
+
+    // A square has four corners and four straight sides.
+    let mut square = Vec::new();
+    for i in 0..40 {
+        square.push(point(i as f32, 0.0));
+    }
+    for i in 0..40 {
+        square.push(point(40.0, i as f32));
+    }
+    for i in 0..40 {
+        square.push(point(40.0 - i as f32, 40.0));
+    }
+    for i in 0..40 {
+        square.push(point(0.0, 40.0 - i as f32));
+    }
+    let path = check(&square, true, 0.1);
+    let endpoints: Vec<Point> = path
+        .iter()
+        .filter_map(|evt| match evt {
+            PathEvent::Cubic { to, .. } => Some(to),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        endpoints,
+        vec![point(40.0, 0.0), point(40.0, 40.0), point(0.0, 40.0), point(0.0, 0.0)]
+    );
+
+    // A smooth closed loop.
+    let circle: Vec<Point> = (0..64)
+        .map(|i| {
+            let a = i as f32 * std::f32::consts::PI * 2.0 / 64.0;
+            point(a.cos() * 20.0, a.sin() * 20.0)
+        })
+        .collect();
+    let path = check(&circle, true, 0.05);
+    // The tangent is continuous at the start of the loop.
+    let events: Vec<PathEvent> = path.iter().collect();
+    if let (PathEvent::Cubic { from, ctrl1, .. }, PathEvent::Cubic { ctrl2, to, .. }) =
+        (events[1], events[events.len() - 2])
+    {
+        assert_eq!(from, to);
+        assert!((ctrl1 - from).normalize().dot((to - ctrl2).normalize()) > 0.999);
+    } else {
+        panic!();
+    }
+
+    // Degenerate inputs.
+    check(&[], false, 0.1);
+    check(&[point(1.0, 1.0)], false, 0.1);
+    check(&[point(1.0, 1.0), point(1.0, 1.0), point(2.0, 1.0)], false, 0.1);
+}
+
+#[test]
+fn fit_path_to_curves_round_trip() {
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.cubic_bezier_to(point(10.0, 40.0), point(50.0, 40.0), point(60.0, 0.0));
+    builder.line_to(point(60.0, -20.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let flattened: Vec<PathEvent> = path.iter().flattened(0.01).collect();
+    let fitted = fit_path_to_curves(flattened.iter().cloned(), &CurveFitOptions::tolerance(0.05));
+
+    let events: Vec<PathEvent> = fitted.iter().collect();
+    assert!(events.len() < flattened.len() / 4);
+    assert_eq!(events.first(), Some(&PathEvent::Begin { at: point(0.0, 0.0) }));
+    // There is a corner at (60, 0).
+    assert!(events.iter().any(|evt| match evt {
+        PathEvent::Cubic { to, .. } => (*to - point(60.0, 0.0)).length() < 1e-3,
+        _ => false,
+    }));
+}
@@ -0,0 +1,511 @@
+//! Convex hulls and minimal bounding shapes.
+//!
+//! # Example
+//!
+//! ```
+//! use lyon_algorithms::hull::{convex_hull, enclosing_circle, oriented_bounding_rect};
+//! use lyon_algorithms::path::Path;
+//! use lyon_algorithms::math::point;
+//!
+//! let mut builder = Path::builder();
+//! builder.begin(point(0.0, 0.0));
+//! builder.line_to(point(10.0, 0.0));
+//! builder.quadratic_bezier_to(point(10.0, 10.0), point(0.0, 10.0));
+//! builder.line_to(point(5.0, 5.0));
+//! builder.end(true);
+//! let path = builder.build();
+//!
+//! let hull = convex_hull(path.iter(), 0.01);
+//! let rect = oriented_bounding_rect(&hull);
+//! let circle = enclosing_circle(&hull);
+//! ```
+
+use crate::geom::predicates::orient2d;
+use crate::math::{point, vector, Angle, Point, Rect, Vector};
+use crate::path::iterator::PathIterator;
+use crate::path::PathEvent;
+
+/// Computes a convex polygon that contains the path, using the control points of the curves.
+///
+/// This is faster but less tight than [`convex_hull`](fn.convex_hull.html) for paths that
+/// contain curves.
+///
+/// The points of the polygon are in positive order (see `Winding`), without collinear
+/// points.
+pub fn fast_convex_hull<Iter>(path: Iter) -> Vec<Point>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut points = Vec::new();
+    for evt in path {
+        match evt {
+            PathEvent::Begin { at } => points.push(at),
+            PathEvent::Line { to, .. } => points.push(to),
+            PathEvent::Quadratic { ctrl, to, .. } => points.extend_from_slice(&[ctrl, to]),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => points.extend_from_slice(&[ctrl1, ctrl2, to]),
+            PathEvent::End { .. } => {}
+        }
+    }
+
+    convex_hull_of_points(points)
+}
+
+/// Computes a conservative convex hull of the path, approximating curves within `tolerance`.
+///
+/// The flattened curves can be up to `tolerance` inside of the actual curves, so the hull of
+/// paths that contain curves is pushed out by `tolerance`. The resulting polygon contains the
+/// path and is at most about `tolerance` larger than its exact convex hull. The hull of paths
+/// without curves is exact.
+///
+/// The points of the polygon are in positive order (see `Winding`), without collinear
+/// points.
+pub fn convex_hull<Iter>(path: Iter, tolerance: f32) -> Vec<Point>
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut has_curves = false;
+    let hull = fast_convex_hull(
+        path.into_iter()
+            .inspect(|evt| {
+                has_curves |= matches!(evt, PathEvent::Quadratic { .. } | PathEvent::Cubic { .. })
+            })
+            .flattened(tolerance),
+    );
+
+    if !has_curves || !(tolerance > 0.0) {
+        return hull;
+    }
+
+    convex_hull_of_points(inflated_points(&hull, tolerance))
+}
+
+/// Returns points whose convex hull contains everything within `distance` of a convex
+/// polygon in positive order.
+///
+/// The rounded corners of the inflated polygon are replaced with polygons around them, with
+/// sides turning by at most 45 degrees.
+fn inflated_points(hull: &[Point], distance: f32) -> Vec<Point> {
+    use std::f32::consts::PI;
+
+    let n = hull.len();
+    // The outward normal of the edge starting at a given vertex.
+    let normal = |i: usize| {
+        let edge = hull[(i + 1) % n] - hull[i];
+        vector(edge.y, -edge.x).normalize()
+    };
+
+    let mut points = Vec::with_capacity(n * 4);
+    for i in 0..n {
+        let (start, angle) = if n == 1 {
+            (vector(1.0, 0.0), PI * 2.0)
+        } else {
+            let (n_in, n_out) = (normal((i + n - 1) % n), normal(i));
+            (n_in, n_in.cross(n_out).atan2(n_in.dot(n_out)).abs())
+        };
+
+        let steps = (angle / (PI / 4.0)).ceil().max(1.0);
+        let step = angle / steps;
+        let radius = distance / (step * 0.5).cos();
+        let rotate = |v: Vector, a: f32| {
+            let (sin, cos) = a.sin_cos();
+            vector(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+        };
+
+        let p = hull[i];
+        points.push(p + start * distance);
+        for j in 0..steps as usize {
+            points.push(p + rotate(start, (j as f32 + 0.5) * step) * radius);
+        }
+        points.push(p + rotate(start, angle) * distance);
+    }
+
+    points
+}
+
+/// Computes the convex hull of a set of points.
+///
+/// The points of the polygon are in positive order (see `Winding`), without collinear
+/// points. Points with non-finite coordinates are ignored.
+pub fn convex_hull_of_points(mut points: Vec<Point>) -> Vec<Point> {
+    points.retain(|p| p.x.is_finite() && p.y.is_finite());
+
+    // Andrew's monotone chain algorithm.
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap()
+            .then(a.y.partial_cmp(&b.y).unwrap())
+    });
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull: Vec<Point> = Vec::with_capacity(points.len() + 1);
+
+    // Lower part.
+    for p in &points {
+        while hull.len() >= 2 && orient2d(hull[hull.len() - 2], hull[hull.len() - 1], *p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    // Upper part, without removing points from the lower part.
+    let min_len = hull.len() + 1;
+    for p in points.iter().rev().skip(1) {
+        while hull.len() >= min_len
+            && orient2d(hull[hull.len() - 2], hull[hull.len() - 1], *p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    // The first point was added again at the end.
+    hull.pop();
+
+    hull
+}
+
+/// A rectangle rotated around the origin.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OrientedRect {
+    /// The rectangle in its own coordinate system.
+    pub rect: Rect,
+    /// The rotation from the coordinate system of the rectangle to the one of the path.
+    pub angle: Angle,
+}
+
+impl OrientedRect {
+    /// Returns the corners of the rectangle in positive order.
+    pub fn points(&self) -> [Point; 4] {
+        let (sin, cos) = self.angle.sin_cos();
+        let rotate = |p: Point| point(p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+        let r = &self.rect;
+        [
+            rotate(r.min()),
+            rotate(point(r.max_x(), r.min_y())),
+            rotate(r.max()),
+            rotate(point(r.min_x(), r.max_y())),
+        ]
+    }
+
+    #[inline]
+    pub fn area(&self) -> f32 {
+        self.rect.size.area()
+    }
+}
+
+/// Computes the rectangle with the smallest area that contains a convex polygon.
+///
+/// The polygon is expected to be convex and in positive order, like the ones produced by
+/// [`convex_hull`](fn.convex_hull.html). One of the sides of the rectangle is aligned with
+/// an edge of the polygon.
+pub fn oriented_bounding_rect(hull: &[Point]) -> OrientedRect {
+    let mut best = OrientedRect {
+        rect: bounding_rect_in_frame(hull, vector(1.0, 0.0)),
+        angle: Angle::zero(),
+    };
+
+    let n = hull.len();
+    for i in 0..n {
+        let edge = hull[(i + 1) % n] - hull[i];
+        if edge.square_length() == 0.0 {
+            continue;
+        }
+        let axis = edge.normalize();
+        let rect = bounding_rect_in_frame(hull, axis);
+        if rect.size.area() < best.area() {
+            best = OrientedRect {
+                rect,
+                angle: axis.angle_from_x_axis(),
+            };
+        }
+    }
+
+    best
+}
+
+/// Computes the bounding rectangle of the points in the coordinate system with the x
+/// axis along `axis`.
+fn bounding_rect_in_frame(points: &[Point], axis: Vector) -> Rect {
+    if points.is_empty() {
+        return Rect::zero();
+    }
+
+    let normal = vector(-axis.y, axis.x);
+    let mut min = point(std::f32::MAX, std::f32::MAX);
+    let mut max = point(std::f32::MIN, std::f32::MIN);
+    for p in points {
+        let local = point(p.to_vector().dot(axis), p.to_vector().dot(normal));
+        min = min.min(local);
+        max = max.max(local);
+    }
+
+    Rect {
+        origin: min,
+        size: (max - min).to_size(),
+    }
+}
+
+/// A circle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: f32,
+}
+
+impl Circle {
+    #[inline]
+    pub fn contains(&self, p: Point) -> bool {
+        (p - self.center).square_length() <= self.radius * self.radius
+    }
+}
+
+/// Computes the smallest circle that contains a set of points.
+///
+/// Running it on the convex hull of a path gives the smallest circle that contains the
+/// path, while having less points to process.
+pub fn enclosing_circle(points: &[Point]) -> Circle {
+    // Welzl's algorithm, in its iterative form. Shuffling the points (deterministically)
+    // gives an expected linear running time.
+    let mut points: Vec<Point> = points.to_vec();
+    let mut seed: u32 = 0x9e37_79b9;
+    for i in (1..points.len()).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        points.swap(i, seed as usize % (i + 1));
+    }
+
+    let mut circle = match points.first() {
+        Some(p) => Circle {
+            center: *p,
+            radius: 0.0,
+        },
+        None => {
+            return Circle {
+                center: point(0.0, 0.0),
+                radius: 0.0,
+            }
+        }
+    };
+
+    for i in 1..points.len() {
+        if contains(&circle, points[i]) {
+            continue;
+        }
+        circle = circle_from_2(points[i], points[0]);
+        for j in 1..i {
+            if contains(&circle, points[j]) {
+                continue;
+            }
+            circle = circle_from_2(points[i], points[j]);
+            for k in 0..j {
+                if !contains(&circle, points[k]) {
+                    circle = circle_from_3(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+
+    circle
+}
+
+/// Tests whether the point is in the circle, allowing some rounding error.
+fn contains(circle: &Circle, p: Point) -> bool {
+    (p - circle.center).length() <= circle.radius * (1.0 + 1e-5) + 1e-6
+}
+
+fn circle_from_2(a: Point, b: Point) -> Circle {
+    Circle {
+        center: a.lerp(b, 0.5),
+        radius: (b - a).length() * 0.5,
+    }
+}
+
+fn circle_from_3(a: Point, b: Point, c: Point) -> Circle {
+    // Compute the circumcenter relatively to `a` in double precision.
+    let b = (b - a).to_f64();
+    let c = (c - a).to_f64();
+    let d = 2.0 * (b.x * c.y - b.y * c.x);
+    if d == 0.0 {
+        // The points are aligned, the circle goes through the two furthest ones.
+        let candidates = [
+            circle_from_2(a, a + b.to_f32()),
+            circle_from_2(a, a + c.to_f32()),
+            circle_from_2(a + b.to_f32(), a + c.to_f32()),
+        ];
+        let mut best = candidates[0];
+        for candidate in &candidates[1..] {
+            if candidate.radius > best.radius {
+                best = *candidate;
+            }
+        }
+        return best;
+    }
+
+    let b2 = b.square_length();
+    let c2 = c.square_length();
+    let x = (c.y * b2 - b.y * c2) / d;
+    let y = (b.x * c2 - c.x * b2) / d;
+
+    Circle {
+        center: a + vector(x as f32, y as f32),
+        radius: (x * x + y * y).sqrt() as f32,
+    }
+}
+
+#[test]
+fn hulls() {
+    use crate::path::Path;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(5.0, 1.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(5.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let hull = convex_hull(path.iter(), 0.01);
+    assert_eq!(
+        hull,
+        vec![
+            point(0.0, 0.0),
+            point(10.0, 0.0),
+            point(10.0, 10.0),
+            point(0.0, 10.0),
+        ]
+    );
+
+    // Curves.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.quadratic_bezier_to(point(5.0, 10.0), point(10.0, 0.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let fast = fast_convex_hull(path.iter());
+    assert_eq!(
+        fast,
+        vec![point(0.0, 0.0), point(10.0, 0.0), point(5.0, 10.0)]
+    );
+    let hull = convex_hull(path.iter(), 0.01);
+    assert!(hull.len() > 3);
+    for p in &hull {
+        assert!(p.y <= 5.0 + 0.02);
+    }
+    assert!(hull.iter().any(|p| p.y >= 5.0));
+
+    // The hull is conservative: the curve is inside of it, even with a large tolerance.
+    let curve = crate::geom::QuadraticBezierSegment {
+        from: point(0.0, 0.0),
+        ctrl: point(5.0, 10.0),
+        to: point(10.0, 0.0),
+    };
+    for tolerance in &[0.01, 0.5, 2.0] {
+        let hull = convex_hull(path.iter(), *tolerance);
+        assert!(hull.iter().all(|p| p.y <= 5.0 + 2.0 * tolerance));
+        for i in 0..=100 {
+            let p = curve.sample(i as f32 / 100.0);
+            for j in 0..hull.len() {
+                assert!(orient2d(hull[j], hull[(j + 1) % hull.len()], p) >= 0.0);
+            }
+        }
+    }
+
+    // Curves collapsing to a point or a segment.
+    let mut builder = Path::builder();
+    builder.begin(point(1.0, 1.0));
+    builder.quadratic_bezier_to(point(1.0, 1.0), point(1.0, 1.0));
+    builder.end(false);
+    let path = builder.build();
+    let hull = convex_hull(path.iter(), 0.1);
+    assert_eq!(hull.len(), 8);
+    for p in &hull {
+        let d = (*p - point(1.0, 1.0)).length();
+        assert!(d >= 0.1 && d <= 0.11);
+    }
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.quadratic_bezier_to(point(1.0, 0.0), point(2.0, 0.0));
+    builder.end(false);
+    let path = builder.build();
+    let hull = convex_hull(path.iter(), 0.1);
+    assert!(hull.iter().any(|p| p.y <= -0.1) && hull.iter().any(|p| p.y >= 0.1));
+    assert!(hull.iter().any(|p| p.x <= -0.1) && hull.iter().any(|p| p.x >= 2.1));
+
+    // Degenerate cases.
+    assert_eq!(convex_hull_of_points(vec![]), vec![]);
+    assert_eq!(
+        convex_hull_of_points(vec![point(1.0, 1.0), point(1.0, 1.0)]),
+        vec![point(1.0, 1.0)]
+    );
+    assert_eq!(
+        convex_hull_of_points(vec![point(2.0, 2.0), point(0.0, 0.0), point(1.0, 1.0)]),
+        vec![point(0.0, 0.0), point(2.0, 2.0)]
+    );
+
+    // Non-finite points are ignored.
+    assert_eq!(
+        convex_hull_of_points(vec![
+            point(0.0, 0.0),
+            point(f32::NAN, 1.0),
+            point(10.0, 0.0),
+            point(1.0, f32::INFINITY),
+            point(0.0, 10.0),
+        ]),
+        vec![point(0.0, 0.0), point(10.0, 0.0), point(0.0, 10.0)]
+    );
+}
+
+#[test]
+fn bounding_shapes() {
+    // A square rotated by 30 degrees.
+    let angle = Angle::degrees(30.0);
+    let (sin, cos) = angle.sin_cos();
+    let rotate = |x: f32, y: f32| point(x * cos - y * sin, x * sin + y * cos);
+    let points = vec![
+        rotate(0.0, 0.0),
+        rotate(4.0, 0.0),
+        rotate(4.0, 2.0),
+        rotate(0.0, 2.0),
+        rotate(2.0, 1.0),
+    ];
+
+    let hull = convex_hull_of_points(points.clone());
+    assert_eq!(hull.len(), 4);
+
+    let rect = oriented_bounding_rect(&hull);
+    assert!((rect.area() - 8.0).abs() < 0.001);
+    for corner in &rect.points() {
+        assert!(points.iter().any(|p| (*p - *corner).length() < 0.001));
+    }
+
+    let circle = enclosing_circle(&points);
+    assert!((circle.radius - 5.0f32.sqrt()).abs() < 0.001);
+    assert!((circle.center - rotate(2.0, 1.0)).length() < 0.001);
+
+    // The circle is defined by three of the points.
+    let points = vec![
+        point(0.0, 0.0),
+        point(10.0, 0.0),
+        point(5.0, 1.0),
+        point(5.0, 8.0),
+    ];
+    let circle = enclosing_circle(&points);
+    for p in &points {
+        assert!(circle.contains(*p) || (*p - circle.center).length() - circle.radius < 0.001);
+    }
+    assert!(circle.radius < 6.0);
+
+    assert_eq!(enclosing_circle(&[]).radius, 0.0);
+    assert_eq!(oriented_bounding_rect(&[]).area(), 0.0);
+}
@@ -10,11 +10,19 @@
 pub extern crate lyon_path as path;
 
 pub mod aabb;
+pub mod area;
+pub mod boolean;
+pub mod dash;
 pub(crate) mod advanced_path;
 pub mod fit;
 pub mod hatching;
 pub mod hit_test;
+pub mod hull;
+pub mod measure;
+pub mod offset;
 pub mod raycast;
+pub mod simplify;
+pub mod spatial_index;
 pub mod splitter;
 pub mod walk;
 
@@ -0,0 +1,469 @@
+//! Random access to positions and tangents at given distances along a path.
+//!
+//! Unlike the [walk](../walk/index.html) module which traverses the path once, a
+//! [PathMeasure](struct.PathMeasure.html) precomputes the length of each segment so that
+//! it can be queried any number of times, in any order, for example to animate a position
+//! along a path or to trim a path.
+//!
+//! # Example
+//!
+//! ```
+//! use lyon_algorithms::measure::PathMeasure;
+//! use lyon_algorithms::path::Path;
+//! use lyon_algorithms::math::{point, vector};
+//!
+//! let mut builder = Path::builder();
+//! builder.begin(point(0.0, 0.0));
+//! builder.line_to(point(10.0, 0.0));
+//! builder.quadratic_bezier_to(point(20.0, 0.0), point(20.0, 10.0));
+//! builder.end(false);
+//! let path = builder.build();
+//!
+//! let measure = PathMeasure::new(path.as_slice(), 0.01);
+//! let (position, tangent) = measure.sample_at(5.0);
+//! assert_eq!(position, point(5.0, 0.0));
+//! assert_eq!(tangent, vector(1.0, 0.0));
+//!
+//! // Extract the part of the path between distances 5 and 15, keeping the curve.
+//! let trimmed = measure.extract(5.0, 15.0);
+//! ```
+
+use crate::geom::{
+    ArcLengthTable, BezierSegment, CubicBezierSegment, LineSegment, QuadraticBezierSegment,
+};
+use crate::math::{Point, Vector};
+use crate::path::builder::PathBuilder;
+use crate::path::{Path, PathEvent, PathSlice};
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Caches the lengths of the segments of a path to answer queries at arbitrary distances
+/// along it.
+///
+/// Distances are measured from the start of the path, across all of its sub-paths. The
+/// closing edges of closed sub-paths are part of the measured path.
+///
+/// See the [module documentation](index.html).
+pub struct PathMeasure {
+    segments: Vec<MeasuredSegment>,
+    sub_paths: Vec<MeasuredSubPath>,
+    length: f32,
+}
+
+struct MeasuredSegment {
+    segment: BezierSegment<f32>,
+    table: ArcLengthTable<f32>,
+    // Distance along the path at the start of the segment.
+    start: f32,
+    // Whether this is the implicit closing edge of a closed sub-path.
+    is_closing_edge: bool,
+}
+
+impl MeasuredSegment {
+    fn end(&self) -> f32 {
+        self.start + self.table.length()
+    }
+}
+
+struct MeasuredSubPath {
+    segments: Range<usize>,
+    start: f32,
+    length: f32,
+    closed: bool,
+}
+
+impl PathMeasure {
+    /// Measures a path.
+    ///
+    /// The measured lengths are within `tolerance` of the exact length of each segment.
+    pub fn new(path: PathSlice, tolerance: f32) -> Self {
+        let mut segments = Vec::new();
+        let mut sub_paths = Vec::new();
+        let mut length = 0.0;
+        let mut sub_path_start = (0, 0.0);
+
+        let add_segment = |segments: &mut Vec<MeasuredSegment>,
+                           segment: BezierSegment<f32>,
+                           is_closing_edge: bool,
+                           length: &mut f32| {
+            let table = match segment {
+                BezierSegment::Linear(s) => ArcLengthTable::new(&s, tolerance),
+                BezierSegment::Quadratic(s) => ArcLengthTable::new(&s, tolerance),
+                BezierSegment::Cubic(s) => ArcLengthTable::new(&s, tolerance),
+            };
+            let start = *length;
+            *length += table.length();
+            segments.push(MeasuredSegment {
+                segment,
+                table,
+                start,
+                is_closing_edge,
+            });
+        };
+
+        for evt in path.iter() {
+            match evt {
+                PathEvent::Begin { .. } => {
+                    sub_path_start = (segments.len(), length);
+                }
+                PathEvent::Line { from, to } => {
+                    let segment = BezierSegment::Linear(LineSegment { from, to });
+                    add_segment(&mut segments, segment, false, &mut length);
+                }
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    let segment =
+                        BezierSegment::Quadratic(QuadraticBezierSegment { from, ctrl, to });
+                    add_segment(&mut segments, segment, false, &mut length);
+                }
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    let segment = BezierSegment::Cubic(CubicBezierSegment {
+                        from,
+                        ctrl1,
+                        ctrl2,
+                        to,
+                    });
+                    add_segment(&mut segments, segment, false, &mut length);
+                }
+                PathEvent::End { last, first, close } => {
+                    if close && last != first {
+                        let segment = BezierSegment::Linear(LineSegment {
+                            from: last,
+                            to: first,
+                        });
+                        add_segment(&mut segments, segment, true, &mut length);
+                    }
+                    sub_paths.push(MeasuredSubPath {
+                        segments: sub_path_start.0..segments.len(),
+                        start: sub_path_start.1,
+                        length: length - sub_path_start.1,
+                        closed: close,
+                    });
+                }
+            }
+        }
+
+        PathMeasure {
+            segments,
+            sub_paths,
+            length,
+        }
+    }
+
+    /// Returns the total length of the path.
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    /// Returns the number of sub-paths.
+    pub fn num_sub_paths(&self) -> usize {
+        self.sub_paths.len()
+    }
+
+    /// Returns the length of the sub-path at a given index.
+    pub fn sub_path_length(&self, index: usize) -> f32 {
+        self.sub_paths[index].length
+    }
+
+    /// Returns the position and the unit tangent at a given distance along the path.
+    ///
+    /// The distance is clamped between zero and the length of the path, and a NaN distance
+    /// is treated as zero. An empty path produces the origin and a zero vector.
+    pub fn sample_at(&self, distance: f32) -> (Point, Vector) {
+        let (segment, t) = match self.segment_at(distance) {
+            Some(s) => s,
+            None => return (Point::origin(), Vector::zero()),
+        };
+
+        (segment.sample(t), tangent(&segment, t))
+    }
+
+    /// Returns the segment at a given distance along the path, and the `t` parameter of the
+    /// position at this distance on the segment.
+    ///
+    /// The distance is clamped between zero and the length of the path, and a NaN distance
+    /// is treated as zero. Returns `None` if the path has no segment.
+    pub fn segment_at(&self, distance: f32) -> Option<(BezierSegment<f32>, f32)> {
+        let distance = self.clamp_distance(distance);
+        let idx = self.segment_index_at(distance)?;
+        let segment = &self.segments[idx];
+        let t = segment.table.t_at_length(distance - segment.start);
+
+        Some((segment.segment, t))
+    }
+
+    /// Builds a path containing the parts of this path between two distances.
+    ///
+    /// Curves are kept as curves, only the segments at the start and end of the range are
+    /// split. Sub-paths that are entirely contained in the range are preserved as is, while
+    /// the parts of the other ones produce open sub-paths.
+    pub fn extract(&self, start: f32, end: f32) -> Path {
+        let mut builder = Path::builder();
+        let start = start.max(0.0);
+        let end = end.min(self.length);
+        if start >= end {
+            return builder.build();
+        }
+
+        for (index, sub_path) in self.sub_paths.iter().enumerate() {
+            let sub_path_end = sub_path.start + sub_path.length;
+            if sub_path_end <= start || sub_path.start >= end {
+                continue;
+            }
+            let whole = sub_path.start >= start && sub_path_end <= end;
+
+            let mut started = false;
+            self.add_range(index, start..end, whole, &mut builder, &mut started);
+            if started {
+                builder.end(whole && sub_path.closed);
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Returns the range of distances along the path covered by the sub-path at a given index.
+    pub fn sub_path_range(&self, index: usize) -> Range<f32> {
+        let sub_path = &self.sub_paths[index];
+        sub_path.start..(sub_path.start + sub_path.length)
+    }
+
+    /// Returns whether the sub-path at a given index is closed.
+    pub fn is_sub_path_closed(&self, index: usize) -> bool {
+        self.sub_paths[index].closed
+    }
+
+    /// Adds the parts of a sub-path within a range of distances to a builder.
+    ///
+    /// A new sub-path is started unless `started` is true, in which case the segments
+    /// continue the current sub-path. The caller is responsible for ending the sub-path.
+    pub(crate) fn add_range<Builder: PathBuilder>(
+        &self,
+        sub_path: usize,
+        range: Range<f32>,
+        skip_closing_edge: bool,
+        builder: &mut Builder,
+        started: &mut bool,
+    ) {
+        let (start, end) = (range.start, range.end);
+        for segment in &self.segments[self.sub_paths[sub_path].segments.clone()] {
+            if segment.end() <= start || segment.start >= end {
+                continue;
+            }
+            if skip_closing_edge && segment.is_closing_edge {
+                continue;
+            }
+
+            let t0 = if segment.start < start {
+                segment.table.t_at_length(start - segment.start)
+            } else {
+                0.0
+            };
+            let t1 = if segment.end() > end {
+                segment.table.t_at_length(end - segment.start)
+            } else {
+                1.0
+            };
+            let piece = if t0 > 0.0 || t1 < 1.0 {
+                segment.segment.split_range(t0..t1)
+            } else {
+                segment.segment
+            };
+
+            if !*started {
+                builder.begin(piece.from());
+                *started = true;
+            }
+            match piece {
+                BezierSegment::Linear(s) => {
+                    builder.line_to(s.to);
+                }
+                BezierSegment::Quadratic(s) => {
+                    builder.quadratic_bezier_to(s.ctrl, s.to);
+                }
+                BezierSegment::Cubic(s) => {
+                    builder.cubic_bezier_to(s.ctrl1, s.ctrl2, s.to);
+                }
+            }
+        }
+    }
+
+    fn clamp_distance(&self, distance: f32) -> f32 {
+        if distance.is_nan() {
+            return 0.0;
+        }
+
+        distance.max(0.0).min(self.length)
+    }
+
+    // Index of the last segment starting before a given, already clamped, distance.
+    fn segment_index_at(&self, distance: f32) -> Option<usize> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        // Segments that start at NaN because of non-finite coordinates must not panic.
+        let idx = match self
+            .segments
+            .binary_search_by(|s| s.start.partial_cmp(&distance).unwrap_or(Ordering::Less))
+        {
+            Ok(idx) => idx,
+            Err(idx) => idx.max(1) - 1,
+        };
+
+        // Skip zero-length segments to find a meaningful tangent.
+        let mut idx = idx;
+        while idx + 1 < self.segments.len() && self.segments[idx].end() <= distance {
+            idx += 1;
+        }
+
+        Some(idx)
+    }
+}
+
+// Unit tangent of a segment, falling back to the direction of nearby points where the
+// derivative vanishes, for example at the end of curves with coincident control points.
+fn tangent(segment: &BezierSegment<f32>, t: f32) -> Vector {
+    let d = segment.derivative(t);
+    if d.square_length() > 1e-12 {
+        return d.normalize();
+    }
+
+    let dt = 1e-3;
+    let (t0, t1) = if t + dt <= 1.0 {
+        (t, t + dt)
+    } else {
+        (t - dt, t)
+    };
+    let v = segment.sample(t1) - segment.sample(t0);
+    if v.square_length() > 0.0 {
+        return v.normalize();
+    }
+
+    let v = segment.to() - segment.from();
+    if v.square_length() > 0.0 {
+        v.normalize()
+    } else {
+        Vector::zero()
+    }
+}
+
+#[test]
+fn path_measure() {
+    use crate::math::{point, vector};
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.cubic_bezier_to(point(15.0, 0.0), point(20.0, 5.0), point(20.0, 10.0));
+    builder.end(false);
+    builder.begin(point(100.0, 0.0));
+    builder.line_to(point(110.0, 0.0));
+    builder.line_to(point(110.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let measure = PathMeasure::new(path.as_slice(), 0.001);
+    let cubic = CubicBezierSegment {
+        from: point(10.0, 0.0),
+        ctrl1: point(15.0, 0.0),
+        ctrl2: point(20.0, 5.0),
+        to: point(20.0, 10.0),
+    };
+    let cubic_length = cubic.approximate_length(0.0001);
+    let triangle_length = 20.0 + 200.0f32.sqrt();
+
+    assert_eq!(measure.num_sub_paths(), 2);
+    assert!((measure.sub_path_length(0) - (10.0 + cubic_length)).abs() < 0.01);
+    assert!((measure.sub_path_length(1) - triangle_length).abs() < 0.01);
+    assert!((measure.length() - (10.0 + cubic_length + triangle_length)).abs() < 0.01);
+
+    assert_eq!(measure.sample_at(-1.0), (point(0.0, 0.0), vector(1.0, 0.0)));
+    assert_eq!(measure.sample_at(2.5), (point(2.5, 0.0), vector(1.0, 0.0)));
+
+    let (segment, t) = measure.segment_at(10.0 + cubic_length * 0.5).unwrap();
+    assert_eq!(segment, BezierSegment::Cubic(cubic));
+    assert!((cubic.length_at_t(t) - cubic_length * 0.5).abs() < 0.01);
+
+    // The closing edge of the second sub-path.
+    let start = measure.sub_path_length(0);
+    let (p, v) = measure.sample_at(start + 20.0 + 200.0f32.sqrt() * 0.5);
+    assert!((p - point(105.0, 5.0)).length() < 0.01);
+    assert!((v - vector(-1.0, -1.0).normalize()).length() < 0.001);
+
+    let (p, v) = measure.sample_at(1000.0);
+    assert!((p - point(100.0, 0.0)).length() < 0.01);
+    assert!((v - vector(-1.0, -1.0).normalize()).length() < 0.001);
+
+    // Extracting the end of the first sub-path and the start of the second one.
+    let extracted = measure.extract(5.0, start + 15.0);
+    let events: Vec<PathEvent> = extracted.iter().collect();
+    assert_eq!(events.len(), 8);
+    assert_eq!(
+        events[0],
+        PathEvent::Begin {
+            at: point(5.0, 0.0)
+        }
+    );
+    assert_eq!(
+        events[2],
+        PathEvent::Cubic {
+            from: cubic.from,
+            ctrl1: cubic.ctrl1,
+            ctrl2: cubic.ctrl2,
+            to: cubic.to,
+        }
+    );
+    assert_eq!(
+        events[4],
+        PathEvent::Begin {
+            at: point(100.0, 0.0)
+        }
+    );
+    match events[6] {
+        PathEvent::Line { to, .. } => assert!((to - point(110.0, 5.0)).length() < 0.01),
+        _ => panic!("{:?}", events[6]),
+    }
+    match events[7] {
+        PathEvent::End { close, .. } => assert!(!close),
+        _ => panic!("{:?}", events[7]),
+    }
+
+    // Whole closed sub-paths are preserved.
+    let extracted = measure.extract(start, measure.length());
+    let events: Vec<PathEvent> = extracted.iter().collect();
+    let expected: Vec<PathEvent> = path.iter().skip(4).collect();
+    assert_eq!(events, expected);
+
+    // A trimmed curve.
+    let extracted = measure.extract(10.0, 10.0 + cubic_length * 0.5);
+    match extracted.iter().nth(1) {
+        Some(PathEvent::Cubic { from, to, .. }) => {
+            assert_eq!(from, cubic.from);
+            assert_eq!(to, cubic.sample(t));
+        }
+        evt => panic!("{:?}", evt),
+    }
+
+    // NaN distances are treated as zero, and distances past the end map to the end of the
+    // last segment.
+    assert_eq!(measure.sample_at(f32::NAN), measure.sample_at(0.0));
+    assert_eq!(measure.segment_at(f32::NAN).unwrap().1, 0.0);
+    assert!((measure.segment_at(1000.0).unwrap().1 - 1.0).abs() < 1e-5);
+
+    // Non-finite coordinates don't cause panics.
+    let nan_path = path.transformed(&crate::math::Transform::scale(f32::NAN, 1.0));
+    let nan_measure = PathMeasure::new(nan_path.as_slice(), 0.01);
+    nan_measure.sample_at(5.0);
+    nan_measure.sample_at(f32::NAN);
+
+    let empty = PathMeasure::new(Path::new().as_slice(), 0.01);
+    assert_eq!(empty.length(), 0.0);
+    assert_eq!(empty.segment_at(1.0), None);
+    assert_eq!(empty.sample_at(1.0), (point(0.0, 0.0), vector(0.0, 0.0)));
+    assert!(empty.extract(0.0, 1.0).iter().next().is_none());
+}
@@ -0,0 +1,436 @@
+//! Grow or shrink the fill area of a path by a given distance.
+//!
+//! # Example
+//!
+//! ```
+//! use lyon_algorithms::offset::{offset_path, OffsetOptions};
+//! use lyon_algorithms::path::{Path, LineJoin};
+//! use lyon_algorithms::math::point;
+//!
+//! let mut builder = Path::builder();
+//! builder.begin(point(0.0, 0.0));
+//! builder.line_to(point(10.0, 0.0));
+//! builder.line_to(point(10.0, 10.0));
+//! builder.line_to(point(0.0, 10.0));
+//! builder.end(true);
+//! let square = builder.build();
+//!
+//! // A square with rounded corners, 2 units larger on each side.
+//! let grown = offset_path(
+//!     square.as_slice(),
+//!     2.0,
+//!     &OffsetOptions::tolerance(0.01).with_line_join(LineJoin::Round),
+//! );
+//!
+//! // A smaller square.
+//! let shrunk = offset_path(square.as_slice(), -2.0, &OffsetOptions::default());
+//! ```
+
+use crate::boolean::{boolean_op, positive_outline, BooleanOp};
+use crate::math::{Point, Vector};
+use crate::path::{FillRule, LineJoin, Path, PathEvent, PathSlice};
+
+/// Parameters for `offset_path`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct OffsetOptions {
+    /// Maximum allowed distance to the path when building an approximation.
+    ///
+    /// Default value: `OffsetOptions::DEFAULT_TOLERANCE`.
+    pub tolerance: f32,
+
+    /// The fill rule defining the area of the path to offset.
+    ///
+    /// Default value: `OffsetOptions::DEFAULT_FILL_RULE`.
+    pub fill_rule: FillRule,
+
+    /// The shape of the offset outline around the corners.
+    ///
+    /// Miter joins that would extend further than `miter_limit` times the offset distance
+    /// from the corner are replaced with bevel joins.
+    ///
+    /// Default value: `OffsetOptions::DEFAULT_LINE_JOIN`.
+    pub line_join: LineJoin,
+
+    /// See the SVG specification.
+    ///
+    /// Must be greater than or equal to 1.0.
+    /// Default value: `OffsetOptions::DEFAULT_MITER_LIMIT`.
+    pub miter_limit: f32,
+
+    // To be able to add fields without making it a breaking change, add an empty private field
+    // which makes it impossible to create a OffsetOptions without calling the constructor.
+    _private: (),
+}
+
+impl Default for OffsetOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl OffsetOptions {
+    /// Default flattening tolerance.
+    pub const DEFAULT_TOLERANCE: f32 = 0.1;
+    /// Default fill rule.
+    pub const DEFAULT_FILL_RULE: FillRule = FillRule::NonZero;
+    /// Default line join.
+    pub const DEFAULT_LINE_JOIN: LineJoin = LineJoin::Miter;
+    /// Default miter limit.
+    pub const DEFAULT_MITER_LIMIT: f32 = 4.0;
+    /// Minimum miter limit as defined by the SVG specification.
+    pub const MINIMUM_MITER_LIMIT: f32 = 1.0;
+
+    pub const DEFAULT: Self = OffsetOptions {
+        tolerance: Self::DEFAULT_TOLERANCE,
+        fill_rule: Self::DEFAULT_FILL_RULE,
+        line_join: Self::DEFAULT_LINE_JOIN,
+        miter_limit: Self::DEFAULT_MITER_LIMIT,
+        _private: (),
+    };
+
+    #[inline]
+    pub fn tolerance(tolerance: f32) -> Self {
+        Self::DEFAULT.with_tolerance(tolerance)
+    }
+
+    #[inline]
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    #[inline]
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    #[inline]
+    pub fn with_line_join(mut self, join: LineJoin) -> Self {
+        self.line_join = join;
+        self
+    }
+
+    #[inline]
+    pub fn with_miter_limit(mut self, limit: f32) -> Self {
+        assert!(limit >= Self::MINIMUM_MITER_LIMIT);
+        self.miter_limit = limit;
+        self
+    }
+}
+
+/// Computes the outline of the fill area of a path grown by `distance`, or shrunk if
+/// `distance` is negative.
+///
+/// Curves are flattened, so the result only contains line segments. The path is first
+/// simplified into non-intersecting outlines, and the parts of the result that overlap
+/// are merged, so self-intersections in the input or created by the offset are resolved.
+/// Parts of the shape that are thinner than twice the distance disappear when shrinking.
+pub fn offset_path(path: PathSlice, distance: f32, options: &OffsetOptions) -> Path {
+    let tolerance = options.tolerance;
+    let empty = Path::new();
+    let outline = boolean_op(
+        path,
+        options.fill_rule,
+        empty.as_slice(),
+        FillRule::NonZero,
+        BooleanOp::Union,
+        tolerance,
+    );
+
+    if distance == 0.0 || !distance.is_finite() {
+        return outline;
+    }
+
+    // Move each edge of the outline by `distance` and connect them with joins, then keep the
+    // areas where the offset contours wind positively. The inside of the outline is on the
+    // positive side of its edges, so the contours wind negatively around the parts that the
+    // offset moved past each other.
+    let mut contours = Path::builder();
+    let mut contour = Vec::new();
+    let mut offset = Vec::new();
+    for evt in outline.iter() {
+        match evt {
+            PathEvent::Begin { at } => {
+                contour.clear();
+                contour.push(at);
+            }
+            PathEvent::Line { to, .. } => {
+                contour.push(to);
+            }
+            PathEvent::End { .. } => {
+                offset.clear();
+                offset_contour(&contour, distance, options, &mut offset);
+                if let Some((first, rest)) = offset.split_first() {
+                    contours.begin(*first);
+                    for p in rest {
+                        contours.line_to(*p);
+                    }
+                    contours.end(true);
+                }
+            }
+            _ => {}
+        }
+    }
+    let contours = contours.build();
+
+    positive_outline(contours.as_slice(), tolerance)
+}
+
+/// Builds the contour of a closed polygon with its edges moved by `distance` to their
+/// right side.
+///
+/// On the outer side of the corners, the moved edges are connected with joins. On the inner
+/// side, they are connected through the corner so that the loop they form winds positively.
+fn offset_contour(
+    points: &[Point],
+    distance: f32,
+    options: &OffsetOptions,
+    output: &mut Vec<Point>,
+) {
+    let mut points = points.to_vec();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 2 {
+        return;
+    }
+
+    let n = points.len();
+    for i in 0..n {
+        let prev = points[(i + n - 1) % n];
+        let current = points[i];
+        let next = points[(i + 1) % n];
+
+        let d0 = current - prev;
+        let d1 = next - current;
+        let n0 = edge_normal(prev, current) * distance;
+        let n1 = edge_normal(current, next) * distance;
+        let cross = d0.cross(d1);
+
+        if cross == 0.0 && d0.dot(d1) >= 0.0 {
+            output.push(current + n0);
+        } else if cross * distance > 0.0 || cross == 0.0 {
+            add_join(prev, current, next, distance, options, output);
+        } else {
+            output.push(current + n0);
+            output.push(current);
+            output.push(current + n1);
+        }
+    }
+}
+
+/// Returns the unit vector perpendicular to the edge, on its right side.
+fn edge_normal(from: Point, to: Point) -> Vector {
+    let v = (to - from).normalize();
+    Vector::new(v.y, -v.x)
+}
+
+/// Builds the join between the edges moved by `distance` on the outer side of the corner.
+fn add_join(
+    prev: Point,
+    current: Point,
+    next: Point,
+    distance: f32,
+    options: &OffsetOptions,
+    output: &mut Vec<Point>,
+) {
+    let d0 = current - prev;
+    let d1 = next - current;
+    let n0 = edge_normal(prev, current) * distance.signum();
+    let n1 = edge_normal(current, next) * distance.signum();
+    let radius = distance.abs();
+
+    let start = current + n0 * radius;
+    let end = current + n1 * radius;
+    output.push(start);
+
+    match options.line_join {
+        LineJoin::Round => {
+            // The sagitta of each step of the arc must be within the tolerance. When the
+            // edges go back on themselves, the arc goes around the front of the corner.
+            let angle = if d0.cross(d1) == 0.0 {
+                std::f32::consts::PI.copysign(n0.cross(d0))
+            } else {
+                n0.angle_to(n1).radians
+            };
+            let step = if options.tolerance < radius {
+                2.0 * (1.0 - options.tolerance / radius).acos()
+            } else {
+                angle.abs()
+            };
+            let num_steps = (angle.abs() / step).ceil().max(1.0) as u32;
+            for i in 1..num_steps {
+                let a = angle * i as f32 / num_steps as f32;
+                let (sin, cos) = a.sin_cos();
+                let n = Vector::new(n0.x * cos - n0.y * sin, n0.x * sin + n0.y * cos);
+                output.push(current + n * radius);
+            }
+        }
+        LineJoin::Miter | LineJoin::MiterClip => {
+            // The miter is at the intersection of the offset edges.
+            let u0 = d0.normalize();
+            let u1 = d1.normalize();
+            let limit = options.miter_limit * radius;
+            let s = (end - start).cross(u1) / u0.cross(u1);
+            let tip = start + u0 * s;
+            if s.is_finite() && (tip - current).length() <= limit {
+                output.push(tip);
+            } else if options.line_join == LineJoin::MiterClip {
+                // Clip the miter with a line perpendicular to the bisector, at the limit.
+                let bisector = n0 + n1;
+                if bisector.square_length() > 0.0 {
+                    let dir = bisector.normalize();
+                    let t0 = (limit - radius * n0.dot(dir)) / u0.dot(dir);
+                    let t1 = (limit - radius * n1.dot(dir)) / -u1.dot(dir);
+                    output.push(start + u0 * t0);
+                    output.push(end - u1 * t1);
+                }
+            }
+        }
+        LineJoin::Bevel => {}
+    }
+
+    output.push(end);
+}
+
+#[test]
+fn offset_square() {
+    use crate::math::point;
+
+    let area = |path: &Path| {
+        let mut area = 0.0;
+        for evt in path.iter() {
+            match evt {
+                PathEvent::Line { from, to } | PathEvent::End { last: from, first: to, .. } => {
+                    area += from.to_vector().cross(to.to_vector());
+                }
+                _ => {}
+            }
+        }
+        area * 0.5
+    };
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.end(true);
+    let square = builder.build();
+
+    let options = OffsetOptions::tolerance(0.01);
+    let grown = offset_path(square.as_slice(), 1.0, &options);
+    assert_eq!(area(&grown), 144.0);
+    assert_eq!(grown.iter().count(), 5);
+
+    let bevel = offset_path(
+        square.as_slice(),
+        1.0,
+        &options.with_line_join(LineJoin::Bevel),
+    );
+    assert_eq!(area(&bevel), 142.0);
+
+    let round = offset_path(
+        square.as_slice(),
+        1.0,
+        &options.with_line_join(LineJoin::Round),
+    );
+    let expected = 140.0 + std::f32::consts::PI;
+    assert!((area(&round) - expected).abs() < 0.05);
+
+    // The miter limit is exceeded with right angles when it is lower than sqrt(2).
+    let clipped = offset_path(
+        square.as_slice(),
+        1.0,
+        &options
+            .with_line_join(LineJoin::MiterClip)
+            .with_miter_limit(1.2),
+    );
+    assert!(area(&clipped) > area(&bevel));
+    assert!(area(&clipped) < area(&grown));
+    let limited = offset_path(square.as_slice(), 1.0, &options.with_miter_limit(1.2));
+    assert_eq!(area(&limited), 142.0);
+
+    let shrunk = offset_path(square.as_slice(), -1.0, &options);
+    assert_eq!(area(&shrunk), 64.0);
+    assert_eq!(shrunk.iter().count(), 5);
+
+    let gone = offset_path(square.as_slice(), -6.0, &options);
+    assert_eq!(gone.iter().count(), 0);
+
+    // Offsetting an L shape inwards rounds the inner corner with round joins.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(10.0, 4.0));
+    builder.line_to(point(4.0, 4.0));
+    builder.line_to(point(4.0, 10.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.end(true);
+    let l_shape = builder.build();
+    let shrunk = offset_path(
+        l_shape.as_slice(),
+        -1.0,
+        &options.with_line_join(LineJoin::Round),
+    );
+    let expected = 2.0 * 8.0 * 2.0 - 4.0 + (1.0 - std::f32::consts::PI / 4.0);
+    assert!((area(&shrunk) - expected).abs() < 0.05);
+
+    // Growing two nearby squares merges them.
+    let mut builder = Path::builder();
+    for evt in square.iter() {
+        builder.path_event(evt);
+    }
+    builder.begin(point(11.0, 0.0));
+    builder.line_to(point(21.0, 0.0));
+    builder.line_to(point(21.0, 10.0));
+    builder.line_to(point(11.0, 10.0));
+    builder.end(true);
+    let squares = builder.build();
+    let grown = offset_path(squares.as_slice(), 1.0, &options);
+    assert_eq!(area(&grown), 23.0 * 12.0);
+    assert_eq!(grown.iter().count(), 5);
+}
+
+#[test]
+fn offset_many_vertices() {
+    use crate::math::point;
+
+    // A star with a few thousand vertices, whose offset contour intersects itself between
+    // neighboring branches.
+    let n = 1500;
+    let mut builder = Path::builder();
+    for i in 0..n * 2 {
+        let angle = i as f32 * std::f32::consts::PI / n as f32;
+        let radius = if i % 2 == 0 { 1000.0 } else { 900.0 };
+        let p = point(radius * angle.cos(), radius * angle.sin());
+        if i == 0 {
+            builder.begin(p);
+        } else {
+            builder.line_to(p);
+        }
+    }
+    builder.end(true);
+    let star = builder.build();
+
+    let options = OffsetOptions::tolerance(0.01);
+    for &distance in &[2.0, -2.0] {
+        let offset = offset_path(star.as_slice(), distance, &options);
+        let num_sub_paths = offset
+            .iter()
+            .filter(|evt| matches!(evt, PathEvent::Begin { .. }))
+            .count();
+        assert_eq!(num_sub_paths, 1);
+        // The offset contour has up to three points per vertex of the star.
+        assert!(offset.iter().count() <= 3 * 2 * n + 2);
+        for evt in offset.iter() {
+            if let PathEvent::Line { to, .. } = evt {
+                let r = to.to_vector().length();
+                assert!(r > 900.0 - 2.0 * distance.abs() && r < 1000.0 + 5.0 * distance.abs());
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Remove redundancy from the segments of a path.
+
+use crate::geom::{CubicBezierSegment, QuadraticBezierSegment};
+use crate::math::Point;
+use crate::path::builder::PathBuilder;
+use crate::path::{Path, PathEvent};
+
+/// Rewrites the degenerate curves of a path with simpler segments.
+///
+/// - Cubic bézier curves that are within `epsilon` of a quadratic bézier curve are replaced
+///   with it.
+/// - Curves that are within `epsilon` of a line segment are replaced with it.
+/// - Segments that are shorter than `epsilon` in all directions are removed.
+///
+/// This produces paths with fewer vertices, and avoids curves with control points that
+/// coincide with their endpoints, which don't have a well defined tangent at their ends.
+///
+/// ```
+/// use lyon_algorithms::simplify::simplify_curves;
+/// use lyon_algorithms::path::{Path, PathEvent};
+/// use lyon_algorithms::math::point;
+///
+/// let mut builder = Path::builder();
+/// builder.begin(point(0.0, 0.0));
+/// // A straight line, described with a cubic bézier curve.
+/// builder.cubic_bezier_to(point(0.0, 0.0), point(10.0, 0.0), point(10.0, 0.0));
+/// builder.end(false);
+/// let path = builder.build();
+///
+/// let simplified = simplify_curves(path.iter(), 0.01);
+/// assert_eq!(
+///     simplified.iter().nth(1),
+///     Some(PathEvent::Line { from: point(0.0, 0.0), to: point(10.0, 0.0) })
+/// );
+/// ```
+pub fn simplify_curves<Iter>(path: Iter, epsilon: f32) -> Path
+where
+    Iter: IntoIterator<Item = PathEvent>,
+{
+    let mut builder = Path::builder();
+    // The position of the last endpoint that was added to the output, which the
+    // next segments start from when short segments are removed.
+    let mut current = Point::origin();
+    for evt in path {
+        match evt {
+            PathEvent::Begin { at } => {
+                builder.begin(at);
+                current = at;
+            }
+            PathEvent::Line { to, .. } => {
+                line_to(&mut builder, &mut current, to, epsilon);
+            }
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                let curve = QuadraticBezierSegment {
+                    from: current,
+                    ctrl,
+                    to,
+                };
+                if curve.as_line(epsilon).is_some() {
+                    line_to(&mut builder, &mut current, to, epsilon);
+                } else {
+                    builder.quadratic_bezier_to(ctrl, to);
+                    current = to;
+                }
+            }
+            PathEvent::Cubic { ctrl1, ctrl2, to, .. } => {
+                let curve = CubicBezierSegment {
+                    from: current,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                };
+                if curve.as_line(epsilon).is_some() {
+                    line_to(&mut builder, &mut current, to, epsilon);
+                } else if let Some(quadratic) = curve.as_quadratic(epsilon) {
+                    builder.quadratic_bezier_to(quadratic.ctrl, to);
+                    current = to;
+                } else {
+                    builder.cubic_bezier_to(ctrl1, ctrl2, to);
+                    current = to;
+                }
+            }
+            PathEvent::End { close, .. } => {
+                builder.end(close);
+            }
+        }
+    }
+
+    builder.build()
+}
+
+fn line_to(builder: &mut impl PathBuilder, current: &mut Point, to: Point, epsilon: f32) {
+    if (to - *current).square_length() <= epsilon * epsilon {
+        return;
+    }
+
+    builder.line_to(to);
+    *current = to;
+}
+
+#[test]
+fn simplify_degenerate_curves() {
+    use crate::math::point;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    // A degree elevated quadratic curve.
+    builder.cubic_bezier_to(point(6.0, 20.0), point(12.0, 20.0), point(18.0, 0.0));
+    // A regular cubic curve.
+    builder.cubic_bezier_to(point(18.0, 10.0), point(30.0, -10.0), point(30.0, 0.0));
+    // A quadratic curve with its control point on an endpoint.
+    builder.quadratic_bezier_to(point(30.0, 0.0), point(40.0, 0.0));
+    // Tiny segments.
+    builder.line_to(point(40.0, 0.001));
+    builder.cubic_bezier_to(point(40.001, 0.0), point(40.0, 0.0), point(40.0, 0.0));
+    builder.line_to(point(40.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let simplified: Vec<PathEvent> = simplify_curves(path.iter(), 0.01).iter().collect();
+    assert_eq!(
+        simplified,
+        vec![
+            PathEvent::Begin { at: point(0.0, 0.0) },
+            PathEvent::Quadratic {
+                from: point(0.0, 0.0),
+                ctrl: point(9.0, 30.0),
+                to: point(18.0, 0.0),
+            },
+            PathEvent::Cubic {
+                from: point(18.0, 0.0),
+                ctrl1: point(18.0, 10.0),
+                ctrl2: point(30.0, -10.0),
+                to: point(30.0, 0.0),
+            },
+            PathEvent::Line {
+                from: point(30.0, 0.0),
+                to: point(40.0, 0.0),
+            },
+            PathEvent::Line {
+                from: point(40.0, 0.0),
+                to: point(40.0, 10.0),
+            },
+            PathEvent::End {
+                last: point(40.0, 10.0),
+                first: point(0.0, 0.0),
+                close: true,
+            },
+        ]
+    );
+}
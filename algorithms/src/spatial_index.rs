@@ -0,0 +1,889 @@
+//! A spatial index over the segments of many paths, to speed up hit testing and picking.
+//!
+//! Functions like [`hit_test_path`](../hit_test/fn.hit_test_path.html) and
+//! [`raycast_path`](../raycast/fn.raycast_path.html) go through the whole path for each
+//! query. [`SpatialIndex`](struct.SpatialIndex.html) instead flattens the paths once and
+//! stores their segments in a uniform grid, so that queries only look at the segments
+//! in the cells that they overlap. Paths can be added, updated and removed at any time.
+//!
+//! # Example
+//!
+//! ```
+//! use lyon_algorithms::spatial_index::SpatialIndex;
+//! use lyon_algorithms::path::{FillRule, Path, Winding};
+//! use lyon_algorithms::path::builder::PathBuilder;
+//! use lyon_algorithms::math::{point, rect};
+//!
+//! let mut builder = Path::builder();
+//! builder.add_rectangle(&rect(0.0, 0.0, 10.0, 10.0), Winding::Positive);
+//! let square = builder.build();
+//!
+//! let mut builder = Path::builder();
+//! builder.add_circle(point(30.0, 5.0), 5.0, Winding::Positive);
+//! let circle = builder.build();
+//!
+//! // Cells of 4 units, with curves flattened within 0.01 units.
+//! let mut index = SpatialIndex::new(4.0, 0.01);
+//! let square_id = index.add_path(square.iter());
+//! let circle_id = index.add_path(circle.iter());
+//!
+//! let mut hits = Vec::new();
+//! index.hit_test_fill(point(31.0, 4.0), FillRule::NonZero, &mut hits);
+//! assert_eq!(hits, vec![circle_id]);
+//!
+//! hits.clear();
+//! index.hit_test_stroke(point(10.5, 3.0), 1.0, &mut hits);
+//! assert_eq!(hits, vec![square_id]);
+//! ```
+
+use crate::geom::predicates::orient2d;
+use crate::geom::{Line, LineSegment};
+use crate::math::{point, vector, Point, Rect};
+use crate::path::iterator::PathIterator;
+use crate::path::{FillRule, PathEvent};
+use crate::raycast::{Hit, Ray};
+use std::collections::HashMap;
+
+/// The identifier of a path in a [`SpatialIndex`](struct.SpatialIndex.html).
+///
+/// Identifiers of removed paths are not reused.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PathId(pub u32);
+
+/// The closest segment to a position, see
+/// [`SpatialIndex::nearest_segment`](struct.SpatialIndex.html#method.nearest_segment).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NearestSegment {
+    /// The path that the segment belongs to.
+    pub path: PathId,
+    /// The segment, after flattening.
+    pub segment: LineSegment<f32>,
+    /// The closest position on the segment.
+    pub position: Point,
+    pub distance: f32,
+}
+
+struct Segment {
+    line: LineSegment<f32>,
+    path: PathId,
+    /// Whether this is the implicit closing edge of an open sub-path, which only counts
+    /// for the fill.
+    fill_only: bool,
+}
+
+struct PathEntry {
+    segments: Vec<u32>,
+    min: Point,
+    max: Point,
+}
+
+/// A spatial index over the flattened segments of a set of paths.
+///
+/// The segments are stored in a uniform grid of square cells. Queries only visit the cells
+/// that they overlap, so the size of the cells should be picked so that cells contain few
+/// segments while queries overlap few cells, for example a few times the typical length
+/// of the segments.
+///
+/// Curves are flattened within the tolerance given at creation, so the results of the
+/// queries are approximations within this tolerance.
+///
+/// See the [module documentation](index.html) for an example.
+pub struct SpatialIndex {
+    cell_size: f32,
+    tolerance: f32,
+    cells: HashMap<(i32, i32), Vec<u32>>,
+    segments: Vec<Option<Segment>>,
+    free_segments: Vec<u32>,
+    paths: Vec<Option<PathEntry>>,
+    /// The bounding rectangle of all segments.
+    bounds: Option<(Point, Point)>,
+}
+
+impl SpatialIndex {
+    /// Creates an empty index with cells of size `cell_size`, flattening curves within
+    /// `tolerance`.
+    pub fn new(cell_size: f32, tolerance: f32) -> Self {
+        assert!(cell_size > 0.0);
+
+        SpatialIndex {
+            cell_size,
+            tolerance,
+            cells: HashMap::new(),
+            segments: Vec::new(),
+            free_segments: Vec::new(),
+            paths: Vec::new(),
+            bounds: None,
+        }
+    }
+
+    /// Adds a path to the index.
+    pub fn add_path<Iter>(&mut self, path: Iter) -> PathId
+    where
+        Iter: IntoIterator<Item = PathEvent>,
+    {
+        let id = PathId(self.paths.len() as u32);
+        let entry = self.insert_segments(id, path);
+        self.paths.push(Some(entry));
+
+        id
+    }
+
+    /// Replaces the segments of a path, keeping its identifier.
+    pub fn update_path<Iter>(&mut self, id: PathId, path: Iter)
+    where
+        Iter: IntoIterator<Item = PathEvent>,
+    {
+        let touched_bounds = self.remove_segments(id);
+        let entry = self.insert_segments(id, path);
+        self.paths[id.0 as usize] = Some(entry);
+        if touched_bounds {
+            self.update_bounds();
+        }
+    }
+
+    /// Removes a path from the index.
+    pub fn remove_path(&mut self, id: PathId) {
+        if self.remove_segments(id) {
+            self.update_bounds();
+        }
+    }
+
+    /// Returns the bounding rectangle of the flattened path, or `None` if the path was
+    /// removed or has no segment.
+    pub fn path_bounding_rect(&self, id: PathId) -> Option<Rect> {
+        match self.paths.get(id.0 as usize) {
+            Some(Some(entry)) if !entry.segments.is_empty() => Some(to_rect(entry.min, entry.max)),
+            _ => None,
+        }
+    }
+
+    /// Computes the winding number of a position with respect to a path.
+    pub fn winding_number(&self, id: PathId, position: Point) -> i32 {
+        let mut windings = Vec::new();
+        self.windings_at(position, Some(id), &mut windings);
+
+        windings.first().map(|w| w.1).unwrap_or(0)
+    }
+
+    /// Adds to `output` the paths that contain the position when filled with `fill_rule`,
+    /// ordered by identifier.
+    ///
+    /// Open sub-paths are considered closed, like when filling them.
+    pub fn hit_test_fill(&self, position: Point, fill_rule: FillRule, output: &mut Vec<PathId>) {
+        let mut windings = Vec::new();
+        self.windings_at(position, None, &mut windings);
+
+        for (id, winding) in windings {
+            if fill_rule.is_in(winding as i16) {
+                output.push(id);
+            }
+        }
+    }
+
+    /// Adds to `output` the paths that have a segment within `distance` of the position,
+    /// ordered by identifier.
+    ///
+    /// To hit test strokes, `distance` is typically half of the line width.
+    pub fn hit_test_stroke(&self, position: Point, distance: f32, output: &mut Vec<PathId>) {
+        let d = vector(distance, distance);
+        let mut candidates = Vec::new();
+        self.collect_segments(position - d, position + d, &mut candidates);
+
+        let start = output.len();
+        for idx in candidates {
+            let segment = self.segment(idx);
+            if !segment.fill_only && segment.line.distance_to_point(position) <= distance {
+                output.push(segment.path);
+            }
+        }
+
+        output[start..].sort_unstable();
+        dedup_from(output, start);
+    }
+
+    /// Returns the segment closest to the position, if any is within `max_distance`.
+    ///
+    /// The implicit closing edges of open sub-paths are not considered.
+    pub fn nearest_segment(&self, position: Point, max_distance: f32) -> Option<NearestSegment> {
+        let (min, max) = self.bounds?;
+        // Ring computations are done in 64 bits to avoid overflows.
+        let wide = |c: (i32, i32)| (c.0 as i64, c.1 as i64);
+        let (min, max) = (wide(self.cell(min)), wide(self.cell(max)));
+        let (cx, cy) = wide(self.cell(position));
+
+        let mut best: Option<NearestSegment> = None;
+
+        // Visit rings of cells around the position, starting from the first ring that
+        // overlaps the grid. After the ring at distance `r` has been visited, the segments
+        // that were not visited are further than `r * cell_size`.
+        let dist_to_grid = (min.0 - cx)
+            .max(cx - max.0)
+            .max(min.1 - cy)
+            .max(cy - max.1)
+            .max(0);
+        let dist_to_far_end = (cx - min.0).max(max.0 - cx).max(cy - min.1).max(max.1 - cy);
+        for r in dist_to_grid..=dist_to_far_end {
+            let reach = (r as f32 - 1.0) * self.cell_size;
+            if reach > max_distance {
+                break;
+            }
+
+            let y_range = (cy - r).max(min.1)..=(cy + r).min(max.1);
+            for y in y_range {
+                let is_edge_row = y == cy - r || y == cy + r;
+                let mut visit = |x: i64| {
+                    if x < min.0 || x > max.0 {
+                        return;
+                    }
+                    if let Some(cell) = self.cells.get(&(x as i32, y as i32)) {
+                        for idx in cell {
+                            let segment = self.segment(*idx);
+                            if segment.fill_only {
+                                continue;
+                            }
+                            let closest = segment.line.closest_point(position);
+                            let distance = (closest - position).length();
+                            if distance <= max_distance
+                                && best.map(|b| distance < b.distance).unwrap_or(true)
+                            {
+                                best = Some(NearestSegment {
+                                    path: segment.path,
+                                    segment: segment.line,
+                                    position: closest,
+                                    distance,
+                                });
+                            }
+                        }
+                    }
+                };
+
+                if is_edge_row {
+                    for x in (cx - r).max(min.0)..=(cx + r).min(max.0) {
+                        visit(x);
+                    }
+                } else {
+                    visit(cx - r);
+                    visit(cx + r);
+                }
+            }
+
+            if let Some(best) = best {
+                if best.distance <= r as f32 * self.cell_size {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Adds to `output` the paths that have a segment intersecting the rectangle, ordered
+    /// by identifier.
+    ///
+    /// Paths that surround the rectangle without having any segment in it are not selected.
+    /// The implicit closing edges of open sub-paths are not considered.
+    pub fn select_rect(&self, rect: &Rect, output: &mut Vec<PathId>) {
+        let mut candidates = Vec::new();
+        self.collect_segments(rect.min(), rect.max(), &mut candidates);
+
+        let start = output.len();
+        for idx in candidates {
+            let segment = self.segment(idx);
+            if !segment.fill_only && segment_intersects_rect(&segment.line, rect) {
+                output.push(segment.path);
+            }
+        }
+
+        output[start..].sort_unstable();
+        dedup_from(output, start);
+    }
+
+    /// Finds the closest collision between a ray and the paths.
+    ///
+    /// Like [`raycast_path`](../raycast/fn.raycast_path.html), the implicit closing edges
+    /// of open sub-paths are considered.
+    pub fn raycast(&self, ray: &Ray) -> Option<(PathId, Hit)> {
+        let ray_len = ray.direction.square_length();
+        if ray_len == 0.0 || ray_len.is_nan() {
+            return None;
+        }
+
+        // Clip the ray to the bounds of the grid.
+        let (min, max) = self.bounds?;
+        let margin = vector(self.cell_size, self.cell_size);
+        let (min, max) = (min - margin, max + margin);
+        let mut t_min = 0.0f32;
+        let mut t_max = std::f32::MAX;
+        for &(o, d, lo, hi) in &[
+            (ray.origin.x, ray.direction.x, min.x, max.x),
+            (ray.origin.y, ray.direction.y, min.y, max.y),
+        ] {
+            if d == 0.0 {
+                if o < lo || o > hi {
+                    return None;
+                }
+                continue;
+            }
+            let (t0, t1) = ((lo - o) / d, (hi - o) / d);
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+        if t_min > t_max {
+            return None;
+        }
+
+        let clipped = LineSegment {
+            from: ray.origin + ray.direction * t_min,
+            to: ray.origin + ray.direction * t_max,
+        };
+        let line = Line {
+            point: ray.origin,
+            vector: ray.direction,
+        };
+
+        // The position along the ray is measured with the dot product, like in
+        // `raycast_path`.
+        let mut best: Option<(f32, u32, Point)> = None;
+        for_each_cell(self.cell_size, &clipped, &mut |cell, row_start| {
+            if let Some((dot, _, _)) = best {
+                let row_start = t_min + (t_max - t_min) * row_start as f32;
+                if dot < row_start * ray_len {
+                    // The next rows can't contain a closer collision.
+                    return false;
+                }
+            }
+
+            if let Some(segments) = self.cells.get(&cell) {
+                for idx in segments {
+                    let segment = self.segment(*idx);
+                    if let Some(pos) = segment.line.line_intersection(&line) {
+                        let dot = (pos - ray.origin).dot(ray.direction);
+                        if dot >= 0.0 && best.map(|b| dot < b.0).unwrap_or(true) {
+                            best = Some((dot, *idx, pos));
+                        }
+                    }
+                }
+            }
+
+            true
+        });
+
+        let (_, idx, position) = best?;
+        let segment = self.segment(idx);
+        let v = segment.line.to_vector();
+        let mut normal = vector(-v.y, v.x);
+        if normal.dot(ray.direction) > 0.0 {
+            normal = -normal;
+        }
+
+        Some((
+            segment.path,
+            Hit {
+                position,
+                normal: normal.normalize(),
+            },
+        ))
+    }
+
+    /// Computes the winding numbers at a position of the paths that have segments on its
+    /// left, or only of the path `filter` if specified.
+    fn windings_at(
+        &self,
+        position: Point,
+        filter: Option<PathId>,
+        output: &mut Vec<(PathId, i32)>,
+    ) {
+        let min_x = match (filter, self.bounds) {
+            (Some(id), _) => match self.paths.get(id.0 as usize) {
+                Some(Some(entry)) if !entry.segments.is_empty() => entry.min.x,
+                _ => return,
+            },
+            (None, Some((min, _))) => min.x,
+            (None, None) => return,
+        };
+
+        if position.x < min_x {
+            return;
+        }
+
+        // Count the edges crossing the horizontal half-line on the left of the position.
+        let mut candidates = Vec::new();
+        self.collect_segments(point(min_x, position.y), position, &mut candidates);
+
+        for idx in candidates {
+            let segment = self.segment(idx);
+            if filter.is_some() && filter != Some(segment.path) {
+                continue;
+            }
+            let w = crossing_winding(&segment.line, position);
+            if w != 0 {
+                output.push((segment.path, w));
+            }
+        }
+
+        output.sort_by_key(|w| w.0);
+        let mut merged: Vec<(PathId, i32)> = Vec::with_capacity(output.len());
+        for (id, w) in output.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.0 == id => last.1 += w,
+                _ => merged.push((id, w)),
+            }
+        }
+        *output = merged;
+    }
+
+    /// Collects the sorted and deduplicated indices of the segments in the cells that
+    /// overlap the rectangle.
+    fn collect_segments(&self, min: Point, max: Point, output: &mut Vec<u32>) {
+        let (min, max) = (self.cell(min), self.cell(max));
+        let num_cells = (max.0 as i64 - min.0 as i64 + 1) * (max.1 as i64 - min.1 as i64 + 1);
+
+        if num_cells > self.cells.len() as i64 {
+            // Faster to go through the occupied cells than the ones in the area.
+            for (cell, segments) in &self.cells {
+                if cell.0 >= min.0 && cell.0 <= max.0 && cell.1 >= min.1 && cell.1 <= max.1 {
+                    output.extend_from_slice(segments);
+                }
+            }
+        } else {
+            for y in min.1..=max.1 {
+                for x in min.0..=max.0 {
+                    if let Some(segments) = self.cells.get(&(x, y)) {
+                        output.extend_from_slice(segments);
+                    }
+                }
+            }
+        }
+
+        output.sort_unstable();
+        output.dedup();
+    }
+
+    fn insert_segments<Iter>(&mut self, id: PathId, path: Iter) -> PathEntry
+    where
+        Iter: IntoIterator<Item = PathEvent>,
+    {
+        let mut entry = PathEntry {
+            segments: Vec::new(),
+            min: point(std::f32::MAX, std::f32::MAX),
+            max: point(std::f32::MIN, std::f32::MIN),
+        };
+
+        for evt in path.into_iter().flattened(self.tolerance) {
+            let (line, fill_only) = match evt {
+                PathEvent::Line { from, to } => (LineSegment { from, to }, false),
+                PathEvent::End { last, first, close } => (
+                    LineSegment {
+                        from: last,
+                        to: first,
+                    },
+                    !close,
+                ),
+                _ => continue,
+            };
+
+            if line.from == line.to {
+                continue;
+            }
+
+            let segment = Segment {
+                line,
+                path: id,
+                fill_only,
+            };
+            let idx = match self.free_segments.pop() {
+                Some(idx) => {
+                    self.segments[idx as usize] = Some(segment);
+                    idx
+                }
+                None => {
+                    self.segments.push(Some(segment));
+                    (self.segments.len() - 1) as u32
+                }
+            };
+
+            let cells = &mut self.cells;
+            for_each_cell(self.cell_size, &line, &mut |cell, _| {
+                cells.entry(cell).or_insert_with(Vec::new).push(idx);
+                true
+            });
+
+            entry.segments.push(idx);
+            entry.min = entry.min.min(line.from).min(line.to);
+            entry.max = entry.max.max(line.from).max(line.to);
+        }
+
+        if !entry.segments.is_empty() {
+            self.bounds = Some(match self.bounds {
+                Some((min, max)) => (min.min(entry.min), max.max(entry.max)),
+                None => (entry.min, entry.max),
+            });
+        }
+
+        entry
+    }
+
+    /// Removes the segments of a path from the grid, and returns whether the path was on
+    /// the boundary of the bounds, which then need to be recomputed.
+    fn remove_segments(&mut self, id: PathId) -> bool {
+        let entry = match self.paths[id.0 as usize].take() {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        let touched_bounds = match self.bounds {
+            Some((min, max)) if !entry.segments.is_empty() => {
+                entry.min.x <= min.x
+                    || entry.min.y <= min.y
+                    || entry.max.x >= max.x
+                    || entry.max.y >= max.y
+            }
+            _ => false,
+        };
+
+        for idx in entry.segments {
+            let segment = self.segments[idx as usize].take().unwrap();
+            let cells = &mut self.cells;
+            for_each_cell(self.cell_size, &segment.line, &mut |cell, _| {
+                if let Some(segments) = cells.get_mut(&cell) {
+                    segments.retain(|s| *s != idx);
+                    if segments.is_empty() {
+                        cells.remove(&cell);
+                    }
+                }
+                true
+            });
+            self.free_segments.push(idx);
+        }
+
+        touched_bounds
+    }
+
+    fn update_bounds(&mut self) {
+        self.bounds = None;
+        for entry in self.paths.iter().flatten() {
+            if entry.segments.is_empty() {
+                continue;
+            }
+            self.bounds = Some(match self.bounds {
+                Some((min, max)) => (min.min(entry.min), max.max(entry.max)),
+                None => (entry.min, entry.max),
+            });
+        }
+    }
+
+    fn segment(&self, idx: u32) -> &Segment {
+        self.segments[idx as usize].as_ref().unwrap()
+    }
+
+    fn cell(&self, p: Point) -> (i32, i32) {
+        (
+            to_cell((p.x / self.cell_size) as f64),
+            to_cell((p.y / self.cell_size) as f64),
+        )
+    }
+}
+
+/// The largest cell coordinate, small enough that computations on cell coordinates don't
+/// overflow.
+const MAX_CELL: f64 = (1 << 29) as f64;
+
+/// Converts a position in cell units into a cell coordinate, clamped to the supported range.
+fn to_cell(v: f64) -> i32 {
+    if v.is_nan() {
+        return 0;
+    }
+
+    v.floor().max(-MAX_CELL).min(MAX_CELL) as i32
+}
+
+/// Calls `callback` for each cell that the segment overlaps, along with the position along
+/// the segment at which it enters the row of the cell, until the callback returns false.
+///
+/// The cells are visited row by row, in the direction of the segment. They are slightly
+/// inflated so that rounding errors don't cause segments to be missed.
+fn for_each_cell(
+    cell_size: f32,
+    segment: &LineSegment<f32>,
+    callback: &mut dyn FnMut((i32, i32), f64) -> bool,
+) {
+    const EPSILON: f64 = 1e-3;
+
+    let scale = 1.0 / cell_size as f64;
+    let (ax, ay) = (segment.from.x as f64 * scale, segment.from.y as f64 * scale);
+    let (bx, by) = (segment.to.x as f64 * scale, segment.to.y as f64 * scale);
+    let (dx, dy) = (bx - ax, by - ay);
+
+    let first_row = to_cell(ay.min(by) - EPSILON);
+    let last_row = to_cell(ay.max(by) + EPSILON);
+    for i in 0..=(last_row - first_row) {
+        let row = if dy < 0.0 {
+            last_row - i
+        } else {
+            first_row + i
+        };
+
+        let (t0, t1) = if dy == 0.0 {
+            (0.0, 1.0)
+        } else {
+            let ta = (row as f64 - EPSILON - ay) / dy;
+            let tb = (row as f64 + 1.0 + EPSILON - ay) / dy;
+            (ta.min(tb).max(0.0), ta.max(tb).min(1.0))
+        };
+        if t0 > t1 {
+            continue;
+        }
+
+        let (x0, x1) = (ax + dx * t0, ax + dx * t1);
+        let first_col = to_cell(x0.min(x1) - EPSILON);
+        let last_col = to_cell(x0.max(x1) + EPSILON);
+        for j in 0..=(last_col - first_col) {
+            let col = if dx < 0.0 {
+                last_col - j
+            } else {
+                first_col + j
+            };
+            if !callback((col, row), t0) {
+                return;
+            }
+        }
+    }
+}
+
+/// Returns the winding of the segment if it crosses the horizontal half-line on the left of
+/// the position, zero otherwise.
+///
+/// Segments include their upper endpoint but not the lower one, so that the crossings at
+/// the vertices are counted once.
+fn crossing_winding(segment: &LineSegment<f32>, position: Point) -> i32 {
+    let (from, to) = (segment.from, segment.to);
+    if (from.y <= position.y) == (to.y <= position.y) {
+        return 0;
+    }
+
+    let orientation = orient2d(from, to, position);
+    if to.y > from.y && orientation < 0.0 {
+        1
+    } else if to.y < from.y && orientation > 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+fn segment_intersects_rect(segment: &LineSegment<f32>, rect: &Rect) -> bool {
+    // Clip the segment against each side of the rectangle.
+    let (min, max) = (rect.min(), rect.max());
+    let v = segment.to_vector();
+    let mut t0 = 0.0f32;
+    let mut t1 = 1.0f32;
+    for &(p, q) in &[
+        (-v.x, segment.from.x - min.x),
+        (v.x, max.x - segment.from.x),
+        (-v.y, segment.from.y - min.y),
+        (v.y, max.y - segment.from.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return false;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+
+    t0 <= t1
+}
+
+fn dedup_from(v: &mut Vec<PathId>, start: usize) {
+    let mut tail = v.split_off(start);
+    tail.dedup();
+    v.extend(tail);
+}
+
+fn to_rect(min: Point, max: Point) -> Rect {
+    Rect {
+        origin: min,
+        size: (max - min).to_size(),
+    }
+}
+
+#[test]
+fn spatial_index() {
+    use crate::hit_test::{hit_test_path, path_winding_number_at_position};
+    use crate::math::rect;
+    use crate::path::builder::PathBuilder;
+    use crate::path::{Path, Winding};
+    use crate::raycast::raycast_path;
+
+    let mut paths = Vec::new();
+
+    // A square with a hole.
+    let mut builder = Path::builder();
+    builder.add_rectangle(&rect(0.0, 0.0, 10.0, 10.0), Winding::Positive);
+    builder.add_rectangle(&rect(2.0, 2.0, 4.0, 4.0), Winding::Negative);
+    paths.push(builder.build());
+
+    // Overlapping circles.
+    let mut builder = Path::builder();
+    builder.add_circle(point(15.0, 5.0), 4.0, Winding::Positive);
+    builder.add_circle(point(17.0, 5.0), 4.0, Winding::Positive);
+    paths.push(builder.build());
+
+    // An open sub-path.
+    let mut builder = Path::builder();
+    builder.begin(point(1.0, 20.0));
+    builder.line_to(point(9.0, 12.0));
+    builder.quadratic_bezier_to(point(20.0, 20.0), point(10.0, 25.0));
+    builder.end(false);
+    paths.push(builder.build());
+
+    let mut index = SpatialIndex::new(1.5, 0.01);
+    let ids: Vec<PathId> = paths
+        .iter()
+        .map(|path| index.add_path(path.iter()))
+        .collect();
+
+    let check_fill = |index: &SpatialIndex, paths: &[Option<&Path>]| {
+        for y in -2..60 {
+            for x in -2..60 {
+                // Avoid positions on the edges.
+                let position = point(x as f32 * 0.5 + 0.013, y as f32 * 0.5 + 0.017);
+                for fill_rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+                    let mut hits = Vec::new();
+                    index.hit_test_fill(position, *fill_rule, &mut hits);
+                    let mut expected = Vec::new();
+                    for (i, path) in paths.iter().enumerate() {
+                        if let Some(path) = path {
+                            if hit_test_path(&position, path.iter(), *fill_rule, 0.01) {
+                                expected.push(PathId(i as u32));
+                            }
+                        }
+                    }
+                    assert_eq!(hits, expected, "{:?} {:?}", position, fill_rule);
+                }
+            }
+        }
+    };
+
+    check_fill(&index, &[Some(&paths[0]), Some(&paths[1]), Some(&paths[2])]);
+    let p = point(16.0, 5.0);
+    let expected = path_winding_number_at_position(&p, paths[1].iter(), 0.01);
+    assert_eq!(index.winding_number(ids[1], p), expected);
+    assert_eq!(expected.abs(), 2);
+    assert_eq!(index.winding_number(ids[0], point(3.0, 3.0)), 0);
+
+    // Strokes.
+    let mut hits = Vec::new();
+    index.hit_test_stroke(point(10.2, 5.0), 0.5, &mut hits);
+    assert_eq!(hits, vec![ids[0]]);
+    hits.clear();
+    index.hit_test_stroke(point(11.2, 5.0), 0.5, &mut hits);
+    assert_eq!(hits, vec![ids[1]]);
+    hits.clear();
+    index.hit_test_stroke(point(10.6, 5.0), 0.7, &mut hits);
+    assert_eq!(hits, vec![ids[0], ids[1]]);
+    // The closing edge of open sub-paths isn't part of the stroke.
+    hits.clear();
+    index.hit_test_stroke(point(5.5, 22.5), 0.5, &mut hits);
+    assert!(hits.is_empty());
+
+    // Nearest segment.
+    let nearest = index
+        .nearest_segment(point(5.0, -3.0), std::f32::MAX)
+        .unwrap();
+    assert_eq!(nearest.path, ids[0]);
+    assert!((nearest.distance - 3.0).abs() < 1e-5);
+    assert!((nearest.position - point(5.0, 0.0)).length() < 1e-5);
+    let nearest = index
+        .nearest_segment(point(100.0, 5.0), std::f32::MAX)
+        .unwrap();
+    assert_eq!(nearest.path, ids[1]);
+    assert!((nearest.distance - 79.0).abs() < 0.01);
+    assert!(index.nearest_segment(point(100.0, 5.0), 50.0).is_none());
+    // Positions far outside of the range of the cells.
+    let nearest = index
+        .nearest_segment(point(1e10, 0.0), std::f32::MAX)
+        .unwrap();
+    assert_eq!(nearest.path, ids[1]);
+    assert!(index.nearest_segment(point(-1e10, -1e10), 1.0).is_none());
+
+    // Rectangle selection.
+    let mut selection = Vec::new();
+    index.select_rect(&rect(9.0, 4.0, 3.0, 1.0), &mut selection);
+    assert_eq!(selection, vec![ids[0], ids[1]]);
+    selection.clear();
+    index.select_rect(&rect(3.0, 3.0, 1.0, 1.0), &mut selection);
+    assert!(selection.is_empty());
+    selection.clear();
+    index.select_rect(&rect(-5.0, -5.0, 50.0, 50.0), &mut selection);
+    assert_eq!(selection, ids);
+
+    // Ray casts.
+    for i in 0..64 {
+        let angle = i as f32 * std::f32::consts::PI / 32.0;
+        for origin in &[point(5.0, 5.0), point(-3.0, 12.0), point(30.0, 30.0)] {
+            let ray = Ray {
+                origin: *origin,
+                direction: vector(angle.cos(), angle.sin()) * 2.0,
+            };
+            let hit = index.raycast(&ray);
+            let mut expected: Option<(PathId, Hit)> = None;
+            for (id, path) in ids.iter().zip(paths.iter()) {
+                if let Some(hit) = raycast_path(&ray, path.iter(), 0.01) {
+                    let closer = match expected {
+                        Some((_, ref best)) => {
+                            (hit.position - *origin).length() < (best.position - *origin).length()
+                        }
+                        None => true,
+                    };
+                    if closer {
+                        expected = Some((*id, hit));
+                    }
+                }
+            }
+            match (hit, expected) {
+                (Some(hit), Some(expected)) => {
+                    assert_eq!(hit.0, expected.0);
+                    // The curves may be flattened slightly differently.
+                    assert!((hit.1.position - expected.1.position).length() < 0.01);
+                    assert!((hit.1.normal - expected.1.normal).length() < 0.1);
+                }
+                (None, None) => {}
+                (hit, expected) => panic!(
+                    "{:?} {:?} {:?}",
+                    ray.direction,
+                    hit.map(|h| h.1.position),
+                    expected.map(|h| h.1.position)
+                ),
+            }
+        }
+    }
+
+    // Updates.
+    let mut builder = Path::builder();
+    builder.add_rectangle(&rect(30.0, 0.0, 5.0, 5.0), Winding::Negative);
+    let moved = builder.build();
+    index.update_path(ids[0], moved.iter());
+    index.remove_path(ids[1]);
+    check_fill(&index, &[Some(&moved), None, Some(&paths[2])]);
+    assert_eq!(
+        index.path_bounding_rect(ids[0]),
+        Some(rect(30.0, 0.0, 5.0, 5.0))
+    );
+    assert_eq!(index.path_bounding_rect(ids[1]), None);
+    selection.clear();
+    index.select_rect(&rect(-5.0, -5.0, 50.0, 50.0), &mut selection);
+    assert_eq!(selection, vec![ids[0], ids[2]]);
+    let id = index.add_path(paths[1].iter());
+    assert_eq!(id, PathId(3));
+    check_fill(
+        &index,
+        &[Some(&moved), None, Some(&paths[2]), Some(&paths[1])],
+    );
+}
@@ -5,9 +5,11 @@ use std::ops::Range;
 
 use crate::scalar::{cast, Float, Scalar};
 use crate::segment::{BoundingRect, Segment};
+use crate::utils::{bisect, curvature, polynomial_roots_in_range};
 use crate::CubicBezierSegment;
-use crate::Line;
 use crate::QuadraticBezierSegment;
+use crate::{Line, LineSegment};
+use arrayvec::ArrayVec;
 use crate::{point, vector, Angle, Point, Rect, Rotation, Transform, Vector};
 
 /// An elliptic arc curve segment.
@@ -140,8 +142,8 @@ impl<S: Scalar> Arc<S> {
         let from = self.sample(S::ZERO);
         let to = self.sample(S::ONE);
         let flags = ArcFlags {
-            sweep: S::abs(self.sweep_angle.get()) >= S::PI(),
-            large_arc: self.sweep_angle.get() >= S::ZERO,
+            large_arc: S::abs(self.sweep_angle.get()) >= S::PI(),
+            sweep: self.sweep_angle.get() >= S::ZERO,
         };
         SvgArc {
             from,
@@ -188,11 +190,69 @@ impl<S: Scalar> Arc<S> {
     }
 
     /// Sample the curve's tangent at t (expecting t between 0 and 1).
+    ///
+    /// This is the derivative with respect to the angle. See `derivative` for the derivative
+    /// with respect to t.
     #[inline]
     pub fn sample_tangent(&self, t: S) -> Vector<S> {
         self.tangent_at_angle(self.get_angle(t))
     }
 
+    /// Sample the derivative of the curve with respect to t (expecting t between 0 and 1).
+    #[inline]
+    pub fn derivative(&self, t: S) -> Vector<S> {
+        self.sample_tangent(t) * self.sweep_angle.get()
+    }
+
+    /// Sample the second derivative of the curve with respect to t (expecting t between 0
+    /// and 1).
+    pub fn second_derivative(&self, t: S) -> Vector<S> {
+        let a = self.get_angle(t).get();
+        let sweep = self.sweep_angle.get();
+        Rotation::new(self.x_rotation).transform_vector(vector(
+            -self.radii.x * Float::cos(a),
+            -self.radii.y * Float::sin(a),
+        )) * (sweep * sweep)
+    }
+
+    /// Sample the signed curvature at t (expecting t between 0 and 1).
+    ///
+    /// The curvature is positive where the curve turns in the direction of the positive
+    /// angles, which is the case everywhere if the sweep angle is positive.
+    pub fn curvature(&self, t: S) -> S {
+        curvature(self.derivative(t), self.second_derivative(t))
+    }
+
+    /// Calls the callback with the `t` parameters of the local extrema of the curvature,
+    /// strictly between the endpoints and in increasing order.
+    ///
+    /// The curvature of an ellipse is extremal at the ends of its axes. Circular arcs have a
+    /// constant curvature and therefore no extremum.
+    pub fn for_each_curvature_extremum_t<F>(&self, cb: &mut F)
+    where
+        F: FnMut(S),
+    {
+        let sweep = self.sweep_angle.get();
+        let max_radius = S::max(S::abs(self.radii.x), S::abs(self.radii.y));
+        if sweep == S::ZERO || S::abs(self.radii.x - self.radii.y) <= S::EPSILON * max_radius {
+            return;
+        }
+
+        let quarter = S::PI() * S::HALF;
+        let start = self.start_angle.get() / quarter;
+        let end = (self.start_angle.get() + sweep) / quarter;
+        let step = if sweep > S::ZERO { S::ONE } else { -S::ONE };
+        let mut k = if sweep > S::ZERO {
+            S::floor(start) + S::ONE
+        } else {
+            S::ceil(start) - S::ONE
+        };
+        while (k - end) * step < S::ZERO {
+            cb((k - start) / (end - start));
+            k += step;
+        }
+    }
+
     /// Sample the curve's angle at t (expecting t between 0 and 1).
     #[inline]
     pub fn get_angle(&self, t: S) -> Angle<S> {
@@ -469,6 +529,388 @@ impl<S: Scalar> Arc<S> {
         len
     }
 
+    /// Find the `t` parameter of the closest point on the arc to a given position.
+    pub fn closest_point_t(&self, p: Point<S>) -> S {
+        // Work in the ellipse's local space where its axes are aligned with x and y.
+        let local = Rotation::new(-self.x_rotation).transform_vector(p - self.center);
+        let rx = self.radii.x;
+        let ry = self.radii.y;
+        let start = self.start_angle.radians;
+        let sweep = self.sweep_angle.radians;
+
+        // Derivative of the squared distance with respect to the angle (divided by two).
+        let f = |t: S| {
+            let (sin, cos) = Float::sin_cos(start + sweep * t);
+            (ry * ry - rx * rx) * sin * cos + local.x * rx * sin - local.y * ry * cos
+        };
+        let square_distance = |t: S| {
+            let (sin, cos) = Float::sin_cos(start + sweep * t);
+            vector(rx * cos - local.x, ry * sin - local.y).square_length()
+        };
+
+        // There can be up to four normals to the ellipse passing through a point, so the
+        // derivative of the distance is sampled finely enough to isolate its roots in most
+        // cases. The samples themselves are also considered, so that a pair of roots that
+        // are too close to each other to be isolated still produce a good approximation.
+        let n = angular_sample_count(sweep);
+        let step = S::ONE / cast(n).unwrap();
+
+        let mut best_t = S::ZERO;
+        let mut best_d = square_distance(S::ZERO);
+        let mut t0 = S::ZERO;
+        let mut f0 = f(t0);
+        for i in 1..=n {
+            let t1 = if i == n { S::ONE } else { step * cast(i).unwrap() };
+            let f1 = f(t1);
+
+            let d = square_distance(t1);
+            if d < best_d {
+                best_t = t1;
+                best_d = d;
+            }
+
+            if f0 != S::ZERO && f1 != S::ZERO && (f0 < S::ZERO) != (f1 < S::ZERO) {
+                let t = bisect(&f, t0, t1, f0);
+                let d = square_distance(t);
+                if d < best_d {
+                    best_t = t;
+                    best_d = d;
+                }
+            }
+
+            t0 = t1;
+            f0 = f1;
+        }
+
+        best_t
+    }
+
+    /// Find the closest point on the arc to a given position.
+    #[inline]
+    pub fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.sample(self.closest_point_t(p))
+    }
+
+    /// Compute the distance between the arc and a given position.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point<S>) -> S {
+        (self.closest_point(p) - p).length()
+    }
+
+    /// Computes the intersections (if any) between this arc and a line.
+    ///
+    /// The result is provided in the form of the `t` parameters of each
+    /// point along the arc. To get the intersection points, sample the arc
+    /// at the corresponding values.
+    pub fn line_intersections_t(&self, line: &Line<S>) -> ArrayVec<[S; 2]> {
+        let mut result = ArrayVec::new();
+        for (t, _) in self.line_intersections_inner(line.point, line.vector) {
+            result.push(t);
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this arc and a line.
+    pub fn line_intersections(&self, line: &Line<S>) -> ArrayVec<[Point<S>; 2]> {
+        let mut result = ArrayVec::new();
+        for t in self.line_intersections_t(line) {
+            result.push(self.sample(t));
+        }
+
+        result
+    }
+
+    /// Computes the intersections (if any) between this arc and a line segment.
+    ///
+    /// The result is provided in the form of the `t` parameters of each
+    /// point along the arc and the segment. To get the intersection points,
+    /// sample the segments at the corresponding values.
+    pub fn line_segment_intersections_t(&self, segment: &LineSegment<S>) -> ArrayVec<[(S, S); 2]> {
+        let mut result = ArrayVec::new();
+        for (t, s) in self.line_intersections_inner(segment.from, segment.to_vector()) {
+            if s >= S::ZERO && s <= S::ONE {
+                result.push((t, s));
+            }
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this arc and a line segment.
+    pub fn line_segment_intersections(&self, segment: &LineSegment<S>) -> ArrayVec<[Point<S>; 2]> {
+        let mut result = ArrayVec::new();
+        for (t, _) in self.line_segment_intersections_t(segment) {
+            result.push(self.sample(t));
+        }
+
+        result
+    }
+
+    /// Computes the intersections (if any) between this arc and a quadratic bézier segment.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the arc
+    /// and the curve. To get the intersection points, sample the curves at the corresponding
+    /// values.
+    pub fn quadratic_intersections_t(
+        &self,
+        curve: &QuadraticBezierSegment<S>,
+    ) -> ArrayVec<[(S, S); 4]> {
+        if !self.fast_bounding_rect().intersects(&curve.fast_bounding_rect()) {
+            return ArrayVec::new();
+        }
+
+        let from = self.unit_space_vector(curve.from - self.center);
+        let ctrl = self.unit_space_vector(curve.ctrl - self.center);
+        let to = self.unit_space_vector(curve.to - self.center);
+
+        let mut result = ArrayVec::new();
+        for intersection in self.polynomial_curve_intersections_t(&[
+            from,
+            (ctrl - from) * S::TWO,
+            from + to - ctrl * S::TWO,
+        ]) {
+            result.push(intersection);
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this arc and a quadratic bézier
+    /// segment.
+    pub fn quadratic_intersections(
+        &self,
+        curve: &QuadraticBezierSegment<S>,
+    ) -> ArrayVec<[Point<S>; 4]> {
+        let mut result = ArrayVec::new();
+        for (t, _) in self.quadratic_intersections_t(curve) {
+            result.push(self.sample(t));
+        }
+
+        result
+    }
+
+    /// Computes the intersections (if any) between this arc and a cubic bézier segment.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the arc
+    /// and the curve. To get the intersection points, sample the curves at the corresponding
+    /// values.
+    pub fn cubic_intersections_t(&self, curve: &CubicBezierSegment<S>) -> ArrayVec<[(S, S); 6]> {
+        if !self.fast_bounding_rect().intersects(&curve.fast_bounding_rect()) {
+            return ArrayVec::new();
+        }
+
+        let from = self.unit_space_vector(curve.from - self.center);
+        let ctrl1 = self.unit_space_vector(curve.ctrl1 - self.center);
+        let ctrl2 = self.unit_space_vector(curve.ctrl2 - self.center);
+        let to = self.unit_space_vector(curve.to - self.center);
+
+        let mut result = ArrayVec::new();
+        for intersection in self.polynomial_curve_intersections_t(&[
+            from,
+            (ctrl1 - from) * S::THREE,
+            (from + ctrl2 - ctrl1 * S::TWO) * S::THREE,
+            to - from + (ctrl1 - ctrl2) * S::THREE,
+        ]) {
+            result.push(intersection);
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this arc and a cubic bézier segment.
+    pub fn cubic_intersections(&self, curve: &CubicBezierSegment<S>) -> ArrayVec<[Point<S>; 6]> {
+        let mut result = ArrayVec::new();
+        for (t, _) in self.cubic_intersections_t(curve) {
+            result.push(self.sample(t));
+        }
+
+        result
+    }
+
+    /// Computes the intersections (if any) between this arc and another one.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the arcs.
+    /// To get the intersection points, sample the arcs at the corresponding values.
+    ///
+    /// Returns no intersections if the two arcs are on the same ellipse. Intersections where
+    /// the arcs are tangent to each other may not be detected.
+    pub fn arc_intersections_t(&self, arc: &Arc<S>) -> ArrayVec<[(S, S); 4]> {
+        let mut result = ArrayVec::new();
+
+        if (self.center == arc.center && self.radii == arc.radii && self.x_rotation == arc.x_rotation)
+            || !self.fast_bounding_rect().intersects(&arc.fast_bounding_rect())
+            || self.radii.x == S::ZERO
+            || self.radii.y == S::ZERO
+        {
+            return result;
+        }
+
+        // The other arc expressed in the unit space of this one: c + u * cos(a) + v * sin(a).
+        let rotation = Rotation::new(arc.x_rotation);
+        let c = self.unit_space_vector(arc.center - self.center);
+        let u = self.unit_space_vector(rotation.transform_vector(vector(arc.radii.x, S::ZERO)));
+        let v = self.unit_space_vector(rotation.transform_vector(vector(S::ZERO, arc.radii.y)));
+        let start = arc.start_angle.radians;
+        let sweep = arc.sweep_angle.radians;
+        let position = |t: S| {
+            let (sin, cos) = Float::sin_cos(start + sweep * t);
+            c + u * cos + v * sin
+        };
+        // The implicit equation of the unit circle evaluated along the other arc.
+        let f = |t: S| position(t).square_length() - S::ONE;
+
+        let mut add_intersection = |t2: S| {
+            let p = position(t2);
+            if let Some(t1) = self.t_for_angle(S::atan2(p.y, p.x)) {
+                if !result.is_full() {
+                    result.push((t1, t2));
+                }
+            }
+        };
+
+        // Two ellipses intersect at most four times, so sampling the equation finely
+        // enough isolates the intersections unless they are nearly tangent.
+        let n = angular_sample_count(sweep);
+        let step = S::ONE / cast(n).unwrap();
+        let mut t0 = S::ZERO;
+        let mut f0 = f(t0);
+        if f0 == S::ZERO {
+            add_intersection(t0);
+        }
+        for i in 1..=n {
+            let t1 = if i == n { S::ONE } else { step * cast(i).unwrap() };
+            let f1 = f(t1);
+            if f1 == S::ZERO {
+                add_intersection(t1);
+            } else if f0 != S::ZERO && (f0 < S::ZERO) != (f1 < S::ZERO) {
+                add_intersection(bisect(&f, t0, t1, f0));
+            }
+
+            t0 = t1;
+            f0 = f1;
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this arc and another one.
+    pub fn arc_intersections(&self, arc: &Arc<S>) -> ArrayVec<[Point<S>; 4]> {
+        let mut result = ArrayVec::new();
+        for (t, _) in self.arc_intersections_t(arc) {
+            result.push(self.sample(t));
+        }
+
+        result
+    }
+
+    /// Returns the `t` parameter along the arc corresponding to an angle, or `None` if
+    /// the angle is outside of the arc.
+    fn t_for_angle(&self, angle: S) -> Option<S> {
+        let two_pi = S::TWO * S::PI();
+        let sweep = self.sweep_angle.radians;
+        let abs_sweep = S::abs(sweep);
+        if abs_sweep == S::ZERO {
+            return None;
+        }
+
+        let mut delta = (angle - self.start_angle.radians) % two_pi;
+        if sweep < S::ZERO {
+            delta = -delta;
+        }
+        if delta < S::ZERO {
+            delta += two_pi;
+        }
+
+        if delta <= abs_sweep + S::EPSILON {
+            return Some(S::min(delta / abs_sweep, S::ONE));
+        }
+
+        if two_pi - delta <= S::EPSILON {
+            return Some(S::ZERO);
+        }
+
+        None
+    }
+
+    /// Maps a vector into the space where the arc's ellipse is the unit circle.
+    #[inline]
+    fn unit_space_vector(&self, v: Vector<S>) -> Vector<S> {
+        let v = Rotation::new(-self.x_rotation).transform_vector(v);
+        vector(v.x / self.radii.x, v.y / self.radii.y)
+    }
+
+    /// Intersections with the line `point + s * vector`, as pairs of arc `t` and `s` parameters.
+    fn line_intersections_inner(&self, point: Point<S>, vector: Vector<S>) -> ArrayVec<[(S, S); 2]> {
+        let mut result = ArrayVec::new();
+        if self.radii.x == S::ZERO || self.radii.y == S::ZERO {
+            return result;
+        }
+
+        let p = self.unit_space_vector(point - self.center);
+        let v = self.unit_space_vector(vector);
+
+        // Solve |p + s * v|² = 1.
+        let a = v.square_length();
+        let b = S::TWO * p.dot(v);
+        let c = p.square_length() - S::ONE;
+        if a == S::ZERO {
+            return result;
+        }
+
+        let discriminant = b * b - S::FOUR * a * c;
+        if discriminant < S::ZERO {
+            return result;
+        }
+
+        let sqrt_discriminant = S::sqrt(discriminant);
+        let mut roots: ArrayVec<[S; 2]> = ArrayVec::new();
+        roots.push((-b - sqrt_discriminant) / (S::TWO * a));
+        if discriminant > S::ZERO {
+            roots.push((-b + sqrt_discriminant) / (S::TWO * a));
+        }
+
+        for s in roots {
+            let q = p + v * s;
+            if let Some(t) = self.t_for_angle(S::atan2(q.y, q.x)) {
+                result.push((t, s));
+            }
+        }
+
+        result
+    }
+
+    /// Intersections with a polynomial curve expressed in the unit space of the arc by its
+    /// coefficients in increasing degree order, as pairs of arc and curve `t` parameters.
+    fn polynomial_curve_intersections_t(&self, coeffs: &[Vector<S>]) -> ArrayVec<[(S, S); 8]> {
+        let mut result = ArrayVec::new();
+        if self.radii.x == S::ZERO || self.radii.y == S::ZERO {
+            return result;
+        }
+
+        // |curve(t)|² - 1
+        let mut polynomial = [S::ZERO; 7];
+        for (i, ci) in coeffs.iter().enumerate() {
+            for (j, cj) in coeffs.iter().enumerate() {
+                polynomial[i + j] += ci.dot(*cj);
+            }
+        }
+        polynomial[0] -= S::ONE;
+
+        let degree = coeffs.len() * 2 - 1;
+        for t in polynomial_roots_in_range(&polynomial[..degree], S::ZERO..S::ONE) {
+            let mut p = vector(S::ZERO, S::ZERO);
+            for c in coeffs.iter().rev() {
+                p = p * t + *c;
+            }
+            if let Some(t_arc) = self.t_for_angle(S::atan2(p.y, p.x)) {
+                result.push((t_arc, t));
+            }
+        }
+
+        result
+    }
+
     #[inline]
     fn tangent_at_angle(&self, angle: Angle<S>) -> Vector<S> {
         let a = angle.get();
@@ -500,6 +942,76 @@ impl<S: Scalar> SvgArc<S> {
             || self.from == self.to
     }
 
+    /// Returns the radii of the ellipse that the arc is drawn on.
+    ///
+    /// As described in SVG's implementation notes, negative radii are replaced with their
+    /// absolute value, and radii that are too small for the ellipse to connect the endpoints
+    /// are uniformly scaled up until the ellipse has exactly one solution.
+    pub fn corrected_radii(&self) -> Vector<S> {
+        if self.is_straight_line() {
+            return vector(S::abs(self.radii.x), S::abs(self.radii.y));
+        }
+
+        self.to_arc().radii
+    }
+
+    /// Sample the arc at t (expecting t between 0 and 1).
+    ///
+    /// Arcs that are rendered as straight lines are sampled along the line.
+    pub fn sample(&self, t: S) -> Point<S> {
+        if self.is_straight_line() {
+            return self.from.lerp(self.to, t);
+        }
+
+        self.to_arc().sample(t)
+    }
+
+    /// Split this arc into two sub-arcs.
+    pub fn split(&self, t: S) -> (SvgArc<S>, SvgArc<S>) {
+        let mid = self.sample(t);
+        if self.is_straight_line() {
+            return (
+                SvgArc { to: mid, ..*self },
+                SvgArc { from: mid, ..*self },
+            );
+        }
+
+        let (a, b) = self.to_arc().split(t);
+        // Keep the endpoints exactly where they were to avoid accumulating errors.
+        (
+            SvgArc {
+                from: self.from,
+                to: mid,
+                ..a.to_svg_arc()
+            },
+            SvgArc {
+                from: mid,
+                to: self.to,
+                ..b.to_svg_arc()
+            },
+        )
+    }
+
+    /// Returns the smallest rectangle that contains the arc.
+    pub fn bounding_rect(&self) -> Rect<S> {
+        if self.is_straight_line() {
+            return Rect::from_points(&[self.from, self.to]);
+        }
+
+        let rect = self.to_arc().bounding_rect();
+        // Make sure the rectangle contains the original endpoints exactly.
+        rect.union(&Rect::from_points(&[self.from, self.to]))
+    }
+
+    /// Compute the length of the arc using a flattened approximation.
+    pub fn approximate_length(&self, tolerance: S) -> S {
+        if self.is_straight_line() {
+            return (self.to - self.from).length();
+        }
+
+        self.to_arc().approximate_length(tolerance)
+    }
+
     /// Approximates the arc with a sequence of quadratic bézier segments.
     pub fn for_each_quadratic_bezier<F>(&self, cb: &mut F)
     where
@@ -654,6 +1166,15 @@ where
     }
 }
 
+/// Number of samples used to isolate the roots of a function of an arc's parameter that has
+/// at most four roots per turn.
+fn angular_sample_count<S: Scalar>(sweep: S) -> u32 {
+    let max_step = S::PI() / S::value(16.0);
+    cast::<S, u32>(S::ceil(S::abs(sweep) / max_step))
+        .unwrap_or(1)
+        .max(1)
+}
+
 fn sample_ellipse<S: Scalar>(radii: Vector<S>, x_rotation: Angle<S>, angle: Angle<S>) -> Point<S> {
     Rotation::new(x_rotation).transform_point(point(
         radii.x * Float::cos(angle.get()),
@@ -679,7 +1200,16 @@ impl<S: Scalar> Segment for Arc<S> {
         self.y(t)
     }
     fn derivative(&self, t: S) -> Vector<S> {
-        self.sample_tangent(t)
+        self.derivative(t)
+    }
+    fn second_derivative(&self, t: S) -> Vector<S> {
+        self.second_derivative(t)
+    }
+    fn curvature(&self, t: S) -> S {
+        self.curvature(t)
+    }
+    fn for_each_curvature_extremum_t<F: FnMut(S)>(&self, cb: &mut F) {
+        self.for_each_curvature_extremum_t(cb)
     }
     fn split_range(&self, t_range: Range<S>) -> Self {
         self.split_range(t_range)
@@ -699,6 +1229,15 @@ impl<S: Scalar> Segment for Arc<S> {
     fn approximate_length(&self, tolerance: S) -> S {
         self.approximate_length(tolerance)
     }
+    fn closest_point_t(&self, p: Point<S>) -> S {
+        self.closest_point_t(p)
+    }
+    fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.closest_point(p)
+    }
+    fn distance_to_point(&self, p: Point<S>) -> S {
+        self.distance_to_point(p)
+    }
 }
 
 impl<S: Scalar> BoundingRect for Arc<S> {
@@ -867,6 +1406,67 @@ fn test_from_svg_arc() {
     }
 }
 
+#[test]
+fn svg_arc_queries() {
+    use crate::vector;
+
+    // A half circle from (0, 0) to (20, 0) with radii too small to connect the endpoints.
+    let arc = SvgArc {
+        from: point(0.0f64, 0.0),
+        to: point(20.0, 0.0),
+        radii: vector(-5.0, 5.0),
+        x_rotation: Angle::radians(0.0),
+        flags: ArcFlags {
+            large_arc: false,
+            sweep: true,
+        },
+    };
+
+    assert!((arc.corrected_radii() - vector(10.0, 10.0)).length() < 1e-9);
+    assert!((arc.sample(0.5) - point(10.0, -10.0)).length() < 1e-9);
+    assert!((arc.approximate_length(0.0001) - 10.0 * std::f64::consts::PI).abs() < 0.01);
+
+    let rect = arc.bounding_rect();
+    assert!((rect.min() - point(0.0, -10.0)).length() < 1e-9);
+    assert!((rect.max() - point(20.0, 0.0)).length() < 1e-9);
+
+    let (a, b) = arc.split(0.5);
+    assert_eq!(a.from, arc.from);
+    assert_eq!(b.to, arc.to);
+    assert_eq!(a.to, b.from);
+    for half in &[a, b] {
+        assert!((half.corrected_radii() - vector(10.0, 10.0)).length() < 1e-6);
+        assert!(!half.flags.large_arc);
+        assert!(half.flags.sweep);
+    }
+    assert!((a.sample(0.5) - arc.sample(0.25)).length() < 1e-6);
+    assert!((b.sample(0.5) - arc.sample(0.75)).length() < 1e-6);
+
+    // Round trip through the center notation of a large arc.
+    let large = SvgArc {
+        to: point(10.0, 10.0),
+        radii: vector(10.0, 10.0),
+        flags: ArcFlags {
+            large_arc: true,
+            sweep: false,
+        },
+        ..arc
+    };
+    let round_trip = large.to_arc().to_svg_arc();
+    assert_eq!(round_trip.flags, large.flags);
+    assert!((round_trip.to - large.to).length() < 1e-9);
+
+    // Arcs with a zero radius are lines.
+    let line = SvgArc {
+        radii: vector(0.0, 5.0),
+        ..arc
+    };
+    assert_eq!(line.sample(0.25), point(5.0, 0.0));
+    assert_eq!(line.approximate_length(0.1), 20.0);
+    assert_eq!(line.bounding_rect(), Rect::from_points(&[point(0.0, 0.0), point(20.0, 0.0)]));
+    assert_eq!(line.split(0.5).0.to, point(10.0, 0.0));
+}
+
 #[test]
 fn test_to_quadratics_and_cubics() {
     use euclid::approxeq::ApproxEq;
@@ -1050,3 +1650,221 @@ fn negative_flattening_step() {
 
     arc.for_each_flattened(0.100000001, &mut |_| {});
 }
+
+#[test]
+fn closest_point() {
+    let arcs = [
+        Arc {
+            center: point(0.0f64, 0.0),
+            radii: vector(10.0, 10.0),
+            start_angle: Angle::radians(0.0),
+            sweep_angle: Angle::pi(),
+            x_rotation: Angle::zero(),
+        },
+        Arc {
+            center: point(2.0, 3.0),
+            radii: vector(10.0, 3.0),
+            start_angle: Angle::radians(0.1),
+            sweep_angle: Angle::radians(5.0),
+            x_rotation: Angle::radians(0.5),
+        },
+        Arc {
+            center: point(4.0, 5.0),
+            radii: vector(3.0, 8.0),
+            start_angle: Angle::radians(2.0),
+            sweep_angle: Angle::radians(-3.0),
+            x_rotation: Angle::radians(1.3),
+        },
+    ];
+
+    for arc in &arcs {
+        for &p in &[
+            point(0.0, 0.0),
+            point(10.0, 12.0),
+            point(-5.0, 3.0),
+            point(25.0, -1.0),
+            point(3.0, 7.0),
+            point(1.0, 0.5),
+        ] {
+            let t = arc.closest_point_t(p);
+            let d = arc.distance_to_point(p);
+            assert!((arc.closest_point(p) - p).length() == d);
+
+            // Compare against a brute-force search.
+            for i in 0..=1000 {
+                let t2 = i as f64 / 1000.0;
+                assert!(d <= (arc.sample(t2) - p).length() + 1e-9, "{:?} {:?} {:?}", p, t, t2);
+            }
+        }
+    }
+
+    let arc = arcs[0];
+    assert!((arc.closest_point_t(point(0.0, 20.0)) - 0.5).abs() < 1e-9);
+    assert!((arc.distance_to_point(point(0.0, 20.0)) - 10.0).abs() < 1e-9);
+    assert_eq!(arc.closest_point_t(point(20.0, -5.0)), 0.0);
+}
+
+#[test]
+fn line_intersections() {
+    let arc = Arc {
+        center: point(0.0f64, 0.0),
+        radii: vector(10.0, 5.0),
+        start_angle: Angle::zero(),
+        sweep_angle: Angle::pi(),
+        x_rotation: Angle::zero(),
+    };
+
+    // The horizontal line crosses the ellipse twice, both times within the arc.
+    let line = Line {
+        point: point(-20.0, 3.0),
+        vector: vector(1.0, 0.0),
+    };
+    let intersections = arc.line_intersections(&line);
+    assert_eq!(intersections.len(), 2);
+    for p in &intersections {
+        assert!((p.y - 3.0).abs() < 1e-9);
+        assert!((p.x.abs() - 8.0).abs() < 1e-9);
+    }
+
+    // The other side of the ellipse is not part of the arc.
+    let line = Line {
+        point: point(-20.0, -3.0),
+        vector: vector(1.0, 0.0),
+    };
+    assert!(arc.line_intersections_t(&line).is_empty());
+
+    let segment = LineSegment {
+        from: point(0.0, 3.0),
+        to: point(20.0, 3.0),
+    };
+    let intersections = arc.line_segment_intersections_t(&segment);
+    assert_eq!(intersections.len(), 1);
+    let (t, s) = intersections[0];
+    assert!((arc.sample(t) - segment.sample(s)).length() < 1e-9);
+    assert!((s - 0.4).abs() < 1e-9);
+}
+
+#[test]
+fn curve_intersections() {
+    let arc = Arc {
+        center: point(1.0f64, 2.0),
+        radii: vector(10.0, 5.0),
+        start_angle: Angle::radians(-0.5),
+        sweep_angle: Angle::radians(4.0),
+        x_rotation: Angle::radians(0.3),
+    };
+
+    let quadratic = QuadraticBezierSegment {
+        from: point(-15.0, 0.0),
+        ctrl: point(0.0, 20.0),
+        to: point(15.0, 0.0),
+    };
+    let intersections = arc.quadratic_intersections_t(&quadratic);
+    assert!(!intersections.is_empty());
+    for &(t1, t2) in &intersections {
+        assert!((arc.sample(t1) - quadratic.sample(t2)).length() < 1e-6);
+    }
+    assert_eq!(quadratic.arc_intersections_t(&arc).len(), intersections.len());
+
+    let cubic = CubicBezierSegment {
+        from: point(-15.0, 0.0),
+        ctrl1: point(-5.0, 30.0),
+        ctrl2: point(5.0, -30.0),
+        to: point(15.0, 0.0),
+    };
+    let intersections = arc.cubic_intersections_t(&cubic);
+    assert!(!intersections.is_empty());
+    for &(t1, t2) in &intersections {
+        assert!((arc.sample(t1) - cubic.sample(t2)).length() < 1e-6);
+    }
+    assert_eq!(cubic.arc_intersections_t(&arc).len(), intersections.len());
+}
+
+#[test]
+fn arc_intersections() {
+    let a1 = Arc::circle(point(0.0f64, 0.0), 5.0);
+    let a2 = Arc::circle(point(8.0, 0.0), 5.0);
+
+    let intersections = a1.arc_intersections(&a2);
+    assert_eq!(intersections.len(), 2);
+    for p in &intersections {
+        assert!((p.x - 4.0).abs() < 1e-6);
+        assert!((p.y.abs() - 3.0).abs() < 1e-6);
+    }
+
+    let a3 = Arc {
+        center: point(1.0, 0.5),
+        radii: vector(8.0, 2.0),
+        start_angle: Angle::zero(),
+        sweep_angle: Angle::two_pi(),
+        x_rotation: Angle::radians(0.2),
+    };
+    let intersections = a1.arc_intersections_t(&a3);
+    assert_eq!(intersections.len(), 4);
+    for &(t1, t2) in &intersections {
+        assert!((a1.sample(t1) - a3.sample(t2)).length() < 1e-6);
+    }
+
+    // Half of the ellipse only intersects the circle twice.
+    let a4 = Arc {
+        sweep_angle: Angle::pi(),
+        ..a3
+    };
+    assert_eq!(a1.arc_intersections_t(&a4).len(), 2);
+
+    // Arcs on the same ellipse.
+    assert!(a1.arc_intersections_t(&a1.split(0.5).1).is_empty());
+}
+
+#[test]
+fn curvature_queries() {
+    let arc = Arc {
+        center: point(1.0f64, 2.0),
+        radii: vector(2.0, 1.0),
+        start_angle: Angle::zero(),
+        sweep_angle: Angle::two_pi(),
+        x_rotation: Angle::radians(0.5),
+    };
+
+    let h = 1e-6;
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let d1 = (arc.sample(t + h) - arc.sample(t - h)) / (2.0 * h);
+        let p = arc.sample(t);
+        let d2 = ((arc.sample(t + h) - p) - (p - arc.sample(t - h))) / (h * h);
+        assert!((arc.derivative(t) - d1).length() < 1e-6);
+        assert!((arc.second_derivative(t) - d2).length() < 1e-2);
+        // The segment trait uses the same parameter for both derivatives.
+        assert!((Segment::derivative(&arc, t) - d1).length() < 1e-6);
+        assert!((Segment::second_derivative(&arc, t) - d2).length() < 1e-2);
+        assert!((arc.sample_tangent(t) * arc.sweep_angle.get() - d1).length() < 1e-6);
+    }
+
+    // At the ends of the axes of the ellipse.
+    assert!((arc.curvature(0.0) - 2.0).abs() < 1e-9);
+    assert!((arc.curvature(0.25) - 0.25).abs() < 1e-9);
+    assert!((arc.flip().curvature(0.5) + 2.0).abs() < 1e-9);
+
+    let mut extrema = Vec::new();
+    arc.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert_eq!(extrema, vec![0.25, 0.5, 0.75]);
+
+    let mut extrema = Vec::new();
+    let flipped = Arc {
+        start_angle: Angle::radians(1.0),
+        sweep_angle: Angle::radians(-2.0),
+        ..arc
+    };
+    flipped.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert_eq!(extrema, vec![0.5]);
+
+    // Circles have a constant curvature.
+    let circle = Arc {
+        radii: vector(2.0, 2.0),
+        ..arc
+    };
+    let mut extrema = Vec::new();
+    circle.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert!(extrema.is_empty());
+    assert!((Segment::radius_of_curvature(&circle, 0.3) - 2.0).abs() < 1e-9);
+}
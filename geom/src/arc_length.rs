@@ -0,0 +1,341 @@
+//! Arc-length parameterization utilities.
+
+use crate::scalar::{cast, Scalar};
+use crate::segment::Segment;
+
+use std::ops::Range;
+
+/// A precomputed mapping between the `t` parameter of a segment and the length along it.
+///
+/// Building the table integrates the length of the segment once, after which queries only
+/// require a binary search and a cubic interpolation between two samples, which makes it
+/// a good fit for repeatedly querying positions at given distances along the same curve.
+///
+/// ```
+/// use lyon_geom::{point, ArcLengthTable, QuadraticBezierSegment};
+///
+/// let curve = QuadraticBezierSegment {
+///     from: point(0.0f32, 0.0),
+///     ctrl: point(100.0, 100.0),
+///     to: point(200.0, 0.0),
+/// };
+///
+/// let table = ArcLengthTable::new(&curve, 0.01);
+/// let t = table.t_at_length(table.length() * 0.5);
+/// assert!((t - 0.5).abs() < 0.001);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLengthTable<S> {
+    // Sorted by increasing t and length.
+    samples: Vec<Sample<S>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Sample<S> {
+    t: S,
+    length: S,
+    // The derivative of the length with respect to t.
+    speed: S,
+}
+
+impl<S: Scalar> ArcLengthTable<S> {
+    /// Builds the table for a segment.
+    ///
+    /// The interpolated lengths are within `tolerance` of the lengths along the segment.
+    pub fn new<Seg: Segment<Scalar = S>>(segment: &Seg, tolerance: S) -> Self {
+        let speed = |t: S| segment.derivative(t).length();
+        let sample = |t: S, length: S| Sample {
+            t,
+            length,
+            speed: speed(t),
+        };
+
+        let mut samples = vec![sample(S::ZERO, S::ZERO)];
+
+        let mut range = S::ZERO..S::ONE;
+        let mut range_length = integrate(&speed, range.clone());
+        if !range_length.is_finite() {
+            // Non-finite coordinates, no point in refining.
+            samples.push(sample(S::ONE, range_length));
+            return ArcLengthTable { samples };
+        }
+        loop {
+            let start = *samples.last().unwrap();
+            let end = sample(range.end, start.length + range_length);
+
+            // Check the interpolation in the middle of the range.
+            let mid_t = (range.start + range.end) * S::HALF;
+            let first_half_length = integrate(&speed, range.start..mid_t);
+            let error =
+                S::abs(start.length + first_half_length - interpolate_length(&start, &end, mid_t));
+
+            if error <= tolerance || range.end - range.start <= S::value(1.0 / 1024.0) {
+                samples.push(end);
+                if range.end >= S::ONE {
+                    break;
+                }
+                range = range.end..S::ONE;
+                range_length = integrate(&speed, range.clone());
+            } else {
+                range.end = mid_t;
+                range_length = first_half_length;
+            }
+        }
+
+        ArcLengthTable { samples }
+    }
+
+    /// Returns the total length of the segment.
+    pub fn length(&self) -> S {
+        self.samples.last().unwrap().length
+    }
+
+    /// Returns the length along the segment from its start to the point at `t`.
+    pub fn length_at_t(&self, t: S) -> S {
+        if t <= S::ZERO {
+            return S::ZERO;
+        }
+        if t >= S::ONE {
+            return self.length();
+        }
+
+        let idx = self.partition_point(|s| s.t < t);
+        interpolate_length(&self.samples[idx - 1], &self.samples[idx], t)
+    }
+
+    /// Returns the `t` parameter of the point at a given length along the segment.
+    pub fn t_at_length(&self, length: S) -> S {
+        if length <= S::ZERO {
+            return S::ZERO;
+        }
+        if length >= self.length() {
+            return S::ONE;
+        }
+
+        let idx = self.partition_point(|s| s.length < length);
+        let start = &self.samples[idx - 1];
+        let end = &self.samples[idx];
+
+        // A few newton iterations on the interpolated length, kept within the range.
+        let mut lo = start.t;
+        let mut hi = end.t;
+        let mut t = lo + (hi - lo) * (length - start.length) / (end.length - start.length);
+        for _ in 0..8 {
+            let err = interpolate_length(start, end, t) - length;
+            if S::abs(err) <= S::EPSILON * length {
+                break;
+            }
+            if err > S::ZERO {
+                hi = t;
+            } else {
+                lo = t;
+            }
+            let speed = interpolate_speed(start, end, t);
+            let next = if speed > S::ZERO {
+                t - err / speed
+            } else {
+                lo - S::ONE
+            };
+            t = if next > lo && next < hi {
+                next
+            } else {
+                (lo + hi) * S::HALF
+            };
+        }
+
+        t
+    }
+
+    // Index of the first sample that does not match the predicate, at least one.
+    fn partition_point<P: Fn(&Sample<S>) -> bool>(&self, pred: P) -> usize {
+        let mut lo = 1;
+        let mut hi = self.samples.len() - 1;
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if pred(&self.samples[mid]) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        lo
+    }
+}
+
+/// Cubic hermite interpolation of the length between two samples.
+fn interpolate_length<S: Scalar>(start: &Sample<S>, end: &Sample<S>, t: S) -> S {
+    let dt = end.t - start.t;
+    if dt <= S::ZERO {
+        return start.length;
+    }
+
+    let u = (t - start.t) / dt;
+    let u2 = u * u;
+    let u3 = u2 * u;
+    let h00 = S::TWO * u3 - S::THREE * u2 + S::ONE;
+    let h10 = u3 - S::TWO * u2 + u;
+    let h01 = S::THREE * u2 - S::TWO * u3;
+    let h11 = u3 - u2;
+
+    h00 * start.length + h10 * dt * start.speed + h01 * end.length + h11 * dt * end.speed
+}
+
+/// Derivative of `interpolate_length` with respect to t.
+fn interpolate_speed<S: Scalar>(start: &Sample<S>, end: &Sample<S>, t: S) -> S {
+    let dt = end.t - start.t;
+    if dt <= S::ZERO {
+        return start.speed;
+    }
+
+    let u = (t - start.t) / dt;
+    let u2 = u * u;
+    let d00 = S::SIX * (u2 - u);
+    let d10 = S::THREE * u2 - S::FOUR * u + S::ONE;
+    let d11 = S::THREE * u2 - S::TWO * u;
+
+    (d00 * (start.length - end.length)) / dt + d10 * start.speed + d11 * end.speed
+}
+
+/// Integrates the speed of a curve over a range of `t` parameters, which gives the length of
+/// the corresponding part of the curve.
+pub(crate) fn integrate<S: Scalar, F>(speed: &F, range: Range<S>) -> S
+where
+    F: Fn(S) -> S,
+{
+    let whole = gauss_legendre(speed, range.clone());
+    adaptive_integrate(speed, range, whole, 12)
+}
+
+fn adaptive_integrate<S: Scalar, F>(speed: &F, range: Range<S>, whole: S, depth: u32) -> S
+where
+    F: Fn(S) -> S,
+{
+    let mid = (range.start + range.end) * S::HALF;
+    let left = gauss_legendre(speed, range.start..mid);
+    let right = gauss_legendre(speed, mid..range.end);
+    let sum = left + right;
+    if depth == 0 || !sum.is_finite() || S::abs(sum - whole) <= S::EPSILON * S::max(S::abs(sum), S::ONE) {
+        return sum;
+    }
+
+    adaptive_integrate(speed, range.start..mid, left, depth - 1)
+        + adaptive_integrate(speed, mid..range.end, right, depth - 1)
+}
+
+/// Five points Gauss-Legendre quadrature.
+fn gauss_legendre<S: Scalar, F>(f: &F, range: Range<S>) -> S
+where
+    F: Fn(S) -> S,
+{
+    const NODES: [(f64, f64); 5] = [
+        (0.0, 0.568_888_888_888_888_9),
+        (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+        (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    ];
+
+    let half_width = (range.end - range.start) * S::HALF;
+    let center = (range.start + range.end) * S::HALF;
+    let mut sum = S::ZERO;
+    for &(x, w) in &NODES {
+        let x: S = cast(x).unwrap();
+        let w: S = cast(w).unwrap();
+        sum += w * f(center + half_width * x);
+    }
+
+    sum * half_width
+}
+
+/// Finds the `t` parameter at a given length along a curve, using the curve's length and
+/// speed functions.
+pub(crate) fn t_at_length<S: Scalar, L, V>(length_at_t: &L, speed: &V, length: S, tolerance: S) -> S
+where
+    L: Fn(S) -> S,
+    V: Fn(S) -> S,
+{
+    if length <= S::ZERO {
+        return S::ZERO;
+    }
+    let total = length_at_t(S::ONE);
+    if length >= total {
+        return S::ONE;
+    }
+
+    // Newton iterations, falling back to bisection when they escape the bracket.
+    let mut lo = S::ZERO;
+    let mut hi = S::ONE;
+    let mut t = length / total;
+    for _ in 0..64 {
+        let err = length_at_t(t) - length;
+        if S::abs(err) <= tolerance {
+            break;
+        }
+        if err > S::ZERO {
+            hi = t;
+        } else {
+            lo = t;
+        }
+
+        let v = speed(t);
+        let next = if v > S::ZERO {
+            t - err / v
+        } else {
+            lo - S::ONE
+        };
+        t = if next > lo && next < hi {
+            next
+        } else {
+            (lo + hi) * S::HALF
+        };
+    }
+
+    t
+}
+
+#[test]
+fn arc_length_table() {
+    use crate::{point, CubicBezierSegment, LineSegment};
+
+    let line = LineSegment {
+        from: point(0.0f64, 0.0),
+        to: point(10.0, 0.0),
+    };
+    let table = ArcLengthTable::new(&line, 0.001);
+    assert!((table.length() - 10.0).abs() < 1e-9);
+    assert!((table.t_at_length(2.5) - 0.25).abs() < 1e-9);
+    assert!((table.length_at_t(0.75) - 7.5).abs() < 1e-9);
+    assert_eq!(line.length_at_t(1.5), 10.0);
+    assert_eq!(line.length_at_t(-1.0), 0.0);
+
+    let curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(10.0, 100.0),
+        ctrl2: point(100.0, -50.0),
+        to: point(100.0, 20.0),
+    };
+    let tolerance = 0.01;
+    let table = ArcLengthTable::new(&curve, tolerance);
+    assert!((table.length() - curve.approximate_length(0.0001)).abs() < 0.01);
+    for i in 0..=20 {
+        let t = i as f64 / 20.0;
+        let expected = curve.length_at_t(t);
+        assert!((table.length_at_t(t) - expected).abs() <= tolerance);
+        assert!((table.t_at_length(expected) - t).abs() < 0.001);
+    }
+
+    // The speed of arcs accounts for their sweep angle.
+    let arc = crate::Arc {
+        center: point(0.0f64, 0.0),
+        radii: crate::vector(10.0, 10.0),
+        start_angle: crate::Angle::zero(),
+        sweep_angle: crate::Angle::frac_pi_2(),
+        x_rotation: crate::Angle::zero(),
+    };
+    let table = ArcLengthTable::new(&arc, 0.001);
+    let quarter = 5.0 * std::f64::consts::PI;
+    assert!((table.length() - quarter).abs() < 0.001);
+    assert!((table.length_at_t(0.5) - quarter * 0.5).abs() < 0.001);
+    assert!((table.t_at_length(quarter * 0.25) - 0.25).abs() < 0.001);
+}
@@ -0,0 +1,641 @@
+use crate::{point, rect, Point, Rect, Vector};
+use crate::quadratic_bezier::closest_point_t_among_roots;
+use crate::scalar::Scalar;
+use crate::segment::{BoundingRect, Segment};
+use crate::traits::Transformation;
+use crate::utils::{bisect, curvature, polynomial_roots_in_range};
+use crate::{LineSegment, QuadraticBezierSegment};
+use arrayvec::ArrayVec;
+
+use std::ops::Range;
+
+/// A rational quadratic bézier curve segment, also known as a conic section.
+///
+/// The curve is defined by three points and a weight associated with the control point:
+/// ```text
+/// ∀ t ∈ [0..1],
+///          (1 - t)² * from + 2 * (1 - t) * t * weight * ctrl + t² * to
+/// P(t) =  -------------------------------------------------------------
+///               (1 - t)² + 2 * (1 - t) * t * weight + t²
+/// ```
+///
+/// With a weight of one the curve is a regular quadratic bézier curve. Weights
+/// between zero and one produce ellipse arcs, and weights greater than one produce
+/// hyperbola arcs. Negative weights are not supported.
+///
+/// Paths can't store conics: apart from the weights of zero and one, which produce a
+/// line and a quadratic bézier curve, they are approximated with quadratic bézier curves
+/// when added to a path (see `for_each_quadratic_bezier`).
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct ConicSegment<S> {
+    pub from: Point<S>,
+    pub ctrl: Point<S>,
+    pub to: Point<S>,
+    pub weight: S,
+}
+
+impl<S: Scalar> ConicSegment<S> {
+    /// Creates a conic segment equivalent to a quadratic bézier curve.
+    pub fn from_quadratic(curve: &QuadraticBezierSegment<S>) -> Self {
+        ConicSegment {
+            from: curve.from,
+            ctrl: curve.ctrl,
+            to: curve.to,
+            weight: S::ONE,
+        }
+    }
+
+    /// Sample the curve at t (expecting t between 0 and 1).
+    pub fn sample(&self, t: S) -> Point<S> {
+        let (x, y, w) = self.homogeneous(t);
+
+        point(x / w, y / w)
+    }
+
+    /// Sample the x coordinate of the curve at t (expecting t between 0 and 1).
+    pub fn x(&self, t: S) -> S {
+        self.sample(t).x
+    }
+
+    /// Sample the y coordinate of the curve at t (expecting t between 0 and 1).
+    pub fn y(&self, t: S) -> S {
+        self.sample(t).y
+    }
+
+    /// Sample the curve's derivative at t (expecting t between 0 and 1).
+    pub fn derivative(&self, t: S) -> Vector<S> {
+        let (nx, ny, d) = self.power_basis();
+        let n = Vector::new(
+            nx[0] + (nx[1] + nx[2] * t) * t,
+            ny[0] + (ny[1] + ny[2] * t) * t,
+        );
+        let dn = Vector::new(nx[1] + S::TWO * nx[2] * t, ny[1] + S::TWO * ny[2] * t);
+        let w = d[0] + (d[1] + d[2] * t) * t;
+        let dw = d[1] + S::TWO * d[2] * t;
+
+        (dn * w - n * dw) / (w * w)
+    }
+
+    /// Sample the x coordinate of the curve's derivative at t (expecting t between 0 and 1).
+    pub fn dx(&self, t: S) -> S {
+        self.derivative(t).x
+    }
+
+    /// Sample the y coordinate of the curve's derivative at t (expecting t between 0 and 1).
+    pub fn dy(&self, t: S) -> S {
+        self.derivative(t).y
+    }
+
+    /// Sample the curve's second derivative at t (expecting t between 0 and 1).
+    pub fn second_derivative(&self, t: S) -> Vector<S> {
+        self.derivatives(t).1
+    }
+
+    /// Sample the signed curvature at t (expecting t between 0 and 1).
+    ///
+    /// The curvature is positive where the curve turns in the direction of the positive
+    /// angles and zero where the derivative vanishes.
+    pub fn curvature(&self, t: S) -> S {
+        let (d1, d2, _) = self.derivatives(t);
+        curvature(d1, d2)
+    }
+
+    /// Calls the callback with the `t` parameters of the local extrema of the signed curvature,
+    /// strictly between the endpoints and in increasing order.
+    pub fn for_each_curvature_extremum_t<F>(&self, cb: &mut F)
+    where
+        F: FnMut(S),
+    {
+        // The derivative of the curvature has the sign of this function. Unlike bézier
+        // curves it isn't a polynomial, so its roots are isolated by sampling it. Values
+        // that are within the rounding error of its terms are considered to be zero.
+        let f = |t: S| {
+            let (d1, d2, d3) = self.derivatives(t);
+            let a = d1.cross(d3) * d1.square_length();
+            let b = S::THREE * d1.cross(d2) * d1.dot(d2);
+            if S::abs(a - b) <= S::EPSILON * (S::abs(a) + S::abs(b)) {
+                return S::ZERO;
+            }
+
+            a - b
+        };
+
+        const N: u32 = 32;
+        let step = S::ONE / S::value(N as f32);
+        let mut prev: Option<(S, S)> = None;
+        for i in 0..=N {
+            let t = if i == N { S::ONE } else { step * S::value(i as f32) };
+            let ft = f(t);
+            if ft == S::ZERO {
+                continue;
+            }
+            if let Some((t0, f0)) = prev {
+                if (f0 < S::ZERO) != (ft < S::ZERO) {
+                    cb(bisect(&f, t0, t, f0));
+                }
+            }
+            prev = Some((t, ft));
+        }
+    }
+
+    /// Swap the beginning and the end of the segment.
+    pub fn flip(&self) -> Self {
+        ConicSegment {
+            from: self.to,
+            ctrl: self.ctrl,
+            to: self.from,
+            weight: self.weight,
+        }
+    }
+
+    /// Return the sub-curve inside a given range of t.
+    ///
+    /// This is equivalent splitting at the range's end points.
+    pub fn split_range(&self, t_range: Range<S>) -> Self {
+        let t0 = t_range.start;
+        let t1 = t_range.end;
+
+        // Blossoming in homogeneous coordinates, followed by a normalization
+        // of the weights so that the endpoints have a weight of one.
+        let (fx, fy, fw) = self.homogeneous(t0);
+        let (tx, ty, tw) = self.homogeneous(t1);
+
+        let a = (S::ONE - t0) * (S::ONE - t1);
+        let b = (S::ONE - t0) * t1 + t0 * (S::ONE - t1);
+        let c = t0 * t1;
+        let w = self.weight;
+        let cw = a + b * w + c;
+        let cx = a * self.from.x + b * w * self.ctrl.x + c * self.to.x;
+        let cy = a * self.from.y + b * w * self.ctrl.y + c * self.to.y;
+
+        ConicSegment {
+            from: point(fx / fw, fy / fw),
+            ctrl: point(cx / cw, cy / cw),
+            to: point(tx / tw, ty / tw),
+            weight: cw / S::sqrt(fw * tw),
+        }
+    }
+
+    /// Split this curve into two sub-curves.
+    pub fn split(&self, t: S) -> (ConicSegment<S>, ConicSegment<S>) {
+        (self.before_split(t), self.after_split(t))
+    }
+
+    /// Return the curve before the split point.
+    pub fn before_split(&self, t: S) -> ConicSegment<S> {
+        self.split_range(S::ZERO..t)
+    }
+
+    /// Return the curve after the split point.
+    pub fn after_split(&self, t: S) -> ConicSegment<S> {
+        self.split_range(t..S::ONE)
+    }
+
+    #[inline]
+    pub fn from(&self) -> Point<S> {
+        self.from
+    }
+
+    #[inline]
+    pub fn to(&self) -> Point<S> {
+        self.to
+    }
+
+    #[inline]
+    pub fn baseline(&self) -> LineSegment<S> {
+        LineSegment {
+            from: self.from,
+            to: self.to,
+        }
+    }
+
+    /// Applies the transform to this curve and returns the results.
+    ///
+    /// Conic segments are preserved by affine transformations.
+    #[inline]
+    pub fn transformed<T: Transformation<S>>(&self, transform: &T) -> Self {
+        ConicSegment {
+            from: transform.transform_point(self.from),
+            ctrl: transform.transform_point(self.ctrl),
+            to: transform.transform_point(self.to),
+            weight: self.weight,
+        }
+    }
+
+    /// Returns the quadratic bézier curve with the same points, ignoring the weight.
+    pub fn quadratic_hull(&self) -> QuadraticBezierSegment<S> {
+        QuadraticBezierSegment {
+            from: self.from,
+            ctrl: self.ctrl,
+            to: self.to,
+        }
+    }
+
+    /// Approximates the curve with a sequence of quadratic bézier curves,
+    /// invoking a callback at each step.
+    ///
+    /// If the weight is one, the callback is invoked once with the equivalent quadratic
+    /// bézier curve.
+    pub fn for_each_quadratic_bezier<F>(&self, tolerance: S, cb: &mut F)
+    where
+        F: FnMut(&QuadraticBezierSegment<S>),
+    {
+        self.for_each_quadratic_bezier_with_t(tolerance, &mut |curve, _| cb(curve));
+    }
+
+    /// Approximates the curve with a sequence of quadratic bézier curves,
+    /// invoking a callback at each step with the corresponding range of t.
+    pub fn for_each_quadratic_bezier_with_t<F>(&self, tolerance: S, cb: &mut F)
+    where
+        F: FnMut(&QuadraticBezierSegment<S>, Range<S>),
+    {
+        debug_assert!(tolerance >= S::EPSILON);
+
+        let min_range = S::value(1.0 / 1024.0);
+        let mut sub_curve = *self;
+        let mut range = S::ZERO..S::ONE;
+        loop {
+            if sub_curve.quadratic_approximation_error() <= tolerance
+                || range.end - range.start <= min_range
+            {
+                cb(&sub_curve.quadratic_hull(), range.clone());
+                if range.end >= S::ONE {
+                    return;
+                }
+                range.start = range.end;
+                range.end = S::ONE;
+            } else {
+                range.end = (range.start + range.end) * S::HALF;
+            }
+            sub_curve = self.split_range(range.clone());
+        }
+    }
+
+    /// Iterates through the curve invoking a callback at each point.
+    pub fn for_each_flattened<F>(&self, tolerance: S, callback: &mut F)
+    where
+        F: FnMut(Point<S>),
+    {
+        let tolerance = tolerance * S::HALF;
+        self.for_each_quadratic_bezier(tolerance, &mut |curve| {
+            curve.for_each_flattened(tolerance, callback);
+        });
+    }
+
+    /// Compute the length of the segment using a flattened approximation.
+    pub fn approximate_length(&self, tolerance: S) -> S {
+        let mut from = self.from;
+        let mut len = S::ZERO;
+        self.for_each_flattened(tolerance, &mut |to| {
+            len += (to - from).length();
+            from = to;
+        });
+
+        len
+    }
+
+    /// Find the `t` parameter of the closest point on the curve to a given position.
+    pub fn closest_point_t(&self, p: Point<S>) -> S {
+        // The closest point is either an endpoint or at a root of
+        // (N(t) - p * D(t)) · (N'(t) * D(t) - N(t) * D'(t)), which is a quartic polynomial.
+        let (nx, ny, d) = self.power_basis();
+        let a = [
+            Vector::new(nx[0] - p.x * d[0], ny[0] - p.y * d[0]),
+            Vector::new(nx[1] - p.x * d[1], ny[1] - p.y * d[1]),
+            Vector::new(nx[2] - p.x * d[2], ny[2] - p.y * d[2]),
+        ];
+        let dx = derivative_numerator(&nx, &d);
+        let dy = derivative_numerator(&ny, &d);
+        let b = [
+            Vector::new(dx[0], dy[0]),
+            Vector::new(dx[1], dy[1]),
+            Vector::new(dx[2], dy[2]),
+        ];
+
+        let mut coeffs = [S::ZERO; 5];
+        for (i, ai) in a.iter().enumerate() {
+            for (j, bj) in b.iter().enumerate() {
+                coeffs[i + j] += ai.dot(*bj);
+            }
+        }
+
+        closest_point_t_among_roots(&|t| self.sample(t), p, &coeffs)
+    }
+
+    /// Find the closest point on the curve to a given position.
+    #[inline]
+    pub fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.sample(self.closest_point_t(p))
+    }
+
+    /// Compute the distance between the curve and a given position.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point<S>) -> S {
+        (self.closest_point(p) - p).length()
+    }
+
+    /// Returns a conservative rectangle that contains the curve.
+    pub fn fast_bounding_rect(&self) -> Rect<S> {
+        let (min_x, max_x) = self.fast_bounding_range_x();
+        let (min_y, max_y) = self.fast_bounding_range_y();
+
+        rect(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+
+    /// Returns a conservative range of x this curve is contained in.
+    pub fn fast_bounding_range_x(&self) -> (S, S) {
+        let min_x = self.from.x.min(self.ctrl.x).min(self.to.x);
+        let max_x = self.from.x.max(self.ctrl.x).max(self.to.x);
+
+        (min_x, max_x)
+    }
+
+    /// Returns a conservative range of y this curve is contained in.
+    pub fn fast_bounding_range_y(&self) -> (S, S) {
+        let min_y = self.from.y.min(self.ctrl.y).min(self.to.y);
+        let max_y = self.from.y.max(self.ctrl.y).max(self.to.y);
+
+        (min_y, max_y)
+    }
+
+    /// Returns the smallest rectangle the curve is contained in
+    pub fn bounding_rect(&self) -> Rect<S> {
+        let (min_x, max_x) = self.bounding_range_x();
+        let (min_y, max_y) = self.bounding_range_y();
+
+        rect(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+
+    /// Returns the smallest range of x this curve is contained in.
+    pub fn bounding_range_x(&self) -> (S, S) {
+        let (nx, _, d) = self.power_basis();
+        let mut min = S::min(self.from.x, self.to.x);
+        let mut max = S::max(self.from.x, self.to.x);
+        for t in self.local_extrema_t(&nx, &d) {
+            let x = self.x(t);
+            min = S::min(min, x);
+            max = S::max(max, x);
+        }
+
+        (min, max)
+    }
+
+    /// Returns the smallest range of y this curve is contained in.
+    pub fn bounding_range_y(&self) -> (S, S) {
+        let (_, ny, d) = self.power_basis();
+        let mut min = S::min(self.from.y, self.to.y);
+        let mut max = S::max(self.from.y, self.to.y);
+        for t in self.local_extrema_t(&ny, &d) {
+            let y = self.y(t);
+            min = S::min(min, y);
+            max = S::max(max, y);
+        }
+
+        (min, max)
+    }
+
+    fn local_extrema_t(&self, n: &[S; 3], d: &[S; 3]) -> ArrayVec<[S; 8]> {
+        polynomial_roots_in_range(&derivative_numerator(n, d), S::ZERO..S::ONE)
+    }
+
+    // Returns the first three derivatives of the curve at t.
+    fn derivatives(&self, t: S) -> (Vector<S>, Vector<S>, Vector<S>) {
+        // With B = N / w, differentiating N = B * w gives the derivatives of B from the
+        // ones of the numerator and denominator polynomials (of which the third derivatives
+        // are zero).
+        let (nx, ny, d) = self.power_basis();
+        let (x, y, w) = self.homogeneous(t);
+        let b0 = Vector::new(x, y) / w;
+        let n1 = Vector::new(nx[1] + S::TWO * nx[2] * t, ny[1] + S::TWO * ny[2] * t);
+        let n2 = Vector::new(S::TWO * nx[2], S::TWO * ny[2]);
+        let w1 = d[1] + S::TWO * d[2] * t;
+        let w2 = S::TWO * d[2];
+
+        let b1 = (n1 - b0 * w1) / w;
+        let b2 = (n2 - b1 * (S::TWO * w1) - b0 * w2) / w;
+        let b3 = -(b2 * (S::THREE * w1) + b1 * (S::THREE * w2)) / w;
+
+        (b1, b2, b3)
+    }
+
+    // Returns the numerator of the curve in homogeneous coordinates along with the
+    // denominator.
+    fn homogeneous(&self, t: S) -> (S, S, S) {
+        let one_t = S::ONE - t;
+        let a = one_t * one_t;
+        let b = S::TWO * one_t * t * self.weight;
+        let c = t * t;
+
+        (
+            a * self.from.x + b * self.ctrl.x + c * self.to.x,
+            a * self.from.y + b * self.ctrl.y + c * self.to.y,
+            a + b + c,
+        )
+    }
+
+    // Coefficients of the numerator (x and y) and denominator polynomials in
+    // increasing degree order.
+    fn power_basis(&self) -> ([S; 3], [S; 3], [S; 3]) {
+        let w = self.weight;
+        let basis = |p0: S, p1: S, p2: S| {
+            [p0, S::TWO * (w * p1 - p0), p0 - S::TWO * w * p1 + p2]
+        };
+
+        (
+            basis(self.from.x, self.ctrl.x, self.to.x),
+            basis(self.from.y, self.ctrl.y, self.to.y),
+            basis(S::ONE, S::ONE, S::ONE),
+        )
+    }
+
+    // Upper bound of the distance between the curve and its quadratic hull.
+    fn quadratic_approximation_error(&self) -> S {
+        // See "Approximating conic sections with quadratic bézier curves" in the
+        // Skia source code.
+        let a = self.weight - S::ONE;
+        let k = a / (S::FOUR * (S::TWO + a));
+        let v = self.from.to_vector() - self.ctrl.to_vector() * S::TWO + self.to.to_vector();
+
+        S::abs(k) * v.length()
+    }
+}
+
+// Numerator of the derivative of N(t) / D(t), which is N'(t) * D(t) - N(t) * D'(t).
+// The cubic terms cancel out.
+fn derivative_numerator<S: Scalar>(n: &[S; 3], d: &[S; 3]) -> [S; 3] {
+    [
+        n[1] * d[0] - n[0] * d[1],
+        S::TWO * (n[2] * d[0] - n[0] * d[2]),
+        n[2] * d[1] - n[1] * d[2],
+    ]
+}
+
+impl<S: Scalar> Segment for ConicSegment<S> {
+    impl_segment!(S);
+}
+
+impl<S: Scalar> BoundingRect for ConicSegment<S> {
+    type Scalar = S;
+    fn bounding_rect(&self) -> Rect<S> {
+        self.bounding_rect()
+    }
+    fn fast_bounding_rect(&self) -> Rect<S> {
+        self.fast_bounding_rect()
+    }
+    fn bounding_range_x(&self) -> (S, S) {
+        self.bounding_range_x()
+    }
+    fn bounding_range_y(&self) -> (S, S) {
+        self.bounding_range_y()
+    }
+    fn fast_bounding_range_x(&self) -> (S, S) {
+        self.fast_bounding_range_x()
+    }
+    fn fast_bounding_range_y(&self) -> (S, S) {
+        self.fast_bounding_range_y()
+    }
+}
+
+#[cfg(test)]
+fn quarter_circle() -> ConicSegment<f64> {
+    // A quarter of the unit circle.
+    ConicSegment {
+        from: point(1.0, 0.0),
+        ctrl: point(1.0, 1.0),
+        to: point(0.0, 1.0),
+        weight: std::f64::consts::FRAC_1_SQRT_2,
+    }
+}
+
+#[test]
+fn sample_circle() {
+    let conic = quarter_circle();
+    for i in 0..=10 {
+        let p = conic.sample(i as f64 / 10.0);
+        assert!((p.to_vector().length() - 1.0).abs() < 1e-12);
+    }
+    assert!((conic.sample(0.5) - point(0.5f64.sqrt(), 0.5f64.sqrt())).length() < 1e-12);
+
+    // The derivative is tangent to the circle.
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let p = conic.sample(t);
+        let d = conic.derivative(t);
+        assert!(p.to_vector().dot(d).abs() < 1e-12);
+
+        let h = 1e-6;
+        let numerical = (conic.sample(t + h) - conic.sample(t - h)) / (2.0 * h);
+        assert!((numerical - d).length() < 1e-6);
+    }
+}
+
+#[test]
+fn split() {
+    let conic = quarter_circle();
+    let (a, b) = conic.split(0.3);
+    assert_eq!(a.from, conic.from);
+    assert_eq!(b.to, conic.to);
+    assert!((a.to - conic.sample(0.3)).length() < 1e-12);
+    assert!((b.from - conic.sample(0.3)).length() < 1e-12);
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        assert!((a.sample(t).to_vector().length() - 1.0).abs() < 1e-12);
+        assert!((b.sample(t).to_vector().length() - 1.0).abs() < 1e-12);
+    }
+
+    let sub = conic.split_range(0.25..0.75);
+    assert!((sub.from - conic.sample(0.25)).length() < 1e-12);
+    assert!((sub.to - conic.sample(0.75)).length() < 1e-12);
+    assert!((sub.sample(0.5).to_vector().length() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn bounding_rect() {
+    let conic = ConicSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(1.0, 2.0),
+        to: point(2.0, 0.0),
+        weight: 2.0,
+    };
+
+    let r = conic.bounding_rect();
+    assert_eq!(r.min_x(), 0.0);
+    assert_eq!(r.max_x(), 2.0);
+    assert_eq!(r.min_y(), 0.0);
+    assert!((r.max_y() - conic.sample(0.5).y).abs() < 1e-12);
+
+    let fast = conic.fast_bounding_rect();
+    assert!(fast.contains_rect(&r));
+}
+
+#[test]
+fn quadratic_approximation() {
+    let conic = quarter_circle();
+    let tolerance = 0.0001;
+    let mut count = 0;
+    let mut prev = conic.from;
+    conic.for_each_quadratic_bezier(tolerance, &mut |curve| {
+        assert_eq!(curve.from, prev);
+        prev = curve.to;
+        count += 1;
+        for i in 0..=10 {
+            let p = curve.sample(i as f64 / 10.0);
+            assert!((p.to_vector().length() - 1.0).abs() <= tolerance);
+        }
+    });
+    assert_eq!(prev, conic.to);
+    assert!(count > 1);
+
+    let length = conic.approximate_length(0.00001);
+    assert!((length - std::f64::consts::FRAC_PI_2).abs() < 0.0001);
+
+    // Conics with a weight of one are quadratic bézier curves.
+    let quadratic = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(1.0, 2.0),
+        to: point(2.0, 0.0),
+    };
+    let mut count = 0;
+    ConicSegment::from_quadratic(&quadratic).for_each_quadratic_bezier(0.01, &mut |curve| {
+        assert_eq!(*curve, quadratic);
+        count += 1;
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn curvature_queries() {
+    let circle = quarter_circle();
+    let mut extrema = Vec::new();
+    circle.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert!(extrema.is_empty());
+
+    let h = 1e-5;
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        assert!((circle.curvature(t) - 1.0).abs() < 1e-9);
+        let dd = (circle.derivative(t + h) - circle.derivative(t - h)) / (2.0 * h);
+        assert!((circle.second_derivative(t) - dd).length() < 1e-5);
+    }
+
+    let ellipse = ConicSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(1.0, 1.0),
+        to: point(2.0, 0.0),
+        weight: 0.5,
+    };
+    let mut extrema = Vec::new();
+    ellipse.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert_eq!(extrema.len(), 1);
+    assert!((extrema[0] - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn closest_point() {
+    let conic = quarter_circle();
+    let t = conic.closest_point_t(point(2.0, 2.0));
+    assert!((t - 0.5).abs() < 1e-9);
+    assert!((conic.distance_to_point(point(0.0, 0.0)) - 1.0).abs() < 1e-9);
+    assert!((conic.distance_to_point(point(3.0, -1.0)) - 5.0f64.sqrt()).abs() < 1e-9);
+}
@@ -3,12 +3,15 @@ use crate::cubic_to_quadratic::*;
 pub use crate::flatten_cubic::Flattened;
 use crate::flatten_cubic::{find_cubic_bezier_inflection_points, flatten_cubic_bezier_with_t};
 use crate::{rect, Point, Rect, Vector};
+use crate::arc_length;
 use crate::monotonic::Monotonic;
+use crate::offset::offset_cubic;
+use crate::quadratic_bezier::closest_point_t_among_roots;
 use crate::scalar::Scalar;
 use crate::segment::{BoundingRect, Segment};
 use crate::traits::Transformation;
-use crate::utils::{cubic_polynomial_roots, min_max};
-use crate::{Line, LineEquation, LineSegment, QuadraticBezierSegment};
+use crate::utils::{cubic_polynomial_roots, curvature, min_max, polynomial_roots_in_range};
+use crate::{Arc, Line, LineEquation, LineSegment, QuadraticBezierSegment};
 use arrayvec::ArrayVec;
 
 use std::cmp::Ordering::{Equal, Greater, Less};
@@ -152,6 +155,78 @@ impl<S: Scalar> CubicBezierSegment<S> {
         self.from.y * c0 + self.ctrl1.y * c1 + self.ctrl2.y * c2 + self.to.y * c3
     }
 
+    /// Sample the curve's second derivative at t (expecting t between 0 and 1).
+    pub fn second_derivative(&self, t: S) -> Vector<S> {
+        let a = (self.ctrl2 - self.ctrl1) - (self.ctrl1 - self.from);
+        let b = (self.to - self.ctrl2) - (self.ctrl2 - self.ctrl1);
+
+        (a * (S::ONE - t) + b * t) * S::SIX
+    }
+
+    /// Sample the signed curvature at t (expecting t between 0 and 1).
+    ///
+    /// The curvature is positive where the curve turns in the direction of the positive
+    /// angles and zero where the derivative vanishes.
+    pub fn curvature(&self, t: S) -> S {
+        curvature(self.derivative(t), self.second_derivative(t))
+    }
+
+    /// Calls the callback with the `t` parameters of the local extrema of the signed curvature,
+    /// strictly between the endpoints and in increasing order.
+    ///
+    /// Cusps, where the curvature is unbounded, are reported as extrema.
+    pub fn for_each_curvature_extremum_t<F>(&self, cb: &mut F)
+    where
+        F: FnMut(S),
+    {
+        // With d1, d2 and d3 the first three derivatives of the curve (polynomials in t),
+        // the derivative of the curvature has the sign of:
+        // cross(d1, d3) * dot(d1, d1) - 3 * cross(d1, d2) * dot(d1, d2)
+        let a = self.ctrl1 - self.from;
+        let b = self.ctrl2 - self.ctrl1;
+        let c = self.to - self.ctrl2;
+        let d1 = [a * S::THREE, (b - a) * S::SIX, (a - b * S::TWO + c) * S::THREE];
+        let d2 = [(b - a) * S::SIX, (a - b * S::TWO + c) * S::SIX];
+        let d3 = [d2[1]];
+
+        let mut d1_cross_d3 = [S::ZERO; 3];
+        let mut d1_dot_d1 = [S::ZERO; 5];
+        let mut d1_cross_d2 = [S::ZERO; 4];
+        let mut d1_dot_d2 = [S::ZERO; 4];
+        for (i, u) in d1.iter().enumerate() {
+            for (j, v) in d3.iter().enumerate() {
+                d1_cross_d3[i + j] += u.cross(*v);
+            }
+            for (j, v) in d1.iter().enumerate() {
+                d1_dot_d1[i + j] += u.dot(*v);
+            }
+            for (j, v) in d2.iter().enumerate() {
+                d1_cross_d2[i + j] += u.cross(*v);
+                d1_dot_d2[i + j] += u.dot(*v);
+            }
+        }
+
+        let mut coeffs = [S::ZERO; 7];
+        for (i, u) in d1_cross_d3.iter().enumerate() {
+            for (j, v) in d1_dot_d1.iter().enumerate() {
+                coeffs[i + j] += *u * *v;
+            }
+        }
+        for (i, u) in d1_cross_d2.iter().enumerate() {
+            for (j, v) in d1_dot_d2.iter().enumerate() {
+                if i + j < coeffs.len() {
+                    coeffs[i + j] -= S::THREE * *u * *v;
+                }
+            }
+        }
+
+        for t in polynomial_roots_in_range(&coeffs, S::ZERO..S::ONE) {
+            if t > S::ZERO && t < S::ONE {
+                cb(t);
+            }
+        }
+    }
+
     /// Return the sub-curve inside a given range of t.
     ///
     /// This is equivalent to splitting at the range's end points.
@@ -253,6 +328,44 @@ impl<S: Scalar> CubicBezierSegment<S> {
         self.non_point_is_linear(tolerance)
     }
 
+    /// Returns the quadratic bézier curve that this curve is the degree elevation of, if any.
+    ///
+    /// Returns `None` if the curve is more than `epsilon` away from its closest quadratic
+    /// bézier curve with the same endpoints.
+    pub fn as_quadratic(&self, epsilon: S) -> Option<QuadraticBezierSegment<S>> {
+        // A degree elevated quadratic curve has ctrl1 = from + 2/3 * (ctrl - from) and
+        // ctrl2 = to + 2/3 * (ctrl - to), so each control point gives a candidate ctrl.
+        let ctrl_a = (self.ctrl1 * S::THREE - self.from.to_vector()) / S::TWO;
+        let ctrl_b = (self.ctrl2 * S::THREE - self.to.to_vector()) / S::TWO;
+
+        // Using the average of the candidates moves ctrl1 and ctrl2 by opposite vectors d
+        // of length |ctrl_a - ctrl_b| / 3, which moves the curve by at most
+        // max(|B1(t) - B2(t)|) * |d| = sqrt(3) / 6 * |d|.
+        let max_error = (ctrl_a - ctrl_b).length() * S::value(0.096_225_05);
+        if max_error > epsilon {
+            return None;
+        }
+
+        Some(QuadraticBezierSegment {
+            from: self.from,
+            ctrl: ctrl_a.lerp(ctrl_b, S::HALF),
+            to: self.to,
+        })
+    }
+
+    /// Returns the line segment between the endpoints if the curve is within `epsilon` of it.
+    pub fn as_line(&self, epsilon: S) -> Option<LineSegment<S>> {
+        // The curve is inside the convex hull of its control points.
+        let line = self.baseline();
+        if line.distance_to_point(self.ctrl1) > epsilon
+            || line.distance_to_point(self.ctrl2) > epsilon
+        {
+            return None;
+        }
+
+        Some(line)
+    }
+
     #[inline]
     fn non_point_is_linear(&self, tolerance: S) -> bool {
         let line = self.baseline().to_line().equation();
@@ -440,6 +553,167 @@ impl<S: Scalar> CubicBezierSegment<S> {
         len
     }
 
+    /// Computes the length of the curve between its start and the point at `t`.
+    ///
+    /// Unlike `approximate_length`, this numerically integrates the length of the curve
+    /// instead of flattening it.
+    pub fn length_at_t(&self, t: S) -> S {
+        let t = S::min(S::max(t, S::ZERO), S::ONE);
+        arc_length::integrate(&|t| self.derivative(t).length(), S::ZERO..t)
+    }
+
+    /// Returns the `t` parameter of the point at a given length along the curve.
+    ///
+    /// The length of the curve at the returned parameter is within `tolerance` of the
+    /// requested length. Lengths outside of the curve are clamped to its endpoints.
+    ///
+    /// For repeated queries on the same curve, see `ArcLengthTable`.
+    pub fn t_at_length(&self, length: S, tolerance: S) -> S {
+        arc_length::t_at_length(
+            &|t| self.length_at_t(t),
+            &|t| self.derivative(t).length(),
+            length,
+            tolerance,
+        )
+    }
+
+    /// Approximates the offset of the curve with a sequence of cubic bézier curves,
+    /// invoking a callback at each step.
+    ///
+    /// The curve is split at its inflections and cusps. Positive distances offset the
+    /// curve along the normal `(-tangent.y, tangent.x)`, negative distances along the
+    /// opposite direction. The curves are within `tolerance` of the exact offset, except
+    /// where the offset distance exceeds the radius of curvature of the curve, where the
+    /// exact offset has a cusp.
+    ///
+    /// The offset is not continuous at the cusps of the curve, callers are responsible
+    /// for joining the curves on each side.
+    pub fn offset<F>(&self, distance: S, tolerance: S, callback: &mut F)
+    where
+        F: FnMut(&CubicBezierSegment<S>),
+    {
+        offset_cubic(self, distance, tolerance, callback);
+    }
+
+    /// Find the `t` parameter of the closest point on the curve to a given position.
+    pub fn closest_point_t(&self, p: Point<S>) -> S {
+        // With the curve expressed as a + b * t + c * t² + d * t³ relative to p, the closest
+        // point is either an end point or at a root of the quintic polynomial
+        // (a + b * t + c * t² + d * t³) · (b + 2 * c * t + 3 * d * t²).
+        let a = self.from - p;
+        let b = (self.ctrl1 - self.from) * S::THREE;
+        let c = (self.from.to_vector() + self.ctrl2.to_vector() - self.ctrl1.to_vector() * S::TWO)
+            * S::THREE;
+        let d = self.to - self.from + (self.ctrl1 - self.ctrl2) * S::THREE;
+
+        let coeffs = [
+            a.dot(b),
+            b.dot(b) + S::TWO * a.dot(c),
+            S::THREE * (a.dot(d) + b.dot(c)),
+            S::FOUR * b.dot(d) + S::TWO * c.dot(c),
+            S::FIVE * c.dot(d),
+            S::THREE * d.dot(d),
+        ];
+
+        closest_point_t_among_roots(&|t| self.sample(t), p, &coeffs)
+    }
+
+    /// Find the closest point on the curve to a given position.
+    #[inline]
+    pub fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.sample(self.closest_point_t(p))
+    }
+
+    /// Compute the distance between the curve and a given position.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point<S>) -> S {
+        (self.closest_point(p) - p).length()
+    }
+
+    /// Invokes the callback with the `t` parameters in ]0, 1[ where the derivative of the curve
+    /// vanishes, in increasing order.
+    ///
+    /// The curve abruptly changes direction at these points. Curves that aren't straight
+    /// lines have at most one cusp.
+    pub fn for_each_cusp_t<F>(&self, cb: &mut F)
+    where
+        F: FnMut(S),
+    {
+        // The derivative is 3 * (a + 2 * (b - a) * t + (a - 2 * b + c) * t²).
+        let a = self.ctrl1 - self.from;
+        let b = self.ctrl2 - self.ctrl1;
+        let c = self.to - self.ctrl2;
+        let c0 = a;
+        let c1 = (b - a) * S::TWO;
+        let c2 = a - b * S::TWO + c;
+
+        let scale = S::max(S::max(a.length(), b.length()), c.length());
+        if scale == S::ZERO {
+            return;
+        }
+
+        // Find the roots of the coordinate with the largest coefficients and check
+        // that the other one also vanishes.
+        let x_scale = S::abs(c0.x) + S::abs(c1.x) + S::abs(c2.x);
+        let y_scale = S::abs(c0.y) + S::abs(c1.y) + S::abs(c2.y);
+        let (c0, c1, c2) = if x_scale >= y_scale {
+            (c0, c1, c2)
+        } else {
+            (c0.yx(), c1.yx(), c2.yx())
+        };
+
+        for t in polynomial_roots_in_range(&[c0.x, c1.x, c2.x], S::ZERO..S::ONE) {
+            if t <= S::ZERO || t >= S::ONE {
+                continue;
+            }
+            let d = c0.y + (c1.y + c2.y * t) * t;
+            if S::abs(d) <= S::EPSILON * scale {
+                cb(t);
+            }
+        }
+    }
+
+    /// Returns the pair of `t` parameters at which the curve intersects itself, if any.
+    ///
+    /// The first parameter is smaller than the second one, and the curve forms a loop
+    /// between them. Curves that don't loop, including curves with a cusp, return `None`.
+    pub fn self_intersection_t(&self) -> Option<(S, S)> {
+        // In the power basis, the curve is a * t³ + b * t² + c * t + d. With s != t:
+        // B(s) - B(t) = (s - t) * (a * (s² + s * t + t²) + b * (s + t) + c)
+        // Writing u = s + t and v = s * t, the curve intersects itself where
+        // a * (u² - v) + b * u + c = 0, which gives u after eliminating v with a cross
+        // product. s and t are then the roots of x² - u * x + v.
+        let p0 = self.from.to_vector();
+        let p1 = self.ctrl1.to_vector();
+        let p2 = self.ctrl2.to_vector();
+        let p3 = self.to.to_vector();
+        let a = p3 - p0 + (p1 - p2) * S::THREE;
+        let b = (p0 - p1 * S::TWO + p2) * S::THREE;
+        let c = (p1 - p0) * S::THREE;
+
+        let a_cross_b = a.cross(b);
+        let a_length = a.square_length();
+        if a_length == S::ZERO || S::abs(a_cross_b) <= S::EPSILON * a_length.sqrt() * b.length() {
+            return None;
+        }
+
+        let u = -a.cross(c) / a_cross_b;
+        let v = u * u + (b * u + c).dot(a) / a_length;
+        let discriminant = u * u - S::FOUR * v;
+        if discriminant <= S::ZERO {
+            return None;
+        }
+
+        let sqrt_d = discriminant.sqrt();
+        let t1 = (u - sqrt_d) * S::HALF;
+        let t2 = (u + sqrt_d) * S::HALF;
+        if t1 < S::ZERO || t2 > S::ONE {
+            return None;
+        }
+
+        Some((t1, t2))
+    }
+
     pub fn for_each_inflection_t<F>(&self, cb: &mut F)
     where
         F: FnMut(S),
@@ -798,6 +1072,24 @@ impl<S: Scalar> CubicBezierSegment<S> {
         self.cubic_intersections(&curve.to_cubic())
     }
 
+    /// Computes the intersections (if any) between this segment and an arc.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the curve
+    /// and the arc. To get the intersection points, sample the curves at the corresponding values.
+    pub fn arc_intersections_t(&self, arc: &Arc<S>) -> ArrayVec<[(S, S); 6]> {
+        let mut result = ArrayVec::new();
+        for (t_arc, t) in arc.cubic_intersections_t(self) {
+            result.push((t, t_arc));
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this segment and an arc.
+    pub fn arc_intersections(&self, arc: &Arc<S>) -> ArrayVec<[Point<S>; 6]> {
+        arc.cubic_intersections(self)
+    }
+
     /// Computes the intersections (if any) between this segment and a line.
     ///
     /// The result is provided in the form of the `t` parameters of each
@@ -1334,3 +1626,234 @@ fn test_cubic_intersection_deduping() {
     assert!(f64::abs(intersections[0].x) < epsilon);
     assert!(f64::abs(intersections[0].y) < epsilon);
 }
+
+#[test]
+fn closest_point() {
+    use crate::point;
+
+    let curves = [
+        CubicBezierSegment {
+            from: point(0.0f64, 0.0),
+            ctrl1: point(0.0, 20.0),
+            ctrl2: point(20.0, 20.0),
+            to: point(20.0, 0.0),
+        },
+        // Self-intersecting.
+        CubicBezierSegment {
+            from: point(0.0, 0.0),
+            ctrl1: point(30.0, 20.0),
+            ctrl2: point(-10.0, 20.0),
+            to: point(20.0, 0.0),
+        },
+        // S-shaped.
+        CubicBezierSegment {
+            from: point(0.0, 0.0),
+            ctrl1: point(20.0, -10.0),
+            ctrl2: point(-10.0, 30.0),
+            to: point(10.0, 20.0),
+        },
+    ];
+
+    for curve in &curves {
+        for &p in &[
+            point(10.0, 0.0),
+            point(10.0, 12.0),
+            point(-5.0, 3.0),
+            point(25.0, -1.0),
+            point(3.0, 7.0),
+            point(14.0, 20.0),
+        ] {
+            let t = curve.closest_point_t(p);
+            let d = curve.distance_to_point(p);
+            assert!((curve.closest_point(p) - p).length() == d);
+
+            // Compare against a brute-force search.
+            for i in 0..=1000 {
+                let t2 = i as f64 / 1000.0;
+                assert!(d <= (curve.sample(t2) - p).length() + 1e-9, "{:?} {:?} {:?}", p, t, t2);
+            }
+        }
+    }
+}
+
+#[test]
+fn length_at_t() {
+    use crate::point;
+
+    let curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(10.0, 100.0),
+        ctrl2: point(100.0, -50.0),
+        to: point(100.0, 20.0),
+    };
+
+    let length = curve.approximate_length(0.00001);
+    assert!((curve.length_at_t(1.0) - length).abs() < 0.001);
+    assert!((curve.before_split(0.3).approximate_length(0.00001) - curve.length_at_t(0.3)).abs() < 0.001);
+
+    for i in 0..=10 {
+        let expected = i as f64 / 10.0;
+        let t = curve.t_at_length(curve.length_at_t(expected), 0.0001);
+        assert!((t - expected).abs() < 0.0001);
+    }
+
+    assert_eq!(curve.t_at_length(-1.0, 0.01), 0.0);
+    assert_eq!(curve.t_at_length(length * 2.0, 0.01), 1.0);
+}
+
+#[test]
+fn self_intersection() {
+    use crate::point;
+
+    let curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(100.0, 100.0),
+        ctrl2: point(-50.0, 100.0),
+        to: point(50.0, 0.0),
+    };
+    let (t1, t2) = curve.self_intersection_t().unwrap();
+    assert!(t1 > 0.0 && t1 < t2 && t2 < 1.0);
+    assert!((curve.sample(t1) - curve.sample(t2)).length() < 1e-9);
+    // The intersection is on the axis of symmetry of the curve.
+    assert!((curve.sample(t1).x - 25.0).abs() < 1e-9);
+    assert!((t1 + t2 - 1.0).abs() < 1e-9);
+
+    // Splitting the loop removes the self intersection.
+    assert!(curve.before_split((t1 + t2) * 0.5).self_intersection_t().is_none());
+    assert!(curve.after_split((t1 + t2) * 0.5).self_intersection_t().is_none());
+
+    let mut cusps = Vec::new();
+    curve.for_each_cusp_t(&mut |t| cusps.push(t));
+    assert!(cusps.is_empty());
+
+    // A curve with a cusp doesn't intersect itself.
+    let cusp = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(100.0, 100.0),
+        ctrl2: point(0.0, 100.0),
+        to: point(100.0, 0.0),
+    };
+    assert!(cusp.self_intersection_t().is_none());
+    let mut cusps = Vec::new();
+    cusp.for_each_cusp_t(&mut |t| cusps.push(t));
+    assert_eq!(cusps, vec![0.5]);
+
+    // Vertical cusp.
+    let mut cusps = Vec::new();
+    CubicBezierSegment {
+        from: cusp.from.yx(),
+        ctrl1: cusp.ctrl1.yx(),
+        ctrl2: cusp.ctrl2.yx(),
+        to: cusp.to.yx(),
+    }
+    .for_each_cusp_t(&mut |t| cusps.push(t));
+    assert_eq!(cusps, vec![0.5]);
+
+    // Simple curves.
+    let curve = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(10.0, 100.0),
+        ctrl2: point(100.0, -50.0),
+        to: point(100.0, 20.0),
+    };
+    assert!(curve.self_intersection_t().is_none());
+    assert!(QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(10.0, 100.0),
+        to: point(100.0, 20.0),
+    }
+    .to_cubic()
+    .self_intersection_t()
+    .is_none());
+}
+
+#[test]
+fn degree_reduction() {
+    use crate::point;
+
+    let quadratic = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(10.0, 30.0),
+        to: point(30.0, 0.0),
+    };
+    let cubic = quadratic.to_cubic();
+    let reduced = cubic.as_quadratic(1e-9).unwrap();
+    assert!((reduced.ctrl - quadratic.ctrl).length() < 1e-9);
+    assert!(cubic.as_line(1.0).is_none());
+
+    // Moving the control points by opposite vectors of length d moves the curve by at
+    // most d * sqrt(3) / 6.
+    let cubic = CubicBezierSegment {
+        ctrl1: cubic.ctrl1 + crate::vector(0.0, 1.0),
+        ctrl2: cubic.ctrl2 - crate::vector(0.0, 1.0),
+        ..cubic
+    };
+    assert!(cubic.as_quadratic(0.28).is_none());
+    let reduced = cubic.as_quadratic(0.29).unwrap();
+    let mut max_distance: f64 = 0.0;
+    for i in 0..=100 {
+        let t = i as f64 / 100.0;
+        max_distance = max_distance.max((reduced.sample(t) - cubic.sample(t)).length());
+    }
+    assert!(max_distance <= 0.29 && max_distance > 0.28);
+
+    let line = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(40.0, 0.01),
+        ctrl2: point(-10.0, 0.0),
+        to: point(30.0, 0.0),
+    };
+    assert!(line.as_line(0.1).is_none());
+    let line = CubicBezierSegment {
+        ctrl1: point(20.0, 0.01),
+        ctrl2: point(10.0, 0.0),
+        ..line
+    };
+    assert_eq!(line.as_line(0.1), Some(line.baseline()));
+    assert!(line.as_line(0.001).is_none());
+}
+
+#[test]
+fn curvature_queries() {
+    use crate::point;
+
+    let curves = [
+        CubicBezierSegment {
+            from: point(0.0f64, 0.0),
+            ctrl1: point(0.0, 100.0),
+            ctrl2: point(100.0, 100.0),
+            to: point(100.0, 0.0),
+        },
+        CubicBezierSegment {
+            from: point(0.0, 0.0),
+            ctrl1: point(10.0, 100.0),
+            ctrl2: point(100.0, -50.0),
+            to: point(100.0, 20.0),
+        },
+    ];
+
+    for curve in &curves {
+        let h = 1e-6;
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            let dd = (curve.derivative(t + h) - curve.derivative(t - h)) / (2.0 * h);
+            assert!((curve.second_derivative(t) - dd).length() < 1e-3);
+        }
+
+        let mut extrema = Vec::new();
+        curve.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+        assert!(!extrema.is_empty());
+        for &t in &extrema {
+            // The curvature is either larger or smaller than on both sides.
+            let k = curve.curvature(t);
+            let before = curve.curvature(t - 0.001) - k;
+            let after = curve.curvature(t + 0.001) - k;
+            assert!(before * after > 0.0, "{:?} {:?} {:?}", t, before, after);
+        }
+    }
+
+    // The symmetric arch bends the most in its middle.
+    let mut extrema = Vec::new();
+    curves[0].for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert!(extrema.iter().any(|t| (t - 0.5).abs() < 1e-9));
+}
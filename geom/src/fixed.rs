@@ -0,0 +1,901 @@
+//! A fixed point scalar type for deterministic geometry.
+//!
+//! Floating point arithmetic is deterministic in principle, but the results of functions such as
+//! `sin`, `cos`, `powf` or `acos` depend on the platform's math library, which makes it hard to
+//! produce bit-exact results across machines, for example for networked replay or lockstep
+//! simulations.
+//!
+//! [`Fixed`](struct.Fixed.html) implements [`Scalar`](../trait.Scalar.html) with integer
+//! arithmetic, and its transcendental functions are implemented in this module using only
+//! operations that are exactly specified by IEEE 754. As a result, sampling, splitting and
+//! flattening segments with `Fixed` coordinates produces the same results on every platform.
+//!
+//! # Examples
+//!
+//! ```
+//! use lyon_geom::{point, Fixed, QuadraticBezierSegment};
+//!
+//! let fx = Fixed::from_f32;
+//! let curve = QuadraticBezierSegment {
+//!     from: point(fx(0.0), fx(0.0)),
+//!     ctrl: point(fx(100.0), fx(100.0)),
+//!     to: point(fx(200.0), fx(0.0)),
+//! };
+//!
+//! assert_eq!(curve.sample(fx(0.5)), point(fx(100.0), fx(50.0)));
+//!
+//! let mut points = Vec::new();
+//! curve.for_each_flattened(fx(0.1), &mut |p| points.push(p));
+//! assert_eq!(points.last(), Some(&curve.to));
+//! ```
+
+use crate::scalar::{Float, FloatConst, NumCast, Scalar, Trig};
+
+use num_traits::{FromPrimitive, Num, One, ParseFloatError, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::FpCategory;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+const FRACTIONAL_BITS: u32 = 16;
+const ONE_RAW: i64 = 1 << FRACTIONAL_BITS;
+
+/// A signed 48.16 fixed point number.
+///
+/// The value is stored as a 64 bits integer with 16 fractional bits, which gives a precision of
+/// about `1.5e-5` over a range of about `±1.4e14`.
+///
+/// Unlike floating point numbers, `Fixed` has no infinities or NaN. Arithmetic saturates at
+/// `Fixed::MAX` and `Fixed::MIN` (which also represent the infinities), and operations that
+/// would produce a NaN, such as the square root of a negative number, produce zero.
+///
+/// See the [module documentation](index.html).
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// The largest representable value.
+    pub const MAX: Self = Fixed(i64::MAX);
+    /// The smallest representable value.
+    pub const MIN: Self = Fixed(i64::MIN);
+    /// The smallest positive representable value.
+    pub const DELTA: Self = Fixed(1);
+
+    /// Creates a fixed point number from its raw representation, the value multiplied by 2^16.
+    #[inline]
+    pub const fn from_raw(raw: i64) -> Self {
+        Fixed(raw)
+    }
+
+    /// Returns the raw representation of the value, the value multiplied by 2^16.
+    #[inline]
+    pub const fn to_raw(self) -> i64 {
+        self.0
+    }
+
+    /// Creates a fixed point number from an integer, saturating if it is out of range.
+    #[inline]
+    pub fn from_int(v: i64) -> Self {
+        Fixed(v.saturating_mul(ONE_RAW))
+    }
+
+    /// Converts a single precision float, rounding to the nearest representable value.
+    #[inline]
+    pub fn from_f32(v: f32) -> Self {
+        Fixed::from_f64(v as f64)
+    }
+
+    /// Converts a double precision float, rounding to the nearest representable value.
+    ///
+    /// Out of range values saturate and NaN is converted to zero.
+    pub fn from_f64(v: f64) -> Self {
+        if v.is_nan() {
+            return Fixed(0);
+        }
+        let raw = (v * ONE_RAW as f64).round();
+        if raw >= i64::MAX as f64 {
+            Fixed::MAX
+        } else if raw <= i64::MIN as f64 {
+            Fixed::MIN
+        } else {
+            Fixed(raw as i64)
+        }
+    }
+
+    /// Converts to a single precision float.
+    #[inline]
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Converts to a double precision float.
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / ONE_RAW as f64
+    }
+
+    #[inline]
+    fn from_wide(raw: i128) -> Self {
+        if raw > i64::MAX as i128 {
+            Fixed::MAX
+        } else if raw < i64::MIN as i128 {
+            Fixed::MIN
+        } else {
+            Fixed(raw as i64)
+        }
+    }
+
+    /// Applies a function implemented with deterministic double precision arithmetic.
+    #[inline]
+    fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        Fixed::from_f64(f(self.to_f64()))
+    }
+}
+
+/// Division of wide integers, rounding to the nearest integer.
+fn round_div(n: i128, d: i128) -> i128 {
+    let q = n / d;
+    let r = n % d;
+    if 2 * r.abs() >= d.abs() {
+        if (n < 0) == (d < 0) {
+            q + 1
+        } else {
+            q - 1
+        }
+    } else {
+        q
+    }
+}
+
+/// Integer square root, rounded to the nearest integer.
+fn isqrt(n: u128) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+
+    let mut bit: u128 = 1 << ((127 - n.leading_zeros()) & !1);
+    let mut rem = n;
+    let mut res: u128 = 0;
+    while bit != 0 {
+        if rem >= res + bit {
+            rem -= res + bit;
+            res = (res >> 1) + bit;
+        } else {
+            res >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    if rem > res {
+        res + 1
+    } else {
+        res
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        let product = self.0 as i128 * other.0 as i128;
+        Fixed::from_wide((product + (1 << (FRACTIONAL_BITS - 1))) >> FRACTIONAL_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        if other.0 == 0 {
+            return match self.0.cmp(&0) {
+                Ordering::Greater => Fixed::MAX,
+                Ordering::Less => Fixed::MIN,
+                Ordering::Equal => Fixed(0),
+            };
+        }
+
+        Fixed::from_wide(round_div(
+            (self.0 as i128) << FRACTIONAL_BITS,
+            other.0 as i128,
+        ))
+    }
+}
+
+impl Rem for Fixed {
+    type Output = Self;
+    #[inline]
+    fn rem(self, other: Self) -> Self {
+        if other.0 == 0 {
+            return Fixed(0);
+        }
+        Fixed(self.0.wrapping_rem(other.0))
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Fixed {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Fixed {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Fixed {
+    #[inline]
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for Fixed {
+    #[inline]
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fixed({})", Fixed::to_f64(*self))
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&Fixed::to_f64(*self), f)
+    }
+}
+
+impl Zero for Fixed {
+    #[inline]
+    fn zero() -> Self {
+        Fixed(0)
+    }
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl One for Fixed {
+    #[inline]
+    fn one() -> Self {
+        Fixed(ONE_RAW)
+    }
+}
+
+impl Num for Fixed {
+    type FromStrRadixErr = ParseFloatError;
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseFloatError> {
+        <f64 as Num>::from_str_radix(src, radix).map(Fixed::from_f64)
+    }
+}
+
+impl ToPrimitive for Fixed {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        // Rounds towards zero like float to integer conversions.
+        Some(self.0 / ONE_RAW)
+    }
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        if self.0 <= -ONE_RAW {
+            return None;
+        }
+        Some((self.0 / ONE_RAW) as u64)
+    }
+    #[inline]
+    fn to_f32(&self) -> Option<f32> {
+        Some(Fixed::to_f32(*self))
+    }
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(Fixed::to_f64(*self))
+    }
+}
+
+impl FromPrimitive for Fixed {
+    #[inline]
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Fixed::from_int(n))
+    }
+    #[inline]
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Fixed::from_int(n.min(i64::MAX as u64) as i64))
+    }
+    #[inline]
+    fn from_f32(n: f32) -> Option<Self> {
+        Some(Fixed::from_f32(n))
+    }
+    #[inline]
+    fn from_f64(n: f64) -> Option<Self> {
+        Some(Fixed::from_f64(n))
+    }
+}
+
+impl NumCast for Fixed {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        if let Some(v) = n.to_i64() {
+            if let Some(f) = n.to_f64() {
+                if f == v as f64 {
+                    return Some(Fixed::from_int(v));
+                }
+            }
+        }
+        n.to_f64().map(Fixed::from_f64)
+    }
+}
+
+impl Float for Fixed {
+    fn nan() -> Self {
+        Fixed(0)
+    }
+    fn infinity() -> Self {
+        Fixed::MAX
+    }
+    fn neg_infinity() -> Self {
+        Fixed::MIN
+    }
+    fn neg_zero() -> Self {
+        Fixed(0)
+    }
+    fn min_value() -> Self {
+        Fixed::MIN
+    }
+    fn min_positive_value() -> Self {
+        Fixed::DELTA
+    }
+    fn epsilon() -> Self {
+        Fixed::DELTA
+    }
+    fn max_value() -> Self {
+        Fixed::MAX
+    }
+    fn is_nan(self) -> bool {
+        false
+    }
+    fn is_infinite(self) -> bool {
+        self == Fixed::MAX || self == Fixed::MIN
+    }
+    fn is_finite(self) -> bool {
+        !self.is_infinite()
+    }
+    fn is_normal(self) -> bool {
+        self.0 != 0 && self.is_finite()
+    }
+    fn classify(self) -> FpCategory {
+        if self.0 == 0 {
+            FpCategory::Zero
+        } else if self.is_infinite() {
+            FpCategory::Infinite
+        } else {
+            FpCategory::Normal
+        }
+    }
+    fn floor(self) -> Self {
+        Fixed(self.0 & !(ONE_RAW - 1))
+    }
+    fn ceil(self) -> Self {
+        -(-self).floor()
+    }
+    fn round(self) -> Self {
+        // Rounds half-way cases away from zero like floats.
+        let half = Fixed(ONE_RAW / 2);
+        if self.0 >= 0 {
+            (self + half).floor()
+        } else {
+            -(-self + half).floor()
+        }
+    }
+    fn trunc(self) -> Self {
+        Fixed(self.0 / ONE_RAW * ONE_RAW)
+    }
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+    fn abs(self) -> Self {
+        Fixed(self.0.saturating_abs())
+    }
+    fn signum(self) -> Self {
+        if self.0 < 0 {
+            -Fixed::one()
+        } else {
+            Fixed::one()
+        }
+    }
+    fn is_sign_positive(self) -> bool {
+        self.0 >= 0
+    }
+    fn is_sign_negative(self) -> bool {
+        self.0 < 0
+    }
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        let sum = self.0 as i128 * a.0 as i128 + ((b.0 as i128) << FRACTIONAL_BITS);
+        Fixed::from_wide((sum + (1 << (FRACTIONAL_BITS - 1))) >> FRACTIONAL_BITS)
+    }
+    fn recip(self) -> Self {
+        Fixed::one() / self
+    }
+    fn powi(self, n: i32) -> Self {
+        self.map(|x| soft::powi(x, n))
+    }
+    fn powf(self, n: Self) -> Self {
+        if self.0 <= 0 {
+            return Fixed(0);
+        }
+        let n = n.to_f64();
+        self.map(|x| soft::exp(n * soft::ln(x)))
+    }
+    fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Fixed(0);
+        }
+        Fixed(isqrt((self.0 as u128) << FRACTIONAL_BITS) as i64)
+    }
+    fn exp(self) -> Self {
+        self.map(soft::exp)
+    }
+    fn exp2(self) -> Self {
+        self.map(|x| soft::exp(x * std::f64::consts::LN_2))
+    }
+    fn ln(self) -> Self {
+        if self.0 <= 0 {
+            return Fixed::MIN;
+        }
+        self.map(soft::ln)
+    }
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+    fn log2(self) -> Self {
+        if self.0 <= 0 {
+            return Fixed::MIN;
+        }
+        self.map(|x| soft::ln(x) / std::f64::consts::LN_2)
+    }
+    fn log10(self) -> Self {
+        if self.0 <= 0 {
+            return Fixed::MIN;
+        }
+        self.map(|x| soft::ln(x) / std::f64::consts::LN_10)
+    }
+    fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+    fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+    fn abs_sub(self, other: Self) -> Self {
+        Ord::max(self - other, Fixed(0))
+    }
+    fn cbrt(self) -> Self {
+        self.map(|x| {
+            if x == 0.0 {
+                return 0.0;
+            }
+            let y = soft::exp(soft::ln(x.abs()) / 3.0);
+            x.signum() * y
+        })
+    }
+    fn hypot(self, other: Self) -> Self {
+        let x = self.0.unsigned_abs() as u128;
+        let y = other.0.unsigned_abs() as u128;
+        Fixed::from_wide(isqrt(x * x + y * y) as i128)
+    }
+    fn sin(self) -> Self {
+        self.map(|x| soft::sin_cos(x).0)
+    }
+    fn cos(self) -> Self {
+        self.map(|x| soft::sin_cos(x).1)
+    }
+    fn tan(self) -> Self {
+        let (sin, cos) = soft::sin_cos(self.to_f64());
+        Fixed::from_f64(sin) / Fixed::from_f64(cos)
+    }
+    fn asin(self) -> Self {
+        self.map(|x| soft::atan2(x, soft::sqrt_clamped(1.0 - x * x)))
+    }
+    fn acos(self) -> Self {
+        self.map(|x| soft::atan2(soft::sqrt_clamped(1.0 - x * x), x))
+    }
+    fn atan(self) -> Self {
+        self.map(soft::atan)
+    }
+    fn atan2(self, other: Self) -> Self {
+        Fixed::from_f64(soft::atan2(self.to_f64(), other.to_f64()))
+    }
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = soft::sin_cos(self.to_f64());
+        (Fixed::from_f64(sin), Fixed::from_f64(cos))
+    }
+    fn exp_m1(self) -> Self {
+        self.map(|x| soft::exp(x) - 1.0)
+    }
+    fn ln_1p(self) -> Self {
+        (self + Fixed::one()).ln()
+    }
+    fn sinh(self) -> Self {
+        self.map(|x| (soft::exp(x) - soft::exp(-x)) * 0.5)
+    }
+    fn cosh(self) -> Self {
+        self.map(|x| (soft::exp(x) + soft::exp(-x)) * 0.5)
+    }
+    fn tanh(self) -> Self {
+        self.map(|x| {
+            let e = soft::exp(2.0 * x);
+            (e - 1.0) / (e + 1.0)
+        })
+    }
+    fn asinh(self) -> Self {
+        self.map(|x| x.signum() * soft::ln(x.abs() + (x * x + 1.0).sqrt()))
+    }
+    fn acosh(self) -> Self {
+        if self.0 < ONE_RAW {
+            return Fixed(0);
+        }
+        self.map(|x| soft::ln(x + (x * x - 1.0).sqrt()))
+    }
+    fn atanh(self) -> Self {
+        if self.0 >= ONE_RAW {
+            return Fixed::MAX;
+        }
+        if self.0 <= -ONE_RAW {
+            return Fixed::MIN;
+        }
+        self.map(|x| 0.5 * soft::ln((1.0 + x) / (1.0 - x)))
+    }
+    fn integer_decode(self) -> (u64, i16, i8) {
+        let sign = if self.0 < 0 { -1 } else { 1 };
+        (self.0.unsigned_abs(), -(FRACTIONAL_BITS as i16), sign)
+    }
+}
+
+macro_rules! fixed_consts {
+    ($($name:ident,)*) => {
+        impl FloatConst for Fixed {
+            $(
+                #[inline]
+                fn $name() -> Self {
+                    Fixed::from_f64(std::f64::consts::$name)
+                }
+            )*
+        }
+    };
+}
+
+fixed_consts! {
+    E, FRAC_1_PI, FRAC_1_SQRT_2, FRAC_2_PI, FRAC_2_SQRT_PI, FRAC_PI_2, FRAC_PI_3, FRAC_PI_4,
+    FRAC_PI_6, FRAC_PI_8, LN_10, LN_2, LOG10_E, LOG2_E, PI, SQRT_2,
+}
+
+impl Trig for Fixed {
+    #[inline]
+    fn sin(self) -> Self {
+        Float::sin(self)
+    }
+    #[inline]
+    fn cos(self) -> Self {
+        Float::cos(self)
+    }
+    #[inline]
+    fn tan(self) -> Self {
+        Float::tan(self)
+    }
+    #[inline]
+    fn fast_atan2(y: Self, x: Self) -> Self {
+        Float::atan2(y, x)
+    }
+    #[inline]
+    fn degrees_to_radians(deg: Self) -> Self {
+        deg.map(|x| x * (std::f64::consts::PI / 180.0))
+    }
+    #[inline]
+    fn radians_to_degrees(rad: Self) -> Self {
+        rad.map(|x| x * (180.0 / std::f64::consts::PI))
+    }
+}
+
+impl Scalar for Fixed {
+    const HALF: Self = Fixed(ONE_RAW / 2);
+    const ZERO: Self = Fixed(0);
+    const ONE: Self = Fixed(ONE_RAW);
+    const TWO: Self = Fixed(ONE_RAW * 2);
+    const THREE: Self = Fixed(ONE_RAW * 3);
+    const FOUR: Self = Fixed(ONE_RAW * 4);
+    const FIVE: Self = Fixed(ONE_RAW * 5);
+    const SIX: Self = Fixed(ONE_RAW * 6);
+    const SEVEN: Self = Fixed(ONE_RAW * 7);
+    const EIGHT: Self = Fixed(ONE_RAW * 8);
+    const NINE: Self = Fixed(ONE_RAW * 9);
+    const TEN: Self = Fixed(ONE_RAW * 10);
+
+    const EPSILON: Self = Fixed(4);
+
+    #[inline]
+    fn value(v: f32) -> Self {
+        Fixed::from_f32(v)
+    }
+}
+
+/// Elementary functions implemented with the basic floating point operations and square roots,
+/// the results of which are exactly specified by IEEE 754, unlike the ones from the platform's
+/// math library.
+///
+/// The results are only precise enough to be rounded to `Fixed`.
+mod soft {
+    use std::f64::consts::{FRAC_PI_2, LN_2, PI};
+
+    pub fn powi(mut x: f64, n: i32) -> f64 {
+        let mut exp = n.unsigned_abs();
+        let mut result = 1.0;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result *= x;
+            }
+            x *= x;
+            exp >>= 1;
+        }
+
+        if n < 0 {
+            1.0 / result
+        } else {
+            result
+        }
+    }
+
+    pub fn sqrt_clamped(x: f64) -> f64 {
+        if x > 0.0 {
+            x.sqrt()
+        } else {
+            0.0
+        }
+    }
+
+    pub fn exp(x: f64) -> f64 {
+        if x > 64.0 {
+            return f64::MAX;
+        }
+        if x < -64.0 {
+            return 0.0;
+        }
+
+        // exp(x) = 2^k * exp(r) with |r| <= ln(2) / 2.
+        let k = (x / LN_2).round();
+        let r = x - k * LN_2;
+
+        let mut term = 1.0;
+        let mut sum = 1.0;
+        for i in 1..16 {
+            term *= r / i as f64;
+            sum += term;
+        }
+
+        sum * f64::from_bits(((k as i64 + 1023) as u64) << 52)
+    }
+
+    /// Expects a strictly positive input.
+    pub fn ln(x: f64) -> f64 {
+        // x = m * 2^e with m in [0.75, 1.5).
+        let bits = x.to_bits();
+        let mut e = ((bits >> 52) & 0x7ff) as i64 - 1023;
+        let mut m = f64::from_bits((bits & ((1 << 52) - 1)) | (1023 << 52));
+        if m > 1.5 {
+            m *= 0.5;
+            e += 1;
+        }
+
+        // ln(m) = 2 * atanh(s) with |s| <= 0.2.
+        let s = (m - 1.0) / (m + 1.0);
+        let s2 = s * s;
+        let mut term = s;
+        let mut sum = 0.0;
+        for i in 0..12 {
+            sum += term / (2 * i + 1) as f64;
+            term *= s2;
+        }
+
+        e as f64 * LN_2 + 2.0 * sum
+    }
+
+    pub fn sin_cos(x: f64) -> (f64, f64) {
+        // x = k * pi / 2 + r with |r| <= pi / 4.
+        let k = (x / FRAC_PI_2).round();
+        let r = x - k * FRAC_PI_2;
+        let r2 = r * r;
+
+        let mut sin = 0.0;
+        let mut cos = 0.0;
+        let mut sin_term = r;
+        let mut cos_term = 1.0;
+        for i in 0..10 {
+            sin += sin_term;
+            cos += cos_term;
+            let n = (2 * i + 2) as f64;
+            sin_term *= -r2 / (n * (n + 1.0));
+            cos_term *= -r2 / ((n - 1.0) * n);
+        }
+
+        match (k as i64).rem_euclid(4) {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+
+    pub fn atan(x: f64) -> f64 {
+        if x < 0.0 {
+            return -atan(-x);
+        }
+        if x > 1.0 {
+            return FRAC_PI_2 - atan(1.0 / x);
+        }
+
+        // Halve the angle twice so that the series converges quickly.
+        let mut y = x;
+        for _ in 0..2 {
+            y /= 1.0 + (1.0 + y * y).sqrt();
+        }
+
+        let y2 = y * y;
+        let mut term = y;
+        let mut sum = 0.0;
+        for i in 0..12 {
+            sum += term / (2 * i + 1) as f64;
+            term *= -y2;
+        }
+
+        sum * 4.0
+    }
+
+    pub fn atan2(y: f64, x: f64) -> f64 {
+        if x > 0.0 {
+            atan(y / x)
+        } else if x < 0.0 {
+            if y >= 0.0 {
+                atan(y / x) + PI
+            } else {
+                atan(y / x) - PI
+            }
+        } else if y > 0.0 {
+            FRAC_PI_2
+        } else if y < 0.0 {
+            -FRAC_PI_2
+        } else {
+            0.0
+        }
+    }
+}
+
+#[test]
+fn fixed_arithmetic() {
+    let fx = Fixed::from_f64;
+
+    assert_eq!(fx(1.5) + fx(2.25), fx(3.75));
+    assert_eq!(fx(1.5) - fx(2.25), fx(-0.75));
+    assert_eq!(fx(1.5) * fx(-2.25), fx(-3.375));
+    assert_eq!(fx(1.0) / fx(4.0), fx(0.25));
+    assert_eq!(fx(-1.0) / fx(3.0), Fixed::from_raw(-21845));
+    assert_eq!(fx(1.0) / fx(0.0), Fixed::MAX);
+    assert_eq!(Fixed::MAX + fx(1.0), Fixed::MAX);
+    assert_eq!(Fixed::MIN * fx(2.0), Fixed::MIN);
+
+    assert_eq!(fx(-1.25).floor(), fx(-2.0));
+    assert_eq!(fx(-1.25).ceil(), fx(-1.0));
+    assert_eq!(fx(-1.5).round(), fx(-2.0));
+    assert_eq!(fx(-1.25).trunc(), fx(-1.0));
+    assert_eq!(fx(-1.25).fract(), fx(-0.25));
+
+    assert_eq!(fx(2.0).sqrt(), fx(std::f64::consts::SQRT_2));
+    assert_eq!(fx(3.0).hypot(fx(4.0)), fx(5.0));
+    assert_eq!(
+        Fixed::from_int(1_000_000)
+            .hypot(Fixed::from_int(1_000_000))
+            .to_raw()
+            >> 16,
+        1_414_213
+    );
+
+    for i in -100..100 {
+        let x = i as f64 * 0.173;
+        let close = |a: Fixed, b: f64| (a.to_f64() - b).abs() <= 2.0 / 65536.0;
+        assert!(close(Float::sin(fx(x)), fx(x).to_f64().sin()), "sin({})", x);
+        assert!(close(Float::cos(fx(x)), fx(x).to_f64().cos()), "cos({})", x);
+        assert!(
+            close(Float::atan(fx(x)), fx(x).to_f64().atan()),
+            "atan({})",
+            x
+        );
+        assert!(
+            close(fx(x).atan2(fx(1.5)), fx(x).to_f64().atan2(1.5)),
+            "atan2({})",
+            x
+        );
+        assert!(close(fx(x).cbrt(), fx(x).to_f64().cbrt()), "cbrt({})", x);
+        if x.abs() < 1.0 {
+            assert!(close(fx(x).acos(), fx(x).to_f64().acos()), "acos({})", x);
+        }
+        if x > 0.0 {
+            assert!(close(fx(x).ln(), fx(x).to_f64().ln()), "ln({})", x);
+            assert!(close(
+                fx(x).powf(fx(1.0 / 6.0)),
+                fx(x).to_f64().powf(fx(1.0 / 6.0).to_f64())
+            ));
+        }
+        if x < 5.0 {
+            assert!(close(fx(x).exp(), fx(x).to_f64().exp()), "exp({})", x);
+        }
+    }
+}
+
+#[test]
+fn fixed_segments() {
+    use crate::{point, CubicBezierSegment, LineSegment};
+
+    let fx = Fixed::from_f32;
+    let line = LineSegment {
+        from: point(fx(0.0), fx(0.0)),
+        to: point(fx(10.0), fx(-5.0)),
+    };
+    assert_eq!(line.sample(fx(0.25)), point(fx(2.5), fx(-1.25)));
+    assert_eq!(line.split(fx(0.5)).0.to, point(fx(5.0), fx(-2.5)));
+
+    let curve = CubicBezierSegment {
+        from: point(fx(0.0), fx(0.0)),
+        ctrl1: point(fx(10.0), fx(100.0)),
+        ctrl2: point(fx(100.0), fx(-50.0)),
+        to: point(fx(100.0), fx(20.0)),
+    };
+    let (a, b) = curve.split(fx(0.5));
+    assert_eq!(a.to, b.from);
+    assert_eq!(a.to, curve.sample(fx(0.5)));
+
+    // The flattened curve closely matches the floating point version.
+    let curve_f64 = CubicBezierSegment {
+        from: curve.from.cast::<f64>(),
+        ctrl1: curve.ctrl1.cast::<f64>(),
+        ctrl2: curve.ctrl2.cast::<f64>(),
+        to: curve.to.cast::<f64>(),
+    };
+    let mut expected = Vec::new();
+    curve_f64.for_each_flattened(0.1, &mut |p| expected.push(p));
+    let mut count = 0;
+    curve.for_each_flattened(fx(0.1), &mut |p| {
+        assert!((p.cast::<f64>() - expected[count]).length() < 0.01);
+        count += 1;
+    });
+    assert_eq!(count, expected.len());
+    let mut last = point(fx(0.0), fx(0.0));
+    curve.for_each_flattened(fx(0.1), &mut |p| last = p);
+    assert_eq!(last, curve.to);
+}
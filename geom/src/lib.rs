@@ -14,8 +14,9 @@
 //!
 //! - lines and line segments,
 //! - quadratic and cubic bézier curves,
+//! - conic sections (rational quadratic bézier curves),
 //! - elliptic arcs,
-//! - triangles.
+//! - triangles and polygons.
 //!
 //! # Flattening
 //!
@@ -88,12 +89,18 @@ pub extern crate serde;
 #[macro_use]
 mod segment;
 pub mod arc;
+pub mod arc_length;
+pub mod conic;
 pub mod cubic_bezier;
 mod cubic_bezier_intersections;
 pub mod cubic_to_quadratic;
+pub mod fixed;
 mod flatten_cubic;
 mod line;
 mod monotonic;
+mod offset;
+mod polygon;
+pub mod predicates;
 pub mod quadratic_bezier;
 mod triangle;
 pub mod utils;
@@ -101,12 +108,20 @@ pub mod utils;
 #[doc(inline)]
 pub use crate::arc::{Arc, ArcFlags, SvgArc};
 #[doc(inline)]
+pub use crate::arc_length::ArcLengthTable;
+#[doc(inline)]
+pub use crate::conic::ConicSegment;
+#[doc(inline)]
 pub use crate::cubic_bezier::CubicBezierSegment;
 #[doc(inline)]
+pub use crate::fixed::Fixed;
+#[doc(inline)]
 pub use crate::line::{Line, LineEquation, LineSegment};
 #[doc(inline)]
 pub use crate::monotonic::Monotonic;
 #[doc(inline)]
+pub use crate::polygon::PolygonShape;
+#[doc(inline)]
 pub use crate::quadratic_bezier::QuadraticBezierSegment;
 #[doc(inline)]
 pub use crate::segment::{BezierSegment, Segment};
@@ -115,6 +130,16 @@ pub use crate::triangle::Triangle;
 
 pub use crate::scalar::Scalar;
 
+/// The two possible orientations for the edges of a shape to be built in.
+///
+/// Positive winding corresponds to the positive orientation in trigonometry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Winding {
+    Positive,
+    Negative,
+}
+
 mod scalar {
     pub(crate) use euclid::Trig;
     pub(crate) use num_traits::cast::cast;
@@ -316,4 +341,34 @@ pub mod traits {
             (*self).transform_vector(v)
         }
     }
+
+    /// A non-affine mapping of points, for example a perspective projection or a mesh warp.
+    ///
+    /// Unlike with a `Transformation`, straight lines are not necessarily mapped to straight
+    /// lines, so curves can't be warped by simply mapping their control points.
+    ///
+    /// This is implemented for closures and for 3D transforms (projecting the z = 0 plane).
+    pub trait Warp<S> {
+        /// Maps a point, or returns `None` if it can't be mapped, for example because a
+        /// perspective projection puts it on or behind the viewer.
+        fn warp_point(&self, p: Point<S>) -> Option<Point<S>>;
+    }
+
+    impl<S, F> Warp<S> for F
+    where
+        F: Fn(Point<S>) -> Point<S>,
+    {
+        #[inline]
+        fn warp_point(&self, p: Point<S>) -> Option<Point<S>> {
+            Some(self(p))
+        }
+    }
+
+    // Points that are projected on or behind the viewer (w <= 0) can't be mapped.
+    impl<S: Scalar> Warp<S> for euclid::default::Transform3D<S> {
+        #[inline]
+        fn warp_point(&self, p: Point<S>) -> Option<Point<S>> {
+            self.transform_point2d(p)
+        }
+    }
 }
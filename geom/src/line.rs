@@ -172,6 +172,26 @@ impl<S: Scalar> LineSegment<S> {
         self.to_vector().length()
     }
 
+    /// Computes the length of the segment between its start and the point at `t`.
+    ///
+    /// `t` is clamped to the range [0, 1].
+    #[inline]
+    pub fn length_at_t(&self, t: S) -> S {
+        self.length() * S::min(S::max(t, S::ZERO), S::ONE)
+    }
+
+    /// Returns the `t` parameter of the point at a given length along the segment.
+    ///
+    /// The tolerance is unused, the result is exact.
+    pub fn t_at_length(&self, length: S, _tolerance: S) -> S {
+        let total = self.length();
+        if total == S::ZERO {
+            return S::ZERO;
+        }
+
+        S::min(S::max(length / total, S::ZERO), S::ONE)
+    }
+
     /// Changes the segment's length, moving destination point.
     pub fn set_length(&mut self, new_length: S) {
         let v = self.to_vector();
@@ -179,6 +199,31 @@ impl<S: Scalar> LineSegment<S> {
         self.to = self.from + v * (new_length / old_length);
     }
 
+    /// Find the `t` parameter of the closest point on the segment to a given position.
+    pub fn closest_point_t(&self, p: Point<S>) -> S {
+        let v = self.to_vector();
+        let square_length = v.square_length();
+        if square_length == S::ZERO {
+            return S::ZERO;
+        }
+
+        let t = (p - self.from).dot(v) / square_length;
+
+        t.max(S::ZERO).min(S::ONE)
+    }
+
+    /// Find the closest point on the segment to a given position.
+    #[inline]
+    pub fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.sample(self.closest_point_t(p))
+    }
+
+    /// Compute the distance between this segment and a given position.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point<S>) -> S {
+        (self.closest_point(p) - p).length()
+    }
+
     #[inline]
     pub fn translate(&mut self, by: Vector<S>) -> Self {
         LineSegment {
@@ -409,6 +454,13 @@ impl<S: Scalar> Segment for LineSegment<S> {
     fn dy(&self, _t: S) -> S {
         self.to.y - self.from.y
     }
+    fn second_derivative(&self, _t: S) -> Vector<S> {
+        vector(S::ZERO, S::ZERO)
+    }
+    fn curvature(&self, _t: S) -> S {
+        S::ZERO
+    }
+    fn for_each_curvature_extremum_t<F: FnMut(S)>(&self, _cb: &mut F) {}
     fn split_range(&self, t_range: Range<S>) -> Self {
         self.split_range(t_range)
     }
@@ -427,6 +479,15 @@ impl<S: Scalar> Segment for LineSegment<S> {
     fn approximate_length(&self, _tolerance: S) -> S {
         self.length()
     }
+    fn closest_point_t(&self, p: Point<S>) -> S {
+        self.closest_point_t(p)
+    }
+    fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.closest_point(p)
+    }
+    fn distance_to_point(&self, p: Point<S>) -> S {
+        self.distance_to_point(p)
+    }
 }
 
 impl<S: Scalar> BoundingRect for LineSegment<S> {
@@ -959,3 +1020,27 @@ fn horizontal_line_intersection() {
     assert_eq!(segment.horizontal_line_intersection_t(1.5), None);
     assert_eq!(segment.horizontal_line_intersection_t(3.5), None);
 }
+
+#[test]
+fn closest_point() {
+    let segment = LineSegment {
+        from: point(1.0, 1.0),
+        to: point(5.0, 1.0),
+    };
+
+    assert_eq!(segment.closest_point_t(point(3.0, 4.0)), 0.5);
+    assert_eq!(segment.closest_point(point(3.0, 4.0)), point(3.0, 1.0));
+    assert_eq!(segment.distance_to_point(point(3.0, 4.0)), 3.0);
+
+    // Positions beyond the end points project onto the end points.
+    assert_eq!(segment.closest_point_t(point(-2.0, 0.0)), 0.0);
+    assert_eq!(segment.closest_point_t(point(8.0, -3.0)), 1.0);
+    assert_eq!(segment.distance_to_point(point(8.0, -3.0)), 5.0);
+
+    // Degenerate segment.
+    let segment = LineSegment {
+        from: point(1.0, 1.0),
+        to: point(1.0, 1.0),
+    };
+    assert_eq!(segment.distance_to_point(point(4.0, 5.0)), 5.0);
+}
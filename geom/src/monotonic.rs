@@ -61,6 +61,18 @@ impl<T: Segment> Monotonic<T> {
         self.segment.dy(t)
     }
     #[inline]
+    pub fn second_derivative(&self, t: T::Scalar) -> Vector<T::Scalar> {
+        self.segment.second_derivative(t)
+    }
+    #[inline]
+    pub fn curvature(&self, t: T::Scalar) -> T::Scalar {
+        self.segment.curvature(t)
+    }
+    #[inline]
+    pub fn for_each_curvature_extremum_t<F: FnMut(T::Scalar)>(&self, cb: &mut F) {
+        self.segment.for_each_curvature_extremum_t(cb)
+    }
+    #[inline]
     pub fn split_range(&self, t_range: Range<T::Scalar>) -> Self {
         Self {
             segment: self.segment.split_range(t_range),
@@ -93,6 +105,18 @@ impl<T: Segment> Monotonic<T> {
     pub fn approximate_length(&self, tolerance: T::Scalar) -> T::Scalar {
         self.segment.approximate_length(tolerance)
     }
+    #[inline]
+    pub fn closest_point_t(&self, p: Point<T::Scalar>) -> T::Scalar {
+        self.segment.closest_point_t(p)
+    }
+    #[inline]
+    pub fn closest_point(&self, p: Point<T::Scalar>) -> Point<T::Scalar> {
+        self.segment.closest_point(p)
+    }
+    #[inline]
+    pub fn distance_to_point(&self, p: Point<T::Scalar>) -> T::Scalar {
+        self.segment.distance_to_point(p)
+    }
 }
 
 impl<T: Segment> Segment for Monotonic<T> {
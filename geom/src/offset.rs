@@ -0,0 +1,406 @@
+use crate::scalar::Scalar;
+use crate::{vector, CubicBezierSegment, Point, QuadraticBezierSegment, Vector};
+use arrayvec::ArrayVec;
+
+use std::ops::Range;
+
+#[cfg(test)]
+use crate::point;
+
+/// Approximates the offset of a quadratic bézier segment with a sequence of quadratic béziers.
+///
+/// See `QuadraticBezierSegment::offset`.
+pub(crate) fn offset_quadratic<S: Scalar, F>(
+    curve: &QuadraticBezierSegment<S>,
+    distance: S,
+    tolerance: S,
+    cb: &mut F,
+) where
+    F: FnMut(&QuadraticBezierSegment<S>),
+{
+    debug_assert!(tolerance >= S::EPSILON);
+
+    if distance == S::ZERO {
+        cb(curve);
+        return;
+    }
+
+    // The only place where the tangent of a quadratic bézier curve can flip is when
+    // the control point is aligned with the endpoints but outside of the baseline.
+    let mut splits: ArrayVec<[S; 3]> = ArrayVec::new();
+    splits.push(S::ZERO);
+    let v0 = curve.ctrl - curve.from;
+    let v1 = curve.to - curve.ctrl;
+    let l0 = v0.length();
+    let l1 = v1.length();
+    if l0 > S::ZERO
+        && l1 > S::ZERO
+        && v0.dot(v1) < S::ZERO
+        && S::abs(v0.cross(v1)) <= S::EPSILON * l0 * l1
+    {
+        splits.push(l0 / (l0 + l1));
+    }
+    splits.push(S::ONE);
+
+    for_each_sub_range(&splits, &mut |range, force| {
+        let sub_curve = curve.split_range(range);
+        let (t0, t1) = match (
+            quadratic_start_tangent(&sub_curve),
+            quadratic_end_tangent(&sub_curve),
+        ) {
+            (Some(t0), Some(t1)) => (t0, t1),
+            // A degenerate curve, nothing to offset.
+            _ => return true,
+        };
+
+        let from = sub_curve.from + normal(t0) * distance;
+        let to = sub_curve.to + normal(t1) * distance;
+
+        // The control point is at the intersection of the offset tangents.
+        let cross = t0.cross(t1);
+        let ctrl = if S::abs(cross) > S::EPSILON {
+            from + t0 * ((to - from).cross(t1) / cross)
+        } else if force || sub_curve.is_linear(tolerance) {
+            from.lerp(to, S::HALF)
+        } else {
+            return false;
+        };
+
+        let offset_curve = QuadraticBezierSegment { from, ctrl, to };
+        if !force
+            && !offset_is_within_tolerance(
+                &|t| offset_curve.sample(t),
+                &|p| sub_curve.distance_to_point(p),
+                distance,
+                tolerance,
+            )
+        {
+            return false;
+        }
+
+        cb(&offset_curve);
+
+        true
+    });
+}
+
+/// Approximates the offset of a cubic bézier segment with a sequence of cubic béziers.
+///
+/// See `CubicBezierSegment::offset`.
+pub(crate) fn offset_cubic<S: Scalar, F>(
+    curve: &CubicBezierSegment<S>,
+    distance: S,
+    tolerance: S,
+    cb: &mut F,
+) where
+    F: FnMut(&CubicBezierSegment<S>),
+{
+    debug_assert!(tolerance >= S::EPSILON);
+
+    if distance == S::ZERO {
+        cb(curve);
+        return;
+    }
+
+    let mut splits: ArrayVec<[S; 8]> = ArrayVec::new();
+    splits.push(S::ZERO);
+    curve.for_each_inflection_t(&mut |t| {
+        splits.push(t);
+    });
+    curve.for_each_cusp_t(&mut |t| {
+        splits.push(t);
+    });
+    splits.push(S::ONE);
+    // Don't let NaN parameters from non-finite coordinates break the sort.
+    splits.retain(|t| t.is_finite());
+    splits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    for_each_sub_range(&splits, &mut |range, force| {
+        let sub_curve = curve.split_range(range);
+        let (t0, t1) = match (
+            cubic_start_tangent(&sub_curve),
+            cubic_end_tangent(&sub_curve),
+        ) {
+            (Some(t0), Some(t1)) => (t0, t1),
+            // A degenerate curve, nothing to offset.
+            _ => return true,
+        };
+
+        let from = sub_curve.from + normal(t0) * distance;
+        let to = sub_curve.to + normal(t1) * distance;
+
+        // Pick the length of the tangents so that the approximation goes through the
+        // offset of the middle of the curve:
+        // from + 3 * a * t0 / 8 + to - 3 * b * t1 / 8 = mid
+        let mid_tangent = sub_curve.derivative(S::HALF).try_normalize().unwrap_or(t0);
+        let mid = sub_curve.sample(S::HALF) + normal(mid_tangent) * distance;
+        let v = (mid * S::value(8.0) - (from.to_vector() + to.to_vector()) * S::FOUR).to_vector()
+            / S::THREE;
+        let cross = t0.cross(t1);
+        let mut lengths = None;
+        if S::abs(cross) > S::EPSILON {
+            let a = v.cross(t1) / cross;
+            let b = -t0.cross(v) / cross;
+            if a >= S::ZERO && b >= S::ZERO {
+                lengths = Some((a, b));
+            }
+        }
+
+        // Otherwise scale the original tangents like the baseline.
+        let (a, b) = lengths.unwrap_or_else(|| {
+            let baseline = (sub_curve.to - sub_curve.from).length();
+            let scale = if baseline > S::ZERO {
+                (to - from).length() / baseline
+            } else {
+                S::ONE
+            };
+            (
+                (sub_curve.ctrl1 - sub_curve.from).length() * scale,
+                (sub_curve.to - sub_curve.ctrl2).length() * scale,
+            )
+        });
+
+        let offset_curve = CubicBezierSegment {
+            from,
+            ctrl1: from + t0 * a,
+            ctrl2: to - t1 * b,
+            to,
+        };
+
+        if !force
+            && !offset_is_within_tolerance(
+                &|t| offset_curve.sample(t),
+                &|p| sub_curve.distance_to_point(p),
+                distance,
+                tolerance,
+            )
+        {
+            return false;
+        }
+
+        cb(&offset_curve);
+
+        true
+    });
+}
+
+/// Splits each range between consecutive values of `splits` in halves until the callback
+/// accepts the sub-range.
+///
+/// The callback is forced to accept ranges that are too small to be split further.
+fn for_each_sub_range<S: Scalar, F>(splits: &[S], cb: &mut F)
+where
+    F: FnMut(Range<S>, bool) -> bool,
+{
+    let min_range = S::value(1.0 / 1024.0);
+    for i in 1..splits.len() {
+        let start = splits[i - 1];
+        let end = splits[i];
+        if end <= start {
+            continue;
+        }
+
+        let mut range = start..end;
+        loop {
+            let force = range.end - range.start <= min_range;
+            if cb(range.clone(), force) {
+                if range.end >= end {
+                    break;
+                }
+                range.start = range.end;
+                range.end = end;
+            } else {
+                range.end = (range.start + range.end) * S::HALF;
+            }
+        }
+    }
+}
+
+fn offset_is_within_tolerance<S: Scalar>(
+    sample: &dyn Fn(S) -> Point<S>,
+    distance_to_curve: &dyn Fn(Point<S>) -> S,
+    distance: S,
+    tolerance: S,
+) -> bool {
+    let expected = S::abs(distance);
+    for &t in &[0.1, 0.3, 0.5, 0.7, 0.9] {
+        let d = distance_to_curve(sample(S::value(t)));
+        if S::abs(d - expected) > tolerance {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[inline]
+fn normal<S: Scalar>(tangent: Vector<S>) -> Vector<S> {
+    vector(-tangent.y, tangent.x)
+}
+
+fn first_tangent<S: Scalar>(from: Point<S>, points: &[Point<S>]) -> Option<Vector<S>> {
+    for p in points {
+        if let Some(v) = (*p - from).try_normalize() {
+            return Some(v);
+        }
+    }
+
+    None
+}
+
+fn quadratic_start_tangent<S: Scalar>(curve: &QuadraticBezierSegment<S>) -> Option<Vector<S>> {
+    first_tangent(curve.from, &[curve.ctrl, curve.to])
+}
+
+fn quadratic_end_tangent<S: Scalar>(curve: &QuadraticBezierSegment<S>) -> Option<Vector<S>> {
+    first_tangent(curve.to, &[curve.ctrl, curve.from]).map(|v| -v)
+}
+
+fn cubic_start_tangent<S: Scalar>(curve: &CubicBezierSegment<S>) -> Option<Vector<S>> {
+    first_tangent(curve.from, &[curve.ctrl1, curve.ctrl2, curve.to])
+}
+
+fn cubic_end_tangent<S: Scalar>(curve: &CubicBezierSegment<S>) -> Option<Vector<S>> {
+    first_tangent(curve.to, &[curve.ctrl2, curve.ctrl1, curve.from]).map(|v| -v)
+}
+
+#[cfg(test)]
+fn check_offset<F>(distance: f64, tolerance: f64, distance_to_curve: F, samples: &[Point<f64>])
+where
+    F: Fn(Point<f64>) -> f64,
+{
+    for p in samples {
+        let d = distance_to_curve(*p);
+        assert!(
+            (d - distance.abs()).abs() <= tolerance * 1.01,
+            "{:?} is at distance {} from the curve",
+            p,
+            d
+        );
+    }
+}
+
+#[test]
+fn offset_quadratic_bezier() {
+    let curve = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(50.0, 100.0),
+        to: point(100.0, 0.0),
+    };
+
+    for &distance in &[5.0, -5.0, 20.0] {
+        let tolerance = 0.05;
+        let mut prev: Option<Point<f64>> = None;
+        let mut count = 0;
+        let mut samples = Vec::new();
+        curve.offset(distance, tolerance, &mut |c| {
+            if let Some(prev) = prev {
+                assert!((c.from - prev).length() < 1e-6);
+            } else {
+                assert!(((c.from - point(0.0, 0.0)).length() - distance.abs()).abs() < 1e-6);
+            }
+            prev = Some(c.to);
+            count += 1;
+            for i in 0..10 {
+                samples.push(c.sample(i as f64 / 10.0));
+            }
+        });
+
+        assert!(count > 1);
+        assert!(((prev.unwrap() - point(100.0, 0.0)).length() - distance.abs()).abs() < 1e-6);
+        check_offset(
+            distance,
+            tolerance,
+            |p| curve.distance_to_point(p),
+            &samples,
+        );
+    }
+
+    // Positive distances offset the curve along (-tangent.y, tangent.x).
+    let line = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(5.0, 0.0),
+        to: point(10.0, 0.0),
+    };
+    let mut count = 0;
+    line.offset(2.0, 0.01, &mut |c| {
+        assert_eq!(c.from, point(0.0, 2.0));
+        assert_eq!(c.to, point(10.0, 2.0));
+        count += 1;
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn offset_cubic_bezier() {
+    let curves = [
+        // Simple arch.
+        CubicBezierSegment {
+            from: point(0.0f64, 0.0),
+            ctrl1: point(0.0, 100.0),
+            ctrl2: point(100.0, 100.0),
+            to: point(100.0, 0.0),
+        },
+        // Inflection.
+        CubicBezierSegment {
+            from: point(0.0, 0.0),
+            ctrl1: point(50.0, 100.0),
+            ctrl2: point(50.0, -100.0),
+            to: point(100.0, 0.0),
+        },
+    ];
+
+    for curve in &curves {
+        for &distance in &[2.0, -2.0, 10.0] {
+            let tolerance = 0.05;
+            let mut samples = Vec::new();
+            let mut count = 0;
+            curve.offset(distance, tolerance, &mut |c| {
+                for i in 0..10 {
+                    samples.push(c.sample(i as f64 / 10.0));
+                }
+                count += 1;
+            });
+
+            assert!(count < 10);
+            check_offset(
+                distance,
+                tolerance,
+                |p| curve.distance_to_point(p),
+                &samples,
+            );
+        }
+    }
+
+    // Near a cusp the exact offset is not within the offset distance of the curve, only
+    // check that the curve is split at the cusp.
+    let cusp = CubicBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl1: point(100.0, 100.0),
+        ctrl2: point(0.0, 100.0),
+        to: point(100.0, 0.0),
+    };
+    let mut endpoints = Vec::new();
+    cusp.offset(2.0, 0.05, &mut |c| {
+        endpoints.push((c.from, c.to));
+    });
+    assert_eq!(endpoints.len(), 2);
+    assert!((endpoints[0].0 - point(-2.0_f64.sqrt(), 2.0_f64.sqrt())).length() < 1e-6);
+    assert!((endpoints[0].1 - point(50.0, 75.0)).length() > 1.99);
+
+    let mut cusps = Vec::new();
+    cusp.for_each_cusp_t(&mut |t| cusps.push(t));
+    assert_eq!(cusps.len(), 1);
+    assert!((cusps[0] - 0.5).abs() < 1e-6);
+
+    let mut cusps = Vec::new();
+    curves[1].for_each_cusp_t(&mut |t| cusps.push(t));
+    assert!(cusps.is_empty());
+
+    // Non-finite coordinates don't cause a panic.
+    let nan = CubicBezierSegment {
+        ctrl1: point(std::f64::NAN, 100.0),
+        ..curves[1]
+    };
+    nan.offset(2.0, 0.05, &mut |_| {});
+}
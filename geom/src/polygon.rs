@@ -0,0 +1,296 @@
+use crate::scalar::Scalar;
+use crate::{Point, Rect, Vector, Winding};
+
+/// A closed polygon, defined by a sequence of vertices.
+///
+/// The last vertex is implicitly connected to the first one, which doesn't need to be
+/// repeated. This type only borrows the vertices, making it cheap to create from existing
+/// data such as the output of a tessellator.
+///
+/// This is a geometric shape for queries, unrelated to `lyon_path::polygon::Polygon`
+/// which is a path made of straight lines.
+///
+/// ```
+/// use lyon_geom::{point, PolygonShape};
+///
+/// let square = PolygonShape {
+///     points: &[point(0.0, 0.0), point(1.0, 0.0), point(1.0, 1.0), point(0.0, 1.0)],
+/// };
+///
+/// assert_eq!(square.area(), 1.0);
+/// assert!(square.is_convex());
+/// assert!(square.contains_point(point(0.5, 0.5)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PolygonShape<'l, S> {
+    pub points: &'l [Point<S>],
+}
+
+impl<'l, S: Scalar> PolygonShape<'l, S> {
+    /// Returns the area of the polygon, positive if its vertices are in the positive
+    /// orientation and negative otherwise.
+    ///
+    /// The areas of the parts of self-intersecting polygons that have opposite orientations
+    /// cancel out.
+    pub fn signed_area(&self) -> S {
+        let mut area = S::ZERO;
+        self.for_each_edge_cross(&mut |_, _, cross| {
+            area += cross;
+        });
+
+        area * S::HALF
+    }
+
+    /// Returns the area of the polygon.
+    ///
+    /// See `signed_area`.
+    pub fn area(&self) -> S {
+        S::abs(self.signed_area())
+    }
+
+    /// Returns the orientation of the vertices of the polygon, or `None` if its area is zero.
+    pub fn orientation(&self) -> Option<Winding> {
+        let area = self.signed_area();
+        if area > S::ZERO {
+            Some(Winding::Positive)
+        } else if area < S::ZERO {
+            Some(Winding::Negative)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the center of mass of the polygon, or `None` if it has no vertex.
+    ///
+    /// For polygons with an area of zero, this is the average of the vertices.
+    pub fn centroid(&self) -> Option<Point<S>> {
+        let n = self.points.len();
+        if n == 0 {
+            return None;
+        }
+
+        let mut area = S::ZERO;
+        let mut sum = Vector::zero();
+        self.for_each_edge_cross(&mut |a, b, cross| {
+            area += cross;
+            sum += (a + b) * cross;
+        });
+
+        let origin = self.points[0];
+        if area == S::ZERO {
+            let mut sum = Vector::zero();
+            for p in self.points {
+                sum += *p - origin;
+            }
+            return Some(origin + sum / S::value(n as f32));
+        }
+
+        Some(origin + sum / (S::THREE * area))
+    }
+
+    /// Returns true if the polygon is convex.
+    ///
+    /// Aligned consecutive vertices are allowed, but polygons with an area of zero or
+    /// that go around their center more than once are not convex.
+    pub fn is_convex(&self) -> bool {
+        let n = self.points.len();
+        if n < 3 {
+            return false;
+        }
+
+        let mut sign = S::ZERO;
+        let mut total_angle = S::ZERO;
+        let mut prev_edge: Option<Vector<S>> = None;
+        // Visit the first edge twice to compare it with the last one.
+        for i in 0..=n {
+            let edge = self.points[(i + 1) % n] - self.points[i % n];
+            if edge.square_length() == S::ZERO {
+                continue;
+            }
+            if let Some(prev) = prev_edge {
+                let cross = prev.cross(edge);
+                if cross != S::ZERO {
+                    if sign == S::ZERO {
+                        sign = cross;
+                    } else if (cross > S::ZERO) != (sign > S::ZERO) {
+                        return false;
+                    }
+                }
+                total_angle += prev.angle_to(edge).radians;
+            }
+            prev_edge = Some(edge);
+        }
+
+        sign != S::ZERO && S::abs(total_angle) < S::PI() * S::THREE
+    }
+
+    /// Returns the winding number of the polygon around a position.
+    ///
+    /// It counts the number of times the polygon goes around the position in the positive
+    /// orientation, minus the number of times it goes around in the negative orientation.
+    pub fn winding_number(&self, point: Point<S>) -> i16 {
+        let n = self.points.len();
+        let mut winding = 0;
+        for i in 0..n {
+            let a = self.points[i];
+            let b = self.points[(i + 1) % n];
+            let side = (b - a).cross(point - a);
+            if a.y <= point.y {
+                if b.y > point.y && side > S::ZERO {
+                    winding += 1;
+                }
+            } else if b.y <= point.y && side < S::ZERO {
+                winding -= 1;
+            }
+        }
+
+        winding
+    }
+
+    /// Returns true if the position is inside of the polygon, using the non-zero fill rule.
+    ///
+    /// Other fill rules can be applied to the result of `winding_number`.
+    pub fn contains_point(&self, point: Point<S>) -> bool {
+        self.winding_number(point) != 0
+    }
+
+    /// Returns the smallest rectangle that contains the polygon.
+    pub fn bounding_rect(&self) -> Rect<S> {
+        Rect::from_points(self.points.iter())
+    }
+
+    // Invokes the callback with the vertices of each edge relative to the first vertex,
+    // along with the cross product of these vertices.
+    fn for_each_edge_cross<F>(&self, cb: &mut F)
+    where
+        F: FnMut(Vector<S>, Vector<S>, S),
+    {
+        let n = self.points.len();
+        if n < 3 {
+            return;
+        }
+
+        let origin = self.points[0];
+        for i in 1..(n - 1) {
+            let a = self.points[i] - origin;
+            let b = self.points[i + 1] - origin;
+            cb(a, b, a.cross(b));
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::point;
+
+#[test]
+fn polygon_area_and_centroid() {
+    let square = PolygonShape {
+        points: &[
+            point(1.0f64, 1.0),
+            point(3.0, 1.0),
+            point(3.0, 3.0),
+            point(1.0, 3.0),
+        ],
+    };
+    assert_eq!(square.signed_area(), 4.0);
+    assert_eq!(square.orientation(), Some(Winding::Positive));
+    assert_eq!(square.centroid(), Some(point(2.0, 2.0)));
+
+    let reversed: Vec<_> = square.points.iter().rev().cloned().collect();
+    let reversed = PolygonShape { points: &reversed };
+    assert_eq!(reversed.signed_area(), -4.0);
+    assert_eq!(reversed.area(), 4.0);
+    assert_eq!(reversed.orientation(), Some(Winding::Negative));
+    assert_eq!(reversed.centroid(), Some(point(2.0, 2.0)));
+
+    // An L shape made of a 2x1 and a 1x1 rectangles.
+    let l = PolygonShape {
+        points: &[
+            point(0.0f64, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(1.0, 1.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+        ],
+    };
+    assert_eq!(l.area(), 3.0);
+    let centroid = l.centroid().unwrap();
+    assert!((centroid - point(5.0 / 6.0, 5.0 / 6.0)).length() < 1e-12);
+
+    let flat = PolygonShape {
+        points: &[point(0.0f64, 0.0), point(2.0, 0.0), point(1.0, 0.0)],
+    };
+    assert_eq!(flat.area(), 0.0);
+    assert_eq!(flat.orientation(), None);
+    assert_eq!(flat.centroid(), Some(point(1.0, 0.0)));
+    assert_eq!(PolygonShape::<f32> { points: &[] }.centroid(), None);
+}
+
+#[test]
+fn polygon_convexity() {
+    let square = [
+        point(0.0f32, 0.0),
+        point(1.0, 0.0),
+        point(2.0, 0.0),
+        point(2.0, 2.0),
+        point(0.0, 2.0),
+    ];
+    assert!(PolygonShape { points: &square }.is_convex());
+    let reversed: Vec<_> = square.iter().rev().cloned().collect();
+    assert!(PolygonShape { points: &reversed }.is_convex());
+
+    let l = [
+        point(0.0f32, 0.0),
+        point(2.0, 0.0),
+        point(2.0, 1.0),
+        point(1.0, 1.0),
+        point(1.0, 2.0),
+        point(0.0, 2.0),
+    ];
+    assert!(!PolygonShape { points: &l }.is_convex());
+
+    // A pentagram turns in a single direction but goes around twice.
+    let star: Vec<_> = (0..5)
+        .map(|i| {
+            let angle = (i * 2) as f32 * std::f32::consts::PI * 2.0 / 5.0;
+            point(angle.cos(), angle.sin())
+        })
+        .collect();
+    assert!(!PolygonShape { points: &star }.is_convex());
+
+    let flat = [point(0.0f32, 0.0), point(1.0, 0.0), point(2.0, 0.0)];
+    assert!(!PolygonShape { points: &flat }.is_convex());
+}
+
+#[test]
+fn polygon_contains_point() {
+    let l = PolygonShape {
+        points: &[
+            point(0.0f32, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(1.0, 1.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+        ],
+    };
+    assert!(l.contains_point(point(0.5, 0.5)));
+    assert!(l.contains_point(point(1.5, 0.5)));
+    assert!(l.contains_point(point(0.5, 1.5)));
+    assert!(!l.contains_point(point(1.5, 1.5)));
+    assert!(!l.contains_point(point(3.0, 0.5)));
+    assert_eq!(l.winding_number(point(0.5, 0.5)), 1);
+
+    // The center of a pentagram is enclosed twice.
+    let star: Vec<_> = (0..5)
+        .map(|i| {
+            let angle = (i * 2) as f32 * std::f32::consts::PI * 2.0 / 5.0;
+            point(angle.cos(), angle.sin())
+        })
+        .collect();
+    let star = PolygonShape { points: &star };
+    assert_eq!(star.winding_number(point(0.0, 0.0)), 2);
+    assert_eq!(star.winding_number(point(0.7, 0.0)), 1);
+    assert_eq!(star.winding_number(point(2.0, 0.0)), 0);
+}
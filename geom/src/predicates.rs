@@ -0,0 +1,587 @@
+//! Robust geometric predicates.
+//!
+//! The predicates in this module return results with the correct sign for any input, including
+//! nearly degenerate configurations where a naive floating point evaluation would misclassify
+//! points, for example nearly aligned points far from the origin.
+//!
+//! The implementation follows Jonathan Shewchuk's
+//! [Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates](https://people.eecs.berkeley.edu/~jrs/papers/robustr.pdf):
+//! the determinants are first evaluated with regular floating point arithmetic along with an
+//! error bound, and the computation only falls back to exact arithmetic when the error bound
+//! does not let us decide the sign of the result. The common case is therefore almost as fast
+//! as the naive computation.
+//!
+//! All computations are done in double precision. Inputs are converted to `f64` which is exact
+//! for both `f32` and `f64` coordinates.
+//!
+//! # Examples
+//!
+//! ```
+//! use lyon_geom::point;
+//! use lyon_geom::predicates::orient2d;
+//!
+//! let a = point(0.5f64, 0.5);
+//! let b = point(12.0, 12.0);
+//! let c = point(24.0, 24.0);
+//! // The points are exactly aligned.
+//! assert_eq!(orient2d(a, b, c), 0.0);
+//! // Nudging c by the smallest possible amount is detected.
+//! let c = point(24.0, f64::from_bits(24.0f64.to_bits() + 1));
+//! assert!(orient2d(a, b, c) > 0.0);
+//! ```
+
+use crate::scalar::Scalar;
+use crate::{LineSegment, Point, Winding};
+
+/// Returns a positive value if the points `a`, `b` and `c` are in counterclockwise order
+/// (`Winding::Positive`), a negative value if they are in clockwise order and zero if they
+/// are aligned.
+///
+/// The magnitude of the result approximates twice the signed area of the triangle, but
+/// only its sign is guaranteed to be exact.
+pub fn orient2d<S: Scalar>(a: Point<S>, b: Point<S>, c: Point<S>) -> f64 {
+    let (a, b, c) = (to_f64(a), to_f64(b), to_f64(c));
+
+    let det_left = (a[0] - c[0]) * (b[1] - c[1]);
+    let det_right = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = det_left - det_right;
+
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let err_bound = CCW_ERR_BOUND_A * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    orient2d_adapt(a, b, c, det_sum)
+}
+
+/// Returns the orientation of the points `a`, `b` and `c`, or `None` if they are aligned.
+pub fn orientation<S: Scalar>(a: Point<S>, b: Point<S>, c: Point<S>) -> Option<Winding> {
+    let det = orient2d(a, b, c);
+    if det > 0.0 {
+        Some(Winding::Positive)
+    } else if det < 0.0 {
+        Some(Winding::Negative)
+    } else {
+        None
+    }
+}
+
+/// Returns a positive value if `d` is inside the circle passing through `a`, `b` and `c`, a
+/// negative value if it lies outside and zero if the four points are cocircular.
+///
+/// The points `a`, `b` and `c` must be in counterclockwise order (see `orient2d`), otherwise
+/// the sign of the result is reversed.
+pub fn incircle<S: Scalar>(a: Point<S>, b: Point<S>, c: Point<S>, d: Point<S>) -> f64 {
+    let (a, b, c, d) = (to_f64(a), to_f64(b), to_f64(c), to_f64(d));
+
+    let adx = a[0] - d[0];
+    let bdx = b[0] - d[0];
+    let cdx = c[0] - d[0];
+    let ady = a[1] - d[1];
+    let bdy = b[1] - d[1];
+    let cdy = c[1] - d[1];
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    let err_bound = ICC_ERR_BOUND_A * permanent;
+    if det > err_bound || -det > err_bound {
+        return det;
+    }
+
+    incircle_exact(a, b, c, d)
+}
+
+/// The ways two line segments can intersect.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SegmentIntersection {
+    /// The segments don't have any point in common.
+    None,
+    /// The segments cross at a single point that is not an endpoint of either of them.
+    Crossing,
+    /// The segments meet at a single point which is an endpoint of at least one of them.
+    Touching,
+    /// The segments are collinear and share a portion of non-zero length.
+    Overlapping,
+}
+
+/// Classifies the intersection of two line segments using exact orientation tests.
+///
+/// Unlike `LineSegment::intersects`, the result is exact, including for segments with shared
+/// endpoints, nearly parallel segments and zero-length segments.
+pub fn segment_intersection<S: Scalar>(
+    a: &LineSegment<S>,
+    b: &LineSegment<S>,
+) -> SegmentIntersection {
+    let o1 = orient2d(a.from, a.to, b.from);
+    let o2 = orient2d(a.from, a.to, b.to);
+    let o3 = orient2d(b.from, b.to, a.from);
+    let o4 = orient2d(b.from, b.to, a.to);
+
+    if (o1 > 0.0 && o2 > 0.0)
+        || (o1 < 0.0 && o2 < 0.0)
+        || (o3 > 0.0 && o4 > 0.0)
+        || (o3 < 0.0 && o4 < 0.0)
+    {
+        return SegmentIntersection::None;
+    }
+
+    if o1 == 0.0 && o2 == 0.0 && o3 == 0.0 && o4 == 0.0 {
+        return collinear_intersection(a, b);
+    }
+
+    if o1 == 0.0 || o2 == 0.0 || o3 == 0.0 || o4 == 0.0 {
+        return SegmentIntersection::Touching;
+    }
+
+    SegmentIntersection::Crossing
+}
+
+fn collinear_intersection<S: Scalar>(
+    a: &LineSegment<S>,
+    b: &LineSegment<S>,
+) -> SegmentIntersection {
+    // The points are on the same line, so projecting them on the axis along which the
+    // line has the largest extent preserves their order. Comparisons are exact.
+    let dx = S::max(S::abs(a.to.x - a.from.x), S::abs(b.to.x - b.from.x));
+    let dy = S::max(S::abs(a.to.y - a.from.y), S::abs(b.to.y - b.from.y));
+    if dx == S::ZERO && dy == S::ZERO {
+        // Both segments are points.
+        return if a.from == b.from {
+            SegmentIntersection::Touching
+        } else {
+            SegmentIntersection::None
+        };
+    }
+
+    let project = |p: Point<S>| if dx >= dy { p.x } else { p.y };
+
+    let (a0, a1) = (project(a.from), project(a.to));
+    let (b0, b1) = (project(b.from), project(b.to));
+    let start = S::max(S::min(a0, a1), S::min(b0, b1));
+    let end = S::min(S::max(a0, a1), S::max(b0, b1));
+
+    if start > end {
+        SegmentIntersection::None
+    } else if start == end {
+        SegmentIntersection::Touching
+    } else {
+        SegmentIntersection::Overlapping
+    }
+}
+
+fn to_f64<S: Scalar>(p: Point<S>) -> [f64; 2] {
+    [p.x.to_f64().unwrap(), p.y.to_f64().unwrap()]
+}
+
+const EPSILON: f64 = 1.1102230246251565e-16; // 2^-53
+const SPLITTER: f64 = 134_217_729.0; // 2^27 + 1
+
+const RESULT_ERR_BOUND: f64 = (3.0 + 8.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_B: f64 = (2.0 + 12.0 * EPSILON) * EPSILON;
+const CCW_ERR_BOUND_C: f64 = (9.0 + 64.0 * EPSILON) * EPSILON * EPSILON;
+const ICC_ERR_BOUND_A: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+fn orient2d_adapt(a: [f64; 2], b: [f64; 2], c: [f64; 2], det_sum: f64) -> f64 {
+    let acx = a[0] - c[0];
+    let bcx = b[0] - c[0];
+    let acy = a[1] - c[1];
+    let bcy = b[1] - c[1];
+
+    let (det_left, det_left_tail) = two_product(acx, bcy);
+    let (det_right, det_right_tail) = two_product(acy, bcx);
+    let b_exp = two_two_diff(det_left, det_left_tail, det_right, det_right_tail);
+
+    let mut det = estimate(&b_exp);
+    let err_bound = CCW_ERR_BOUND_B * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    let acx_tail = two_diff_tail(a[0], c[0], acx);
+    let bcx_tail = two_diff_tail(b[0], c[0], bcx);
+    let acy_tail = two_diff_tail(a[1], c[1], acy);
+    let bcy_tail = two_diff_tail(b[1], c[1], bcy);
+
+    if acx_tail == 0.0 && acy_tail == 0.0 && bcx_tail == 0.0 && bcy_tail == 0.0 {
+        return det;
+    }
+
+    let err_bound = CCW_ERR_BOUND_C * det_sum + RESULT_ERR_BOUND * det.abs();
+    det += (acx * bcy_tail + bcy * acx_tail) - (acy * bcx_tail + bcx * acy_tail);
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    let cross_diff = |ax: f64, by: f64, ay: f64, bx: f64| {
+        let (s1, s0) = two_product(ax, by);
+        let (t1, t0) = two_product(ay, bx);
+        two_two_diff(s1, s0, t1, t0)
+    };
+
+    let c1 = expansion_sum(&b_exp, &cross_diff(acx_tail, bcy, acy_tail, bcx));
+    let c2 = expansion_sum(&c1, &cross_diff(acx, bcy_tail, acy, bcx_tail));
+    let d = expansion_sum(&c2, &cross_diff(acx_tail, bcy_tail, acy_tail, bcx_tail));
+
+    *d.last().unwrap()
+}
+
+fn incircle_exact(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let cross = |p: [f64; 2], q: [f64; 2]| {
+        let (pxqy1, pxqy0) = two_product(p[0], q[1]);
+        let (qxpy1, qxpy0) = two_product(q[0], p[1]);
+        two_two_diff(pxqy1, pxqy0, qxpy1, qxpy0)
+    };
+
+    let ab = cross(a, b);
+    let bc = cross(b, c);
+    let cd = cross(c, d);
+    let da = cross(d, a);
+    let ac = cross(a, c);
+    let bd = cross(b, d);
+
+    let cda = expansion_sum(&expansion_sum(&cd, &da), &ac);
+    let dab = expansion_sum(&expansion_sum(&da, &ab), &bd);
+    let abc = expansion_sum(&expansion_sum(&ab, &bc), &negate(&ac));
+    let bcd = expansion_sum(&expansion_sum(&bc, &cd), &negate(&bd));
+
+    // Each term is the lifted coordinate of a point multiplied by the orientation of the
+    // three others.
+    let lift = |e: &[f64], p: [f64; 2], sign: f64| {
+        let x = scale_expansion(&scale_expansion(e, p[0]), sign * p[0]);
+        let y = scale_expansion(&scale_expansion(e, p[1]), sign * p[1]);
+        expansion_sum(&x, &y)
+    };
+
+    let a_det = lift(&bcd, a, 1.0);
+    let b_det = lift(&cda, b, -1.0);
+    let c_det = lift(&dab, c, 1.0);
+    let d_det = lift(&abc, d, -1.0);
+
+    let det = expansion_sum(
+        &expansion_sum(&a_det, &b_det),
+        &expansion_sum(&c_det, &d_det),
+    );
+
+    *det.last().unwrap()
+}
+
+// Exact arithmetic primitives.
+//
+// An expansion is a sequence of non-overlapping floating point numbers sorted by increasing
+// magnitude, the exact value of which is their sum. The expansions produced below don't
+// contain zeroes (unless the value is zero) so their last component has the sign of the
+// whole expansion.
+
+#[inline]
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    (x, b - b_virtual)
+}
+
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    let b_round = b - b_virtual;
+    let a_round = a - a_virtual;
+    (x, a_round + b_round)
+}
+
+#[inline]
+fn two_diff_tail(a: f64, b: f64, x: f64) -> f64 {
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    let b_round = b_virtual - b;
+    let a_round = a - a_virtual;
+    a_round + b_round
+}
+
+#[inline]
+fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    let x = a - b;
+    (x, two_diff_tail(a, b, x))
+}
+
+#[inline]
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let a_big = c - a;
+    let a_hi = c - a_big;
+    (a_hi, a - a_hi)
+}
+
+#[inline]
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (a_hi, a_lo) = split(a);
+    let (b_hi, b_lo) = split(b);
+    let err1 = x - a_hi * b_hi;
+    let err2 = err1 - a_lo * b_hi;
+    let err3 = err2 - a_hi * b_lo;
+    (x, a_lo * b_lo - err3)
+}
+
+#[inline]
+fn two_one_diff(a1: f64, a0: f64, b: f64) -> (f64, f64, f64) {
+    let (i, x0) = two_diff(a0, b);
+    let (x2, x1) = two_sum(a1, i);
+    (x2, x1, x0)
+}
+
+/// Computes `(a1 + a0) - (b1 + b0)` as an expansion of four components.
+#[inline]
+fn two_two_diff(a1: f64, a0: f64, b1: f64, b0: f64) -> [f64; 4] {
+    let (j, k, x0) = two_one_diff(a1, a0, b0);
+    let (x3, x2, x1) = two_one_diff(j, k, b1);
+    [x0, x1, x2, x3]
+}
+
+fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
+
+fn negate(e: &[f64]) -> Vec<f64> {
+    e.iter().map(|x| -x).collect()
+}
+
+/// Sums two expansions, eliminating zero components.
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() + f.len());
+    let mut e_idx = 0;
+    let mut f_idx = 0;
+
+    // Merge the components by increasing magnitude.
+    let next = |e_idx: &mut usize, f_idx: &mut usize| {
+        let take_e = match (e.get(*e_idx), f.get(*f_idx)) {
+            (Some(e_now), Some(f_now)) => (*f_now > *e_now) == (*f_now > -*e_now),
+            (Some(_), None) => true,
+            _ => false,
+        };
+        if take_e {
+            *e_idx += 1;
+            e[*e_idx - 1]
+        } else {
+            *f_idx += 1;
+            f[*f_idx - 1]
+        }
+    };
+
+    let mut q = next(&mut e_idx, &mut f_idx);
+    while e_idx < e.len() || f_idx < f.len() {
+        let (sum, err) = two_sum(q, next(&mut e_idx, &mut f_idx));
+        if err != 0.0 {
+            h.push(err);
+        }
+        q = sum;
+    }
+
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+
+    h
+}
+
+/// Multiplies an expansion by a scalar, eliminating zero components.
+fn scale_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() * 2);
+
+    let (mut q, err) = two_product(e[0], b);
+    if err != 0.0 {
+        h.push(err);
+    }
+
+    for &e_now in &e[1..] {
+        let (product1, product0) = two_product(e_now, b);
+        let (sum, err) = two_sum(q, product0);
+        if err != 0.0 {
+            h.push(err);
+        }
+        let (new_q, err) = fast_two_sum(product1, sum);
+        if err != 0.0 {
+            h.push(err);
+        }
+        q = new_q;
+    }
+
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+
+    h
+}
+
+#[test]
+fn orient2d_near_degenerate() {
+    use crate::point;
+
+    let a = point(0.5f64, 0.5);
+    let b = point(12.0, 12.0);
+    let c = point(24.0, 24.0);
+    assert_eq!(orient2d(a, b, c), 0.0);
+    assert_eq!(orientation(a, b, c), None);
+
+    // Points on a grid of nearly aligned points, where the naive evaluation is wrong.
+    let ulp = |x: f64, n: i64| f64::from_bits((x.to_bits() as i64 + n) as u64);
+    for i in -8..8 {
+        for j in -8..8 {
+            let p = point(ulp(0.5, i), ulp(0.5, j));
+            let expected = if ulp(0.5, j) > ulp(0.5, i) {
+                Some(Winding::Positive)
+            } else if ulp(0.5, j) < ulp(0.5, i) {
+                Some(Winding::Negative)
+            } else {
+                None
+            };
+            assert_eq!(orientation(b, c, p), expected, "{:?}", p);
+            assert_eq!(orientation(p, b, c), expected, "{:?}", p);
+        }
+    }
+
+    // f32 inputs are exactly converted.
+    let a = point(1.0e7f32, 1.0e7);
+    let b = point(1.0e7f32 + 1.0, 1.0e7 + 1.0);
+    let c = point(1.0e7f32 + 2.0, 1.0e7 + 2.0);
+    assert_eq!(orientation(a, b, c), None);
+    assert_eq!(
+        orientation(a, b, point(1.0e7f32 + 2.0, 1.0e7 + 3.0)),
+        Some(Winding::Positive)
+    );
+    assert_eq!(
+        orientation(a, b, point(1.0e7f32 + 3.0, 1.0e7 + 2.0)),
+        Some(Winding::Negative)
+    );
+}
+
+#[test]
+fn incircle_near_degenerate() {
+    use crate::point;
+
+    let a = point(0.0f64, -1.0);
+    let b = point(1.0, 0.0);
+    let c = point(0.0, 1.0);
+    assert!(incircle(a, b, c, point(0.0, 0.0)) > 0.0);
+    assert!(incircle(a, b, c, point(2.0, 0.0)) < 0.0);
+    assert_eq!(incircle(a, b, c, point(-1.0, 0.0)), 0.0);
+
+    // Far from the origin, cocircular points and their smallest perturbations.
+    let offset = 1.0e9;
+    let a = point(offset, offset - 3.0);
+    let b = point(offset + 3.0, offset);
+    let c = point(offset, offset + 3.0);
+    let d = point(offset - 3.0, offset);
+    assert_eq!(incircle(a, b, c, d), 0.0);
+    let inside = point(offset - 3.0 + 1.2e-7, offset);
+    let outside = point(offset - 3.0 - 1.2e-7, offset);
+    assert!(incircle(a, b, c, inside) > 0.0);
+    assert!(incircle(a, b, c, outside) < 0.0);
+    // Reversing the orientation of the circle reverses the sign.
+    assert!(incircle(c, b, a, inside) < 0.0);
+}
+
+#[test]
+fn segment_intersections() {
+    use crate::point;
+
+    let seg = |x0: f64, y0: f64, x1: f64, y1: f64| LineSegment {
+        from: point(x0, y0),
+        to: point(x1, y1),
+    };
+
+    let a = seg(0.0, 0.0, 10.0, 10.0);
+    assert_eq!(
+        segment_intersection(&a, &seg(0.0, 10.0, 10.0, 0.0)),
+        SegmentIntersection::Crossing
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(0.0, 1.0, 9.0, 10.0)),
+        SegmentIntersection::None
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(5.0, 5.0, 10.0, 0.0)),
+        SegmentIntersection::Touching
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(10.0, 10.0, 20.0, 0.0)),
+        SegmentIntersection::Touching
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(10.0, 10.0, 20.0, 20.0)),
+        SegmentIntersection::Touching
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(5.0, 5.0, 20.0, 20.0)),
+        SegmentIntersection::Overlapping
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(11.0, 11.0, 20.0, 20.0)),
+        SegmentIntersection::None
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(3.0, 3.0, 3.0, 3.0)),
+        SegmentIntersection::Touching
+    );
+    assert_eq!(
+        segment_intersection(&a, &seg(3.0, 4.0, 3.0, 4.0)),
+        SegmentIntersection::None
+    );
+
+    let v = seg(0.0, 0.0, 0.0, 10.0);
+    assert_eq!(
+        segment_intersection(&v, &seg(0.0, 5.0, 0.0, 20.0)),
+        SegmentIntersection::Overlapping
+    );
+    assert_eq!(
+        segment_intersection(&v, &seg(0.0, 10.0, 0.0, 20.0)),
+        SegmentIntersection::Touching
+    );
+
+    // Two points.
+    let p = seg(0.0, 0.0, 0.0, 0.0);
+    assert_eq!(
+        segment_intersection(&p, &seg(0.0, 5.0, 0.0, 5.0)),
+        SegmentIntersection::None
+    );
+    assert_eq!(
+        segment_intersection(&p, &seg(0.0, 0.0, 0.0, 0.0)),
+        SegmentIntersection::Touching
+    );
+
+    // A segment ending a tiny bit before another one.
+    let b = seg(0.0, 10.0, 5.0, f64::from_bits(5.0f64.to_bits() + 1));
+    assert_eq!(segment_intersection(&a, &b), SegmentIntersection::None);
+    let b = seg(0.0, 10.0, 5.0, 5.0);
+    assert_eq!(segment_intersection(&a, &b), SegmentIntersection::Touching);
+}
@@ -1,9 +1,12 @@
 use crate::{rect, Point, Rect, Vector};
+use crate::arc_length;
 use crate::monotonic::Monotonic;
+use crate::offset::offset_quadratic;
 use crate::scalar::Scalar;
 use crate::segment::{BoundingRect, Segment};
 use crate::traits::Transformation;
-use crate::{CubicBezierSegment, Line, LineEquation, LineSegment, Triangle};
+use crate::utils::{curvature, polynomial_roots_in_range};
+use crate::{Arc, CubicBezierSegment, Line, LineEquation, LineSegment, Triangle};
 use arrayvec::ArrayVec;
 
 use std::mem;
@@ -75,6 +78,40 @@ impl<S: Scalar> QuadraticBezierSegment<S> {
         self.from.y * c0 + self.ctrl.y * c1 + self.to.y * c2
     }
 
+    /// Sample the curve's second derivative, which is constant for quadratic bézier curves.
+    pub fn second_derivative(&self, _t: S) -> Vector<S> {
+        ((self.to - self.ctrl) - (self.ctrl - self.from)) * S::TWO
+    }
+
+    /// Sample the signed curvature at t (expecting t between 0 and 1).
+    ///
+    /// The curvature is positive where the curve turns in the direction of the positive
+    /// angles and zero where the derivative vanishes.
+    pub fn curvature(&self, t: S) -> S {
+        curvature(self.derivative(t), self.second_derivative(t))
+    }
+
+    /// Calls the callback with the `t` parameter of the curvature extremum, if any.
+    ///
+    /// The curvature of a quadratic bézier curve is maximal where its derivative is
+    /// the shortest, which happens at most once strictly between the endpoints.
+    pub fn for_each_curvature_extremum_t<F>(&self, cb: &mut F)
+    where
+        F: FnMut(S),
+    {
+        let a = (self.ctrl - self.from) * S::TWO;
+        let b = self.second_derivative(S::ZERO);
+        let div = b.square_length();
+        if div == S::ZERO {
+            return;
+        }
+
+        let t = -a.dot(b) / div;
+        if t > S::ZERO && t < S::ONE {
+            cb(t);
+        }
+    }
+
     /// Swap the beginning and the end of the segment.
     pub fn flip(&self) -> Self {
         QuadraticBezierSegment {
@@ -246,6 +283,17 @@ impl<S: Scalar> QuadraticBezierSegment<S> {
         line.distance_to_point(&self.ctrl) < tolerance
     }
 
+    /// Returns the line segment between the endpoints if the curve is within `epsilon` of it.
+    pub fn as_line(&self, epsilon: S) -> Option<LineSegment<S>> {
+        // The curve is inside the triangle formed by its control points.
+        let line = self.baseline();
+        if line.distance_to_point(self.ctrl) > epsilon {
+            return None;
+        }
+
+        Some(line)
+    }
+
     /// Computes a "fat line" of this segment.
     ///
     /// A fat line is two conservative lines between which the segment
@@ -428,6 +476,75 @@ impl<S: Scalar> QuadraticBezierSegment<S> {
         len
     }
 
+    /// Computes the length of the curve between its start and the point at `t`.
+    ///
+    /// Unlike `approximate_length`, this numerically integrates the length of the curve
+    /// instead of flattening it.
+    pub fn length_at_t(&self, t: S) -> S {
+        let t = S::min(S::max(t, S::ZERO), S::ONE);
+        arc_length::integrate(&|t| self.derivative(t).length(), S::ZERO..t)
+    }
+
+    /// Returns the `t` parameter of the point at a given length along the curve.
+    ///
+    /// The length of the curve at the returned parameter is within `tolerance` of the
+    /// requested length. Lengths outside of the curve are clamped to its endpoints.
+    ///
+    /// For repeated queries on the same curve, see `ArcLengthTable`.
+    pub fn t_at_length(&self, length: S, tolerance: S) -> S {
+        arc_length::t_at_length(
+            &|t| self.length_at_t(t),
+            &|t| self.derivative(t).length(),
+            length,
+            tolerance,
+        )
+    }
+
+    /// Approximates the offset of the curve with a sequence of quadratic bézier curves,
+    /// invoking a callback at each step.
+    ///
+    /// Positive distances offset the curve along the normal `(-tangent.y, tangent.x)`,
+    /// negative distances along the opposite direction. The curves are within `tolerance`
+    /// of the exact offset, except where the offset distance exceeds the radius of curvature
+    /// of the curve, where the exact offset has a cusp.
+    pub fn offset<F>(&self, distance: S, tolerance: S, callback: &mut F)
+    where
+        F: FnMut(&QuadraticBezierSegment<S>),
+    {
+        offset_quadratic(self, distance, tolerance, callback);
+    }
+
+    /// Find the `t` parameter of the closest point on the curve to a given position.
+    pub fn closest_point_t(&self, p: Point<S>) -> S {
+        // With the curve expressed as a + b * t + c * t² relative to p, the closest point
+        // is either an end point or at a root of the cubic polynomial
+        // (a + b * t + c * t²) · (b + 2 * c * t).
+        let a = self.from - p;
+        let b = (self.ctrl - self.from) * S::TWO;
+        let c = self.from.to_vector() + self.to.to_vector() - self.ctrl.to_vector() * S::TWO;
+
+        let coeffs = [
+            a.dot(b),
+            b.dot(b) + S::TWO * a.dot(c),
+            S::THREE * b.dot(c),
+            S::TWO * c.dot(c),
+        ];
+
+        closest_point_t_among_roots(&|t| self.sample(t), p, &coeffs)
+    }
+
+    /// Find the closest point on the curve to a given position.
+    #[inline]
+    pub fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.sample(self.closest_point_t(p))
+    }
+
+    /// Compute the distance between the curve and a given position.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point<S>) -> S {
+        (self.closest_point(p) - p).length()
+    }
+
     /// Returns a triangle containing this curve segment.
     pub fn bounding_triangle(&self) -> Triangle<S> {
         Triangle {
@@ -555,6 +672,68 @@ impl<S: Scalar> QuadraticBezierSegment<S> {
         result
     }
 
+    /// Computes the intersections (if any) between this segment and another quadratic bézier
+    /// segment.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the curves. To
+    /// get the intersection points, sample the curves at the corresponding values.
+    ///
+    /// Returns endpoint intersections where an endpoint intersects the interior of the other curve,
+    /// but not endpoint/endpoint intersections.
+    ///
+    /// Returns no intersections if either curve is a point.
+    pub fn quadratic_intersections_t(
+        &self,
+        curve: &QuadraticBezierSegment<S>,
+    ) -> ArrayVec<[(S, S); 9]> {
+        self.to_cubic().cubic_intersections_t(&curve.to_cubic())
+    }
+
+    /// Computes the intersection points (if any) between this segment and another quadratic
+    /// bézier segment.
+    pub fn quadratic_intersections(
+        &self,
+        curve: &QuadraticBezierSegment<S>,
+    ) -> ArrayVec<[Point<S>; 9]> {
+        self.to_cubic().cubic_intersections(&curve.to_cubic())
+    }
+
+    /// Computes the intersections (if any) between this segment and a cubic bézier segment.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the curves. To
+    /// get the intersection points, sample the curves at the corresponding values.
+    ///
+    /// Returns endpoint intersections where an endpoint intersects the interior of the other curve,
+    /// but not endpoint/endpoint intersections.
+    ///
+    /// Returns no intersections if either curve is a point.
+    pub fn cubic_intersections_t(&self, curve: &CubicBezierSegment<S>) -> ArrayVec<[(S, S); 9]> {
+        self.to_cubic().cubic_intersections_t(curve)
+    }
+
+    /// Computes the intersection points (if any) between this segment and a cubic bézier segment.
+    pub fn cubic_intersections(&self, curve: &CubicBezierSegment<S>) -> ArrayVec<[Point<S>; 9]> {
+        self.to_cubic().cubic_intersections(curve)
+    }
+
+    /// Computes the intersections (if any) between this segment and an arc.
+    ///
+    /// The result is provided in the form of the `t` parameters of each point along the curve
+    /// and the arc. To get the intersection points, sample the curves at the corresponding values.
+    pub fn arc_intersections_t(&self, arc: &Arc<S>) -> ArrayVec<[(S, S); 4]> {
+        let mut result = ArrayVec::new();
+        for (t_arc, t) in arc.quadratic_intersections_t(self) {
+            result.push((t, t_arc));
+        }
+
+        result
+    }
+
+    /// Computes the intersection points (if any) between this segment and an arc.
+    pub fn arc_intersections(&self, arc: &Arc<S>) -> ArrayVec<[Point<S>; 4]> {
+        arc.quadratic_intersections(self)
+    }
+
     #[inline]
     pub fn from(&self) -> Point<S> {
         self.from
@@ -579,6 +758,32 @@ impl<S: Scalar> QuadraticBezierSegment<S> {
     }
 }
 
+/// Picks the parameter of the closest point to `p` among the curve's end points and
+/// the roots within [0, 1] of the polynomial that cancels the derivative of the
+/// squared distance.
+pub(crate) fn closest_point_t_among_roots<S: Scalar, F>(sample: &F, p: Point<S>, coeffs: &[S]) -> S
+where
+    F: Fn(S) -> Point<S>,
+{
+    let mut best_t = S::ZERO;
+    let mut best_d = (sample(S::ZERO) - p).square_length();
+    let d = (sample(S::ONE) - p).square_length();
+    if d < best_d {
+        best_t = S::ONE;
+        best_d = d;
+    }
+
+    for t in polynomial_roots_in_range(coeffs, S::ZERO..S::ONE) {
+        let d = (sample(t) - p).square_length();
+        if d < best_d {
+            best_t = t;
+            best_d = d;
+        }
+    }
+
+    best_t
+}
+
 pub struct FlatteningParameters<S> {
     count: S,
     integral_from: S,
@@ -1118,3 +1323,120 @@ fn test_flattening_straight_line() {
     curve.for_each_flattened(0.1, &mut |_| { count += 1 });
     assert_eq!(count, 1);
 }
+
+#[test]
+fn closest_point() {
+    use crate::point;
+
+    let curve = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(10.0, 20.0),
+        to: point(20.0, 0.0),
+    };
+
+    for &p in &[
+        point(10.0, 0.0),
+        point(10.0, 15.0),
+        point(-5.0, 3.0),
+        point(25.0, -1.0),
+        point(3.0, 7.0),
+        point(14.0, 2.0),
+    ] {
+        let t = curve.closest_point_t(p);
+        let d = curve.distance_to_point(p);
+        assert!((curve.closest_point(p) - p).length() == d);
+
+        // Compare against a brute-force search.
+        for i in 0..=1000 {
+            let t2 = i as f64 / 1000.0;
+            assert!(d <= (curve.sample(t2) - p).length() + 1e-9, "{:?} {:?} {:?}", p, t, t2);
+        }
+    }
+
+    assert!((curve.closest_point_t(point(10.0, 5.0)) - 0.5).abs() < 1e-9);
+    assert_eq!(curve.closest_point_t(point(-1.0, -1.0)), 0.0);
+    assert_eq!(curve.closest_point_t(point(21.0, -1.0)), 1.0);
+}
+
+#[test]
+fn quadratic_intersections() {
+    use crate::point;
+
+    let c1 = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(10.0, 20.0),
+        to: point(20.0, 0.0),
+    };
+    let c2 = QuadraticBezierSegment {
+        from: point(0.0, 10.0),
+        ctrl: point(10.0, -10.0),
+        to: point(20.0, 10.0),
+    };
+
+    let intersections = c1.quadratic_intersections_t(&c2);
+    assert_eq!(intersections.len(), 2);
+    for &(t1, t2) in &intersections {
+        assert!((c1.sample(t1) - c2.sample(t2)).length() < 1e-4);
+    }
+
+    // The curves are symmetrical about x = 10.
+    let points = c1.quadratic_intersections(&c2);
+    assert_eq!(points.len(), 2);
+    assert!((points[0].x + points[1].x - 20.0).abs() < 1e-4);
+    assert!((points[0].y - 5.0).abs() < 1e-4);
+}
+
+#[test]
+fn length_at_t() {
+    use crate::point;
+
+    let curve = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(100.0, 100.0),
+        to: point(200.0, 0.0),
+    };
+
+    let length = curve.approximate_length(0.00001);
+    assert!((curve.length_at_t(1.0) - length).abs() < 0.001);
+    assert!((curve.before_split(0.3).approximate_length(0.00001) - curve.length_at_t(0.3)).abs() < 0.001);
+
+    for i in 0..=10 {
+        let expected = i as f64 / 10.0;
+        let t = curve.t_at_length(curve.length_at_t(expected), 0.0001);
+        assert!((t - expected).abs() < 0.0001);
+    }
+
+    assert_eq!(curve.t_at_length(-1.0, 0.01), 0.0);
+    assert_eq!(curve.t_at_length(length * 2.0, 0.01), 1.0);
+}
+
+#[test]
+fn curvature_queries() {
+    use crate::point;
+
+    let curve = QuadraticBezierSegment {
+        from: point(0.0f64, 0.0),
+        ctrl: point(100.0, 100.0),
+        to: point(200.0, 0.0),
+    };
+
+    let h = 1e-6;
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let dd = (curve.derivative(t + h) - curve.derivative(t - h)) / (2.0 * h);
+        assert!((curve.second_derivative(t) - dd).length() < 1e-4);
+        // The curve turns clockwise.
+        assert!(curve.curvature(t) < 0.0);
+    }
+
+    let mut extrema = Vec::new();
+    curve.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert_eq!(extrema, vec![0.5]);
+    // The apex of the parabola y = x * (200 - x) / 200 has a radius of curvature of 100.
+    assert!((curve.radius_of_curvature(0.5) + 100.0).abs() < 1e-9);
+
+    // The extremum of this curve is before its start.
+    let mut extrema = Vec::new();
+    curve.split(0.6).1.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert!(extrema.is_empty());
+}
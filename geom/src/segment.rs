@@ -1,5 +1,6 @@
 use crate::{Point, Rect, Vector};
-use crate::scalar::Scalar;
+use crate::scalar::{Float, Scalar};
+use crate::utils::curvature;
 use crate::{CubicBezierSegment, LineSegment, QuadraticBezierSegment};
 
 use std::ops::Range;
@@ -40,6 +41,51 @@ pub trait Segment: Copy + Sized {
         self.derivative(t).y
     }
 
+    /// Sample the second derivative at t (expecting t between 0 and 1).
+    ///
+    /// The default implementation uses finite differences of `derivative`.
+    fn second_derivative(&self, t: Self::Scalar) -> Vector<Self::Scalar> {
+        let h = Self::Scalar::value(1.0 / 1024.0);
+        let d = |t| self.derivative(t);
+        let two = Self::Scalar::TWO;
+        let (three, four) = (Self::Scalar::value(3.0), Self::Scalar::value(4.0));
+        // Second order differences, one-sided near the ends.
+        if t - h < Self::Scalar::ZERO {
+            (d(t + h) * four - d(t) * three - d(t + h * two)) / (h * two)
+        } else if t + h > Self::Scalar::ONE {
+            (d(t) * three - d(t - h) * four + d(t - h * two)) / (h * two)
+        } else {
+            (d(t + h) - d(t - h)) / (h * two)
+        }
+    }
+
+    /// Sample the signed curvature at t (expecting t between 0 and 1).
+    ///
+    /// The curvature is positive where the curve turns in the direction of the positive
+    /// angles and zero where the derivative vanishes.
+    fn curvature(&self, t: Self::Scalar) -> Self::Scalar {
+        curvature(self.derivative(t), self.second_derivative(t))
+    }
+
+    /// Sample the signed radius of curvature at t (expecting t between 0 and 1).
+    ///
+    /// This is the radius of the osculating circle, infinite where the curvature is zero.
+    fn radius_of_curvature(&self, t: Self::Scalar) -> Self::Scalar {
+        Self::Scalar::ONE / self.curvature(t)
+    }
+
+    /// Calls the callback with the `t` parameters of the local extrema of the signed curvature
+    /// in increasing order, which are where the curve bends the most sharply.
+    ///
+    /// The default implementation looks for the extrema between evenly spaced samples of the
+    /// curvature, so it can miss extrema that are very close to each other.
+    fn for_each_curvature_extremum_t<F>(&self, cb: &mut F)
+    where
+        F: FnMut(Self::Scalar),
+    {
+        sampled_curvature_extrema(self, cb)
+    }
+
     /// Split this curve into two sub-curves.
     fn split(&self, t: Self::Scalar) -> (Self, Self);
 
@@ -59,6 +105,115 @@ pub trait Segment: Copy + Sized {
 
     /// Compute the length of the segment using a flattened approximation.
     fn approximate_length(&self, tolerance: Self::Scalar) -> Self::Scalar;
+
+    /// Find the `t` parameter of the closest point on the curve to a given position.
+    ///
+    /// The default implementation refines the closest of a few evenly spaced samples using
+    /// `sample` and `derivative`. It can miss the closest point of curves that come back near the
+    /// position between two samples.
+    fn closest_point_t(&self, p: Point<Self::Scalar>) -> Self::Scalar {
+        sampled_closest_point_t(self, p)
+    }
+
+    /// Find the closest point on the curve to a given position.
+    fn closest_point(&self, p: Point<Self::Scalar>) -> Point<Self::Scalar> {
+        self.sample(self.closest_point_t(p))
+    }
+
+    /// Compute the distance between the curve and a given position.
+    fn distance_to_point(&self, p: Point<Self::Scalar>) -> Self::Scalar {
+        (self.closest_point(p) - p).length()
+    }
+}
+
+fn sampled_curvature_extrema<Seg: Segment, F>(segment: &Seg, cb: &mut F)
+where
+    F: FnMut(Seg::Scalar),
+{
+    const NUM_SAMPLES: u32 = 32;
+    let step = Seg::Scalar::ONE / Seg::Scalar::value(NUM_SAMPLES as f32);
+    let t_at = |i: u32| Seg::Scalar::value(i as f32) * step;
+    let curvatures: Vec<Seg::Scalar> = (0..=NUM_SAMPLES)
+        .map(|i| segment.curvature(t_at(i)))
+        .collect();
+
+    for i in 1..NUM_SAMPLES as usize {
+        let before = curvatures[i] - curvatures[i - 1];
+        let after = curvatures[i + 1] - curvatures[i];
+        let sign = if before > Seg::Scalar::ZERO && after <= Seg::Scalar::ZERO {
+            Seg::Scalar::ONE
+        } else if before < Seg::Scalar::ZERO && after >= Seg::Scalar::ZERO {
+            -Seg::Scalar::ONE
+        } else {
+            continue;
+        };
+
+        // Ternary search of the extremum between the neighboring samples.
+        let third = Seg::Scalar::value(1.0 / 3.0);
+        let mut lo = t_at(i as u32 - 1);
+        let mut hi = t_at(i as u32 + 1);
+        for _ in 0..64 {
+            let a = lo + (hi - lo) * third;
+            let b = hi - (hi - lo) * third;
+            if a <= lo || b >= hi {
+                break;
+            }
+            if segment.curvature(a) * sign < segment.curvature(b) * sign {
+                lo = a;
+            } else {
+                hi = b;
+            }
+        }
+
+        cb((lo + hi) * Seg::Scalar::HALF);
+    }
+}
+
+fn sampled_closest_point_t<Seg: Segment>(segment: &Seg, p: Point<Seg::Scalar>) -> Seg::Scalar {
+    const NUM_SAMPLES: u32 = 16;
+    let step = Seg::Scalar::ONE / Seg::Scalar::value(NUM_SAMPLES as f32);
+    let square_distance = |t| (segment.sample(t) - p).square_length();
+
+    let mut t = Seg::Scalar::ZERO;
+    let mut best = square_distance(t);
+    for i in 1..=NUM_SAMPLES {
+        let ti = Seg::Scalar::value(i as f32) * step;
+        let d = square_distance(ti);
+        if d < best {
+            t = ti;
+            best = d;
+        }
+    }
+
+    // The squared distance decreases towards the closest point, so look for where its
+    // derivative changes sign between the neighboring samples.
+    let slope = |t| (segment.sample(t) - p).dot(segment.derivative(t));
+    let (mut lo, mut hi) = if slope(t) < Seg::Scalar::ZERO {
+        (t, Float::min(t + step, Seg::Scalar::ONE))
+    } else {
+        (Float::max(t - step, Seg::Scalar::ZERO), t)
+    };
+    for _ in 0..64 {
+        let mid = (lo + hi) * Seg::Scalar::HALF;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if slope(mid) < Seg::Scalar::ZERO {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    for &candidate in &[lo, hi] {
+        let d = square_distance(candidate);
+        if d < best {
+            t = candidate;
+            best = d;
+        }
+    }
+
+    t
 }
 
 pub trait BoundingRect {
@@ -98,6 +253,11 @@ macro_rules! impl_segment {
         fn derivative(&self, t: $S) -> Vector<$S> { self.derivative(t) }
         fn dx(&self, t: $S) -> $S { self.dx(t) }
         fn dy(&self, t: $S) -> $S { self.dy(t) }
+        fn second_derivative(&self, t: $S) -> Vector<$S> { self.second_derivative(t) }
+        fn curvature(&self, t: $S) -> $S { self.curvature(t) }
+        fn for_each_curvature_extremum_t<F: FnMut($S)>(&self, cb: &mut F) {
+            self.for_each_curvature_extremum_t(cb)
+        }
         fn split(&self, t: $S) -> (Self, Self) { self.split(t) }
         fn before_split(&self, t: $S) -> Self { self.before_split(t) }
         fn after_split(&self, t: $S) -> Self { self.after_split(t) }
@@ -106,6 +266,9 @@ macro_rules! impl_segment {
         fn approximate_length(&self, tolerance: $S) -> $S {
             self.approximate_length(tolerance)
         }
+        fn closest_point_t(&self, p: Point<$S>) -> $S { self.closest_point_t(p) }
+        fn closest_point(&self, p: Point<$S>) -> Point<$S> { self.closest_point(p) }
+        fn distance_to_point(&self, p: Point<$S>) -> $S { self.distance_to_point(p) }
     )
 }
 
@@ -127,6 +290,16 @@ impl<S: Scalar> BezierSegment<S> {
         }
     }
 
+    /// Sample the derivative at t (expecting t between 0 and 1).
+    #[inline]
+    pub fn derivative(&self, t: S) -> Vector<S> {
+        match self {
+            BezierSegment::Linear(segment) => segment.to_vector(),
+            BezierSegment::Quadratic(segment) => segment.derivative(t),
+            BezierSegment::Cubic(segment) => segment.derivative(t),
+        }
+    }
+
     #[inline]
     pub fn from(&self) -> Point<S> {
         match self {
@@ -163,6 +336,27 @@ impl<S: Scalar> BezierSegment<S> {
         }
     }
 
+    /// Find the `t` parameter of the closest point on the segment to a given position.
+    pub fn closest_point_t(&self, p: Point<S>) -> S {
+        match self {
+            BezierSegment::Linear(segment) => segment.closest_point_t(p),
+            BezierSegment::Quadratic(segment) => segment.closest_point_t(p),
+            BezierSegment::Cubic(segment) => segment.closest_point_t(p),
+        }
+    }
+
+    /// Find the closest point on the segment to a given position.
+    #[inline]
+    pub fn closest_point(&self, p: Point<S>) -> Point<S> {
+        self.sample(self.closest_point_t(p))
+    }
+
+    /// Compute the distance between the segment and a given position.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point<S>) -> S {
+        (self.closest_point(p) - p).length()
+    }
+
     /// Split this segment into two sub-segments.
     pub fn split(&self, t: S) -> (BezierSegment<S>, BezierSegment<S>) {
         match self {
@@ -180,6 +374,17 @@ impl<S: Scalar> BezierSegment<S> {
             }
         }
     }
+
+    /// Return the sub-segment inside a given range of t.
+    pub fn split_range(&self, t_range: Range<S>) -> BezierSegment<S> {
+        match self {
+            BezierSegment::Linear(segment) => BezierSegment::Linear(segment.split_range(t_range)),
+            BezierSegment::Quadratic(segment) => {
+                BezierSegment::Quadratic(segment.split_range(t_range))
+            }
+            BezierSegment::Cubic(segment) => BezierSegment::Cubic(segment.split_range(t_range)),
+        }
+    }
 }
 
 impl<S> From<LineSegment<S>> for BezierSegment<S> {
@@ -199,3 +404,58 @@ impl<S> From<CubicBezierSegment<S>> for BezierSegment<S> {
         BezierSegment::Cubic(s)
     }
 }
+
+#[test]
+fn default_implementations() {
+    use crate::point;
+
+    // A segment that only implements the required methods.
+    #[derive(Copy, Clone)]
+    struct Curve(CubicBezierSegment<f64>);
+
+    impl Segment for Curve {
+        type Scalar = f64;
+        fn from(&self) -> Point<f64> { self.0.from }
+        fn to(&self) -> Point<f64> { self.0.to }
+        fn sample(&self, t: f64) -> Point<f64> { self.0.sample(t) }
+        fn derivative(&self, t: f64) -> Vector<f64> { self.0.derivative(t) }
+        fn split(&self, t: f64) -> (Self, Self) {
+            let (a, b) = self.0.split(t);
+            (Curve(a), Curve(b))
+        }
+        fn before_split(&self, t: f64) -> Self { Curve(self.0.before_split(t)) }
+        fn after_split(&self, t: f64) -> Self { Curve(self.0.after_split(t)) }
+        fn split_range(&self, t_range: Range<f64>) -> Self { Curve(self.0.split_range(t_range)) }
+        fn flip(&self) -> Self { Curve(self.0.flip()) }
+        fn approximate_length(&self, tolerance: f64) -> f64 {
+            self.0.approximate_length(tolerance)
+        }
+    }
+
+    let cubic = CubicBezierSegment {
+        from: point(0.0, 0.0),
+        ctrl1: point(50.0, 100.0),
+        ctrl2: point(100.0, -50.0),
+        to: point(150.0, 50.0),
+    };
+    let curve = Curve(cubic);
+    for p in &[point(40.0, 60.0), point(75.0, 0.0), point(200.0, 80.0), point(-10.0, -5.0)] {
+        let expected = cubic.closest_point(*p);
+        assert!((curve.closest_point(*p) - expected).length() < 1e-9);
+        assert!((curve.distance_to_point(*p) - cubic.distance_to_point(*p)).abs() < 1e-9);
+    }
+
+    for &t in &[0.0, 0.3, 0.5, 1.0] {
+        assert!((curve.second_derivative(t) - cubic.second_derivative(t)).length() < 1e-6);
+    }
+
+    let mut expected = Vec::new();
+    cubic.for_each_curvature_extremum_t(&mut |t| expected.push(t));
+    let mut extrema = Vec::new();
+    curve.for_each_curvature_extremum_t(&mut |t| extrema.push(t));
+    assert!(!expected.is_empty());
+    assert_eq!(extrema.len(), expected.len());
+    for (t, expected) in extrema.iter().zip(expected.iter()) {
+        assert!((t - expected).abs() < 1e-4, "{} {}", t, expected);
+    }
+}
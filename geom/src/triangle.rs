@@ -1,7 +1,7 @@
-use crate::{Point, Rect, Size};
+use crate::{Point, Rect, Size, Vector};
 use crate::scalar::Scalar;
 use crate::traits::Transformation;
-use crate::LineSegment;
+use crate::{LineSegment, Winding};
 
 /// A 2D triangle defined by three points `a`, `b` and `c`.
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -13,25 +13,114 @@ pub struct Triangle<S> {
 }
 
 impl<S: Scalar> Triangle<S> {
+    /// Returns the barycentric coordinates `(u, v, w)` of a point, such that
+    /// `point = a * u + b * v + c * w` and `u + v + w = 1`.
+    ///
+    /// The coordinates are not finite if the triangle is degenerate.
     #[inline]
-    fn get_barycentric_coords_for_point(&self, point: Point<S>) -> (S, S, S) {
+    pub fn barycentric_coordinates(&self, point: Point<S>) -> (S, S, S) {
         let v0 = self.b - self.a;
         let v1 = self.c - self.a;
         let v2 = point - self.a;
         let inv = S::ONE / v0.cross(v1);
-        let a = v0.cross(v2) * inv;
-        let b = v2.cross(v1) * inv;
-        let c = S::ONE - a - b;
+        let w = v0.cross(v2) * inv;
+        let v = v2.cross(v1) * inv;
+        let u = S::ONE - v - w;
 
-        (a, b, c)
+        (u, v, w)
     }
 
     pub fn contains_point(&self, point: Point<S>) -> bool {
-        let coords = self.get_barycentric_coords_for_point(point);
+        let coords = self.barycentric_coordinates(point);
 
         coords.0 > S::ZERO && coords.1 > S::ZERO && coords.2 > S::ZERO
     }
 
+    /// Returns the area of the triangle, positive if its vertices are in the positive
+    /// orientation and negative otherwise.
+    #[inline]
+    pub fn signed_area(&self) -> S {
+        (self.b - self.a).cross(self.c - self.a) * S::HALF
+    }
+
+    /// Returns the area of the triangle.
+    #[inline]
+    pub fn area(&self) -> S {
+        S::abs(self.signed_area())
+    }
+
+    /// Returns the orientation of the vertices of the triangle, or `None` if they are aligned.
+    pub fn orientation(&self) -> Option<Winding> {
+        let area = self.signed_area();
+        if area > S::ZERO {
+            Some(Winding::Positive)
+        } else if area < S::ZERO {
+            Some(Winding::Negative)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the center and radius of the circle that goes through the three vertices,
+    /// or `None` if they are aligned.
+    pub fn circumcircle(&self) -> Option<(Point<S>, S)> {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let d = S::TWO * ab.cross(ac);
+        if d == S::ZERO {
+            return None;
+        }
+
+        let ab2 = ab.square_length();
+        let ac2 = ac.square_length();
+        let offset = Vector::new(ac.y * ab2 - ab.y * ac2, ab.x * ac2 - ac.x * ab2) / d;
+
+        Some((self.a + offset, offset.length()))
+    }
+
+    /// Returns the center and radius of the largest circle contained in the triangle.
+    pub fn incircle(&self) -> (Point<S>, S) {
+        let la = (self.c - self.b).length();
+        let lb = (self.a - self.c).length();
+        let lc = (self.b - self.a).length();
+        let perimeter = la + lb + lc;
+        if perimeter == S::ZERO {
+            return (self.a, S::ZERO);
+        }
+
+        let center = (self.a.to_vector() * la + self.b.to_vector() * lb + self.c.to_vector() * lc)
+            / perimeter;
+
+        (center.to_point(), S::TWO * self.area() / perimeter)
+    }
+
+    /// Returns the point of the triangle (including its interior) that is the closest
+    /// to a given position.
+    pub fn closest_point(&self, point: Point<S>) -> Point<S> {
+        if self.signed_area() != S::ZERO {
+            let (u, v, w) = self.barycentric_coordinates(point);
+            if u >= S::ZERO && v >= S::ZERO && w >= S::ZERO {
+                return point;
+            }
+        }
+
+        let mut closest = self.ab().closest_point(point);
+        for edge in &[self.bc(), self.ca()] {
+            let p = edge.closest_point(point);
+            if (p - point).square_length() < (closest - point).square_length() {
+                closest = p;
+            }
+        }
+
+        closest
+    }
+
+    /// Returns the distance between a position and the triangle (zero inside).
+    #[inline]
+    pub fn distance_to_point(&self, point: Point<S>) -> S {
+        (self.closest_point(point) - point).length()
+    }
+
     /// Return the minimum bounding rectangle.
     #[inline]
     pub fn bounding_rect(&self) -> Rect<S> {
@@ -293,3 +382,46 @@ fn test_bounding_rect() {
         assert_eq!(tri.bounding_rect(), r);
     }
 }
+
+#[test]
+fn triangle_queries() {
+    let t = Triangle {
+        a: point(0.0f64, 0.0),
+        b: point(4.0, 0.0),
+        c: point(0.0, 3.0),
+    };
+
+    assert_eq!(t.signed_area(), 6.0);
+    assert_eq!(t.area(), 6.0);
+    assert_eq!(t.orientation(), Some(Winding::Positive));
+    let flipped = Triangle { b: t.c, c: t.b, ..t };
+    assert_eq!(flipped.signed_area(), -6.0);
+    assert_eq!(flipped.area(), 6.0);
+    assert_eq!(flipped.orientation(), Some(Winding::Negative));
+    let flat = Triangle { c: point(8.0, 0.0), ..t };
+    assert_eq!(flat.orientation(), None);
+    assert!(flat.circumcircle().is_none());
+
+    let (u, v, w) = t.barycentric_coordinates(point(1.0, 1.0));
+    assert!((u - 5.0 / 12.0).abs() < 1e-12);
+    assert!((v - 0.25).abs() < 1e-12);
+    assert!((w - 1.0 / 3.0).abs() < 1e-12);
+    assert_eq!(t.barycentric_coordinates(t.b), (0.0, 1.0, 0.0));
+
+    // The circumcenter of a right triangle is the middle of its hypotenuse.
+    let (center, radius) = t.circumcircle().unwrap();
+    assert!((center - point(2.0, 1.5)).length() < 1e-12);
+    assert!((radius - 2.5).abs() < 1e-12);
+
+    // A 3-4-5 triangle has an inradius of 1.
+    let (center, radius) = t.incircle();
+    assert!((center - point(1.0, 1.0)).length() < 1e-12);
+    assert!((radius - 1.0).abs() < 1e-12);
+
+    assert_eq!(t.closest_point(point(1.0, 1.0)), point(1.0, 1.0));
+    assert_eq!(t.closest_point(point(2.0, -1.0)), point(2.0, 0.0));
+    assert_eq!(t.closest_point(point(5.0, -1.0)), point(4.0, 0.0));
+    assert!((t.closest_point(point(4.0, 3.0)) - point(2.56, 1.08)).length() < 1e-12);
+    assert_eq!(flat.closest_point(point(2.0, 1.0)), point(2.0, 0.0));
+    assert_eq!(t.distance_to_point(point(-3.0, 1.0)), 3.0);
+}
@@ -1,6 +1,7 @@
 use crate::{vector, Point, Vector};
 use crate::scalar::{Float, Scalar};
 use arrayvec::ArrayVec;
+use std::ops::Range;
 
 #[inline]
 pub fn min_max<S: Float>(a: S, b: S) -> (S, S) {
@@ -123,6 +124,131 @@ pub fn cubic_polynomial_roots<S: Scalar>(a: S, b: S, c: S, d: S) -> ArrayVec<[S;
     result
 }
 
+/// Computes the signed curvature of a curve from its first and second derivatives.
+///
+/// Returns zero if the first derivative is zero.
+#[inline]
+pub fn curvature<S: Scalar>(derivative: Vector<S>, second_derivative: Vector<S>) -> S {
+    let square_speed = derivative.square_length();
+    if square_speed == S::ZERO {
+        return S::ZERO;
+    }
+
+    derivative.cross(second_derivative) / (square_speed * square_speed.sqrt())
+}
+
+/// Evaluates a polynomial at t.
+///
+/// The coefficients are provided in increasing degree order (`coeffs[i]` is the
+/// coefficient of `t^i`).
+#[inline]
+pub fn evaluate_polynomial<S: Scalar>(coeffs: &[S], t: S) -> S {
+    let mut result = S::ZERO;
+    for &c in coeffs.iter().rev() {
+        result = result * t + c;
+    }
+
+    result
+}
+
+/// Finds the roots of a polynomial within a given range.
+///
+/// The coefficients are provided in increasing degree order (`coeffs[i]` is the
+/// coefficient of `t^i`) and the degree of the polynomial must not exceed 8.
+///
+/// The extrema of the polynomial are found recursively from its derivative, which
+/// isolates each root in an interval where the polynomial is monotonic. The roots are
+/// then refined with a bisection. Only roots where the polynomial changes sign (or is
+/// exactly zero) are reported, in increasing order.
+pub fn polynomial_roots_in_range<S: Scalar>(coeffs: &[S], range: Range<S>) -> ArrayVec<[S; 8]> {
+    let mut result = ArrayVec::new();
+
+    let mut degree = coeffs.len();
+    while degree > 0 && coeffs[degree - 1] == S::ZERO {
+        degree -= 1;
+    }
+    let coeffs = &coeffs[..degree];
+
+    if degree < 2 {
+        return result;
+    }
+
+    debug_assert!(degree <= 9);
+
+    if degree == 2 {
+        let t = -coeffs[0] / coeffs[1];
+        if t >= range.start && t <= range.end {
+            result.push(t);
+        }
+        return result;
+    }
+
+    let mut derivative: ArrayVec<[S; 8]> = ArrayVec::new();
+    let mut n = S::ONE;
+    for &c in &coeffs[1..] {
+        derivative.push(c * n);
+        n += S::ONE;
+    }
+
+    let mut bounds: ArrayVec<[S; 10]> = ArrayVec::new();
+    bounds.push(range.start);
+    for t in polynomial_roots_in_range(&derivative, range.clone()) {
+        if t > range.start && t < range.end {
+            bounds.push(t);
+        }
+    }
+    bounds.push(range.end);
+
+    for i in 0..(bounds.len() - 1) {
+        let a = bounds[i];
+        let b = bounds[i + 1];
+        let fa = evaluate_polynomial(coeffs, a);
+        let fb = evaluate_polynomial(coeffs, b);
+        let root = if fa == S::ZERO {
+            a
+        } else if fb != S::ZERO && (fa < S::ZERO) != (fb < S::ZERO) {
+            bisect(&|t| evaluate_polynomial(coeffs, t), a, b, fa)
+        } else {
+            continue;
+        };
+
+        if result.last() != Some(&root) {
+            result.push(root);
+        }
+    }
+
+    if evaluate_polynomial(coeffs, range.end) == S::ZERO && result.last() != Some(&range.end) {
+        result.push(range.end);
+    }
+
+    result
+}
+
+/// Finds a root of a function between `a` and `b` using a bisection.
+///
+/// The function must change sign within the interval. `fa` is the value of the
+/// function at `a`.
+pub(crate) fn bisect<S: Scalar, F: Fn(S) -> S>(f: &F, mut a: S, mut b: S, mut fa: S) -> S {
+    for _ in 0..64 {
+        let mid = (a + b) * S::HALF;
+        if mid <= a || mid >= b {
+            return mid;
+        }
+        let fm = f(mid);
+        if fm == S::ZERO {
+            return mid;
+        }
+        if (fm < S::ZERO) == (fa < S::ZERO) {
+            a = mid;
+            fa = fm;
+        } else {
+            b = mid;
+        }
+    }
+
+    (a + b) * S::HALF
+}
+
 #[test]
 fn cubic_polynomial() {
     fn assert_approx_eq(a: ArrayVec<[f32; 3]>, b: &[f32], epsilon: f32) {
@@ -172,3 +298,46 @@ fn cubic_polynomial() {
     // Constant.
     assert_approx_eq(cubic_polynomial_roots(0.0, 0.0, 0.0, 0.0), &[], 0.00005);
 }
+
+#[test]
+fn polynomial_roots() {
+    fn assert_approx_eq(a: ArrayVec<[f64; 8]>, b: &[f64], epsilon: f64) {
+        assert_eq!(a.len(), b.len(), "{:?} != {:?}", a, b);
+        for i in 0..a.len() {
+            assert!((a[i] - b[i]).abs() <= epsilon, "{:?} != {:?}", a, b);
+        }
+    }
+
+    // (t - 0.1)(t - 0.5)(t - 0.7)
+    assert_approx_eq(
+        polynomial_roots_in_range(&[-0.035, 0.47, -1.3, 1.0], 0.0..1.0),
+        &[0.1, 0.5, 0.7],
+        1e-9,
+    );
+
+    // (t - 0.1)(t - 0.2)(t - 0.3)(t - 0.4)(t - 2.0)
+    let mut coeffs = [1.0f64, 0.0, 0.0, 0.0, 0.0, 0.0];
+    for &r in &[0.1, 0.2, 0.3, 0.4, 2.0] {
+        let mut next = [0.0; 6];
+        for i in 0..5 {
+            next[i + 1] += coeffs[i];
+            next[i] -= coeffs[i] * r;
+        }
+        coeffs = next;
+    }
+    assert_approx_eq(
+        polynomial_roots_in_range(&coeffs, 0.0..1.0),
+        &[0.1, 0.2, 0.3, 0.4],
+        1e-9,
+    );
+
+    // No root in range.
+    assert_approx_eq(polynomial_roots_in_range(&[1.0, 0.0, 1.0], -10.0..10.0), &[], 0.0);
+
+    // Roots on the range boundaries.
+    assert_approx_eq(
+        polynomial_roots_in_range(&[0.0, -1.0, 1.0], 0.0..1.0),
+        &[0.0, 1.0],
+        0.0,
+    );
+}
@@ -80,7 +80,7 @@
 //!
 
 use crate::events::PathEvent;
-use crate::geom::{Arc, ArcFlags, SvgArc, LineSegment, traits::Transformation};
+use crate::geom::{Arc, ArcFlags, ConicSegment, SvgArc, LineSegment, traits::Transformation};
 use crate::math::*;
 use crate::polygon::Polygon;
 use crate::path::Verb;
@@ -121,6 +121,32 @@ impl std::fmt::Display for BorderRadii {
     }
 }
 
+/// How the knots of a Catmull-Rom spline are spaced.
+///
+/// See [PathBuilder::add_catmull_rom_spline](trait.PathBuilder.html#method.add_catmull_rom_spline).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum CatmullRomParameterization {
+    /// The knots are evenly spaced, regardless of the distance between the points.
+    Uniform,
+    /// The knots are spaced by the square root of the distance between the points.
+    ///
+    /// This variant never produces cusps or self-intersections within a segment.
+    Centripetal,
+    /// The knots are spaced by the distance between the points.
+    Chordal,
+}
+
+impl CatmullRomParameterization {
+    fn alpha(self) -> f32 {
+        match self {
+            CatmullRomParameterization::Uniform => 0.0,
+            CatmullRomParameterization::Centripetal => 0.5,
+            CatmullRomParameterization::Chordal => 1.0,
+        }
+    }
+}
+
 /// The base path building interface.
 ///
 /// Unlike `SvgPathBuilder`, this interface strictly requires sub-paths to be manually
@@ -169,6 +195,29 @@ pub trait PathBuilder {
     /// A sub-path must be in progress when this method is called.
     fn cubic_bezier_to(&mut self, ctrl1: Point, ctrl2: Point, to: Point) -> EndpointId;
 
+    /// Adds a conic section (rational quadratic bézier curve) to the current sub-path.
+    ///
+    /// Paths only store polynomial curves, so conics are stored without loss only when they
+    /// are polynomial curves: a weight of one produces a single quadratic bézier curve and a
+    /// weight of zero a line. Other conics are approximated with quadratic bézier curves,
+    /// `tolerance` being the only guarantee on the distance between the conic and the stored
+    /// curves, and their weight can't be recovered from the path.
+    ///
+    /// A sub-path must be in progress when this method is called and `conic.from` must
+    /// be its current position.
+    fn conic_segment_to(&mut self, conic: &ConicSegment<f32>, tolerance: f32) -> EndpointId {
+        if conic.weight == 0.0 {
+            return self.line_to(conic.to);
+        }
+
+        let mut id = EndpointId::INVALID;
+        conic.for_each_quadratic_bezier(tolerance, &mut |curve| {
+            id = self.quadratic_bezier_to(curve.ctrl, curve.to);
+        });
+
+        id
+    }
+
     /// Hints at the builder that a certain number of endpoints and control
     /// points will be added.
     ///
@@ -329,6 +378,57 @@ pub trait PathBuilder {
         add_rounded_rectangle(self, rect, radii, winding);
     }
 
+    /// Adds a sub-path containing a cubic hermite spline.
+    ///
+    /// The spline interpolates `points`, with the corresponding derivatives in `tangents`.
+    /// Each pair of consecutive points is joined by a cubic bézier curve. If `closed`
+    /// is true, a last curve joins the last point to the first one.
+    ///
+    /// `points` and `tangents` must have the same length.
+    ///
+    /// There must be no sub-path in progress when this method is called.
+    /// No sub-path is in progress after the method is called.
+    fn add_hermite_spline(&mut self, points: &[Point], tangents: &[Vector], closed: bool)
+    where
+        Self: Sized
+    {
+        add_hermite_spline(self, points, tangents, closed);
+    }
+
+    /// Adds a sub-path containing a Catmull-Rom spline interpolating `points`.
+    ///
+    /// Each pair of consecutive points is joined by a cubic bézier curve. The end points
+    /// of open splines are extended by mirroring their neighbor. If `closed` is true, a
+    /// last curve joins the last point to the first one.
+    ///
+    /// There must be no sub-path in progress when this method is called.
+    /// No sub-path is in progress after the method is called.
+    fn add_catmull_rom_spline(
+        &mut self,
+        points: &[Point],
+        parameterization: CatmullRomParameterization,
+        closed: bool,
+    )
+    where
+        Self: Sized
+    {
+        add_catmull_rom_spline(self, points, parameterization.alpha(), 0.0, closed);
+    }
+
+    /// Adds a sub-path containing a cardinal spline interpolating `points`.
+    ///
+    /// A tension of zero produces a uniform Catmull-Rom spline and a tension of one
+    /// produces straight lines between the points.
+    ///
+    /// There must be no sub-path in progress when this method is called.
+    /// No sub-path is in progress after the method is called.
+    fn add_cardinal_spline(&mut self, points: &[Point], tension: f32, closed: bool)
+    where
+        Self: Sized
+    {
+        add_catmull_rom_spline(self, points, 0.0, tension, closed);
+    }
+
     /// Returns a builder that approximates all curves with sequences of line segments.
     fn flattened(self, tolerance: f32) -> Flattened<Self>
     where
@@ -836,6 +936,44 @@ impl<Builder: PathBuilder> WithSvg<Builder> {
         self.builder.cubic_bezier_to(ctrl1, ctrl2, to)
     }
 
+    /// Adds a conic section (rational quadratic bézier curve) starting at the current
+    /// position.
+    ///
+    /// Only conics with a weight of zero or one are stored without loss, the others are
+    /// approximated with quadratic bézier curves within `tolerance`.
+    ///
+    /// See [`PathBuilder::conic_segment_to`](trait.PathBuilder.html#method.conic_segment_to).
+    pub fn conic_to(&mut self, ctrl: Point, to: Point, weight: f32, tolerance: f32) -> EndpointId {
+        if let Some(id) = self.begin_if_needed(&to) {
+            return id;
+        }
+
+        if weight == 0.0 {
+            return self.line_to(to);
+        }
+
+        let conic = ConicSegment {
+            from: self.current_position,
+            ctrl,
+            to,
+            weight,
+        };
+
+        let mut id = EndpointId::INVALID;
+        let mut last_ctrl = ctrl;
+        let builder = &mut self.builder;
+        conic.for_each_quadratic_bezier(tolerance, &mut |curve| {
+            id = builder.quadratic_bezier_to(curve.ctrl, curve.to);
+            last_ctrl = curve.ctrl;
+        });
+
+        self.current_position = to;
+        self.last_cmd = Verb::QuadraticTo;
+        self.last_ctrl = last_ctrl;
+
+        id
+    }
+
     pub fn arc(&mut self, center: Point, radii: Vector, sweep_angle: Angle, x_rotation: Angle) {
         nan_check(center);
         nan_check(radii.to_point());
@@ -1212,6 +1350,111 @@ fn add_rounded_rectangle<Builder: PathBuilder>(
     }
 }
 
+fn add_hermite_spline<Builder: PathBuilder>(
+    builder: &mut Builder,
+    points: &[Point],
+    tangents: &[Vector],
+    closed: bool,
+) {
+    debug_assert_eq!(points.len(), tangents.len());
+    let n = points.len().min(tangents.len());
+    if n == 0 {
+        return;
+    }
+
+    let num_curves = if closed { n } else { n - 1 };
+    builder.reserve(num_curves + 1, num_curves * 2);
+
+    builder.begin(points[0]);
+    for i in 0..num_curves {
+        let j = (i + 1) % n;
+        hermite_to(builder, points[i], tangents[i], points[j], tangents[j]);
+    }
+    builder.end(closed);
+}
+
+/// Catmull-Rom splines with knots spaced by the distance between points raised to the
+/// power of `alpha`, and tangents scaled down by `tension`.
+fn add_catmull_rom_spline<Builder: PathBuilder>(
+    builder: &mut Builder,
+    points: &[Point],
+    alpha: f32,
+    tension: f32,
+    closed: bool,
+) {
+    let n = points.len();
+    if n == 0 {
+        return;
+    }
+
+    // Neighbors of the end points of open splines are mirrored.
+    let get = |idx: isize| -> Point {
+        if closed {
+            return points[idx.rem_euclid(n as isize) as usize];
+        }
+        if idx < 0 {
+            return points[0] + (points[0] - points[1.min(n - 1)]);
+        }
+        if idx as usize >= n {
+            return points[n - 1] + (points[n - 1] - points[n.saturating_sub(2)]);
+        }
+        points[idx as usize]
+    };
+
+    let knot_interval = |a: Point, b: Point| -> f32 {
+        if alpha == 0.0 {
+            return 1.0;
+        }
+        (b - a).square_length().powf(alpha * 0.5)
+    };
+
+    let num_curves = if closed { n } else { n - 1 };
+    builder.reserve(num_curves + 1, num_curves * 2);
+
+    let scale = 1.0 - tension;
+    builder.begin(points[0]);
+    for i in 0..num_curves as isize {
+        let p0 = get(i - 1);
+        let p1 = get(i);
+        let p2 = get(i + 1);
+        let p3 = get(i + 2);
+
+        // Coincident points would produce divisions by zero, fall back to the
+        // neighboring intervals.
+        let mut dt1 = knot_interval(p1, p2);
+        if dt1 < 1e-4 {
+            dt1 = 1.0;
+        }
+        let mut dt0 = knot_interval(p0, p1);
+        if dt0 < 1e-4 {
+            dt0 = dt1;
+        }
+        let mut dt2 = knot_interval(p2, p3);
+        if dt2 < 1e-4 {
+            dt2 = dt1;
+        }
+
+        // Tangents at p1 and p2 of the non-uniform spline, rescaled to the
+        // [0, 1] parameter range of the curve between them.
+        let m1 = ((p1 - p0) / dt0 - (p2 - p0) / (dt0 + dt1) + (p2 - p1) / dt1) * dt1;
+        let m2 = ((p2 - p1) / dt1 - (p3 - p1) / (dt1 + dt2) + (p3 - p2) / dt2) * dt1;
+
+        hermite_to(builder, p1, m1 * scale, p2, m2 * scale);
+    }
+    builder.end(closed);
+}
+
+/// Adds the cubic bézier curve representation of a cubic hermite curve.
+fn hermite_to<Builder: PathBuilder>(
+    builder: &mut Builder,
+    from: Point,
+    from_tangent: Vector,
+    to: Point,
+    to_tangent: Vector,
+) {
+    builder.cubic_bezier_to(from + from_tangent / 3.0, to - to_tangent / 3.0, to);
+}
+
 #[inline]
 fn nan_check(p: Point) {
     debug_assert!(p.x.is_finite());
@@ -1311,3 +1554,174 @@ fn svg_builder_relative_curves() {
     );
     assert_eq!(it.next(), None);
 }
+
+#[test]
+fn conic_to() {
+    use crate::Path;
+
+    let mut builder = Path::builder().with_svg();
+    builder.move_to(point(1.0, 0.0));
+    // A quarter of the unit circle.
+    builder.conic_to(point(1.0, 1.0), point(0.0, 1.0), std::f32::consts::FRAC_1_SQRT_2, 0.001);
+    // With a weight of one, a regular quadratic bézier curve.
+    builder.conic_to(point(-1.0, 1.0), point(-1.0, 0.0), 1.0, 0.001);
+    // With a weight of zero, a line.
+    builder.conic_to(point(-1.0, -1.0), point(0.0, -1.0), 0.0, 0.001);
+    builder.close();
+    let path = builder.build();
+
+    assert!(path.iter().any(|evt| evt
+        == PathEvent::Line {
+            from: point(-1.0, 0.0),
+            to: point(0.0, -1.0)
+        }));
+
+    let mut quadratics = 0;
+    for event in &path {
+        if let PathEvent::Quadratic { from, ctrl, to } = event {
+            quadratics += 1;
+            if to.x >= 0.0 {
+                let curve = crate::geom::QuadraticBezierSegment { from, ctrl, to };
+                for i in 0..=10 {
+                    let p = curve.sample(i as f32 / 10.0);
+                    assert!((p.to_vector().length() - 1.0).abs() <= 0.001);
+                }
+            } else {
+                assert_eq!(ctrl, point(-1.0, 1.0));
+                assert_eq!(to, point(-1.0, 0.0));
+            }
+        }
+    }
+    assert!(quadratics > 2);
+}
+
+#[test]
+fn catmull_rom_spline() {
+    use crate::Path;
+
+    let points = [
+        point(0.0, 0.0),
+        point(10.0, 10.0),
+        point(20.0, 0.0),
+        point(30.0, 10.0),
+    ];
+
+    for &parameterization in &[
+        CatmullRomParameterization::Uniform,
+        CatmullRomParameterization::Centripetal,
+        CatmullRomParameterization::Chordal,
+    ] {
+        for &closed in &[false, true] {
+            let mut builder = Path::builder();
+            builder.add_catmull_rom_spline(&points, parameterization, closed);
+            let path = builder.build();
+
+            // The spline goes through all points.
+            let mut endpoints = vec![points[0]];
+            for event in &path {
+                match event {
+                    PathEvent::Cubic { to, .. } => endpoints.push(to),
+                    PathEvent::End { close, .. } => assert_eq!(close, closed),
+                    PathEvent::Begin { .. } => {}
+                    _ => panic!("unexpected event {:?}", event),
+                }
+            }
+            let expected: Vec<Point> = if closed {
+                points.iter().chain(Some(&points[0])).cloned().collect()
+            } else {
+                points.to_vec()
+            };
+            assert_eq!(endpoints, expected);
+        }
+    }
+
+    // Equally spaced points have the same uniform, centripetal and chordal splines.
+    let points = [point(0.0, 0.0), point(3.0, 4.0), point(8.0, 4.0), point(11.0, 0.0)];
+    let mut uniform = Path::builder();
+    uniform.add_catmull_rom_spline(&points, CatmullRomParameterization::Uniform, false);
+    let mut chordal = Path::builder();
+    chordal.add_catmull_rom_spline(&points, CatmullRomParameterization::Chordal, false);
+    for (a, b) in uniform.build().iter().zip(chordal.build().iter()) {
+        if let (
+            PathEvent::Cubic { ctrl1: a1, ctrl2: a2, .. },
+            PathEvent::Cubic { ctrl1: b1, ctrl2: b2, .. },
+        ) = (a, b) {
+            assert!((a1 - b1).length() < 1e-4);
+            assert!((a2 - b2).length() < 1e-4);
+        }
+    }
+
+    // Coincident points don't produce NaNs.
+    let mut builder = Path::builder();
+    builder.add_catmull_rom_spline(
+        &[point(0.0, 0.0), point(0.0, 0.0), point(1.0, 0.0)],
+        CatmullRomParameterization::Centripetal,
+        false,
+    );
+    for event in &builder.build() {
+        if let PathEvent::Cubic { ctrl1, ctrl2, .. } = event {
+            assert!(ctrl1.x.is_finite() && ctrl1.y.is_finite());
+            assert!(ctrl2.x.is_finite() && ctrl2.y.is_finite());
+        }
+    }
+
+    // A single point.
+    let mut builder = Path::builder();
+    builder.add_catmull_rom_spline(&[point(1.0, 2.0)], CatmullRomParameterization::Uniform, false);
+    assert_eq!(builder.build().iter().count(), 2);
+}
+
+#[test]
+fn cardinal_spline() {
+    use crate::Path;
+
+    let points = [point(0.0, 0.0), point(10.0, 10.0), point(20.0, 0.0)];
+
+    // A tension of one produces straight lines.
+    let mut builder = Path::builder();
+    builder.add_cardinal_spline(&points, 1.0, false);
+    for event in &builder.build() {
+        if let PathEvent::Cubic { from, ctrl1, ctrl2, to } = event {
+            assert_eq!(ctrl1, from);
+            assert_eq!(ctrl2, to);
+        }
+    }
+
+    // A tension of zero produces a uniform Catmull-Rom spline.
+    let mut cardinal = Path::builder();
+    cardinal.add_cardinal_spline(&points, 0.0, true);
+    let mut catmull_rom = Path::builder();
+    catmull_rom.add_catmull_rom_spline(&points, CatmullRomParameterization::Uniform, true);
+    let cardinal: Vec<PathEvent> = cardinal.build().iter().collect();
+    let catmull_rom: Vec<PathEvent> = catmull_rom.build().iter().collect();
+    assert_eq!(cardinal, catmull_rom);
+    // The tangents are parallel to the line between the neighbors of each point.
+    if let PathEvent::Cubic { ctrl1, ctrl2, .. } = cardinal[1] {
+        assert!((ctrl1 - point(-5.0 / 3.0, 5.0 / 3.0)).length() < 1e-5);
+        assert!((ctrl2 - point(20.0 / 3.0, 10.0)).length() < 1e-5);
+    } else {
+        panic!();
+    }
+}
+
+#[test]
+fn hermite_spline() {
+    use crate::Path;
+
+    let mut builder = Path::builder();
+    builder.add_hermite_spline(
+        &[point(0.0, 0.0), point(3.0, 0.0)],
+        &[vector(3.0, 3.0), vector(3.0, -3.0)],
+        false,
+    );
+    let events: Vec<PathEvent> = builder.build().iter().collect();
+    assert_eq!(
+        events[1],
+        PathEvent::Cubic {
+            from: point(0.0, 0.0),
+            ctrl1: point(1.0, 1.0),
+            ctrl2: point(2.0, 1.0),
+            to: point(3.0, 0.0),
+        }
+    );
+}
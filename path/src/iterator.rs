@@ -91,7 +91,7 @@
 //! }
 //! ```
 
-use crate::geom::traits::Transformation;
+use crate::geom::traits::{Segment, Transformation, Warp};
 use crate::geom::{
     cubic_bezier, quadratic_bezier, BezierSegment, CubicBezierSegment, LineSegment,
     QuadraticBezierSegment,
@@ -111,6 +111,17 @@ pub trait PathIterator: Iterator<Item = PathEvent> + Sized {
         Transformed::new(mat, self)
     }
 
+    /// Returns an iterator applying a non-affine transformation to all of its events.
+    ///
+    /// Segments are subdivided and approximated with cubic bézier curves so that the result
+    /// stays within `tolerance` of the exact warped path. The subdivision depth is limited,
+    /// so warps that vary too quickly for that depth may produce pieces beyond `tolerance`.
+    ///
+    /// The parts of the path that the warp can't map are removed (see `Warped`).
+    fn warped<W: Warp<f32>>(self, warp: W, tolerance: f32) -> Warped<Self, W> {
+        Warped::new(warp, tolerance, self)
+    }
+
     /// Returns an iterator of segments.
     fn bezier_segments(self) -> BezierSegments<Self> {
         BezierSegments { iter: self }
@@ -236,6 +247,364 @@ where
     }
 }
 
+/// Applies a non-affine transformation to a path iterator and yields the resulting path iterator.
+///
+/// Since the transformation does not preserve straight lines, all segments including lines are
+/// adaptively subdivided and each piece is approximated with a cubic bézier curve fitted to the
+/// warped positions and tangents of the original segment, until the approximation is within the
+/// tolerance threshold. Line segments that remain straight after warping are kept as lines.
+///
+/// The closing edges of closed sub-paths are warped as well.
+///
+/// The parts of the path that can't be warped, for example the points that a perspective
+/// projection puts behind the viewer, are clipped out: the sub-paths are split into open
+/// sub-paths around them.
+///
+/// # Example
+///
+/// ```
+/// use lyon_path::iterator::PathIterator;
+/// use lyon_path::math::{point, Point};
+/// use lyon_path::{Path, PathEvent};
+///
+/// let mut builder = Path::builder();
+/// builder.begin(point(0.0, 0.0));
+/// builder.line_to(point(100.0, 0.0));
+/// builder.line_to(point(100.0, 10.0));
+/// builder.end(true);
+/// let path = builder.build();
+///
+/// // Bend the path along a circle.
+/// let warp = |p: Point| {
+///     let angle = p.x / 100.0;
+///     point(0.0, -200.0) + lyon_path::math::vector(angle.sin(), angle.cos()) * (200.0 - p.y)
+/// };
+///
+/// let warped: Vec<PathEvent> = path.iter().warped(&warp, 0.01).collect();
+/// assert!(warped.iter().any(|evt| matches!(evt, PathEvent::Cubic { .. })));
+/// ```
+pub struct Warped<I, W> {
+    it: I,
+    warp: W,
+    tolerance: f32,
+    // Start of the current run of warped segments.
+    first: Point,
+    current: Point,
+    // Whether a run of warped segments is in progress.
+    drawing: bool,
+    // Whether parts of the current sub-path couldn't be warped.
+    clipped: bool,
+    // Pending events in reverse order.
+    pending: Vec<PathEvent>,
+}
+
+impl<I, W: Warp<f32>> Warped<I, W>
+where
+    I: Iterator<Item = PathEvent>,
+{
+    /// Creates a new warped path iterator from a path iterator.
+    #[inline]
+    pub fn new(warp: W, tolerance: f32, it: I) -> Warped<I, W> {
+        Warped {
+            it,
+            warp,
+            tolerance,
+            first: point(0.0, 0.0),
+            current: point(0.0, 0.0),
+            drawing: false,
+            clipped: false,
+            pending: Vec::new(),
+        }
+    }
+
+    fn warp_segment<Seg: Segment<Scalar = f32>>(&mut self, segment: &Seg, is_line: bool) {
+        let mut pieces = WarpedPieces {
+            segment,
+            warp: &self.warp,
+            tolerance: self.tolerance,
+            is_line,
+            range: 0.0..1.0,
+            output: &mut self.pending,
+        };
+
+        let ranges = pieces.warpable_ranges();
+        if ranges.len() != 1 || ranges[0] != (0.0..1.0) {
+            self.clipped = true;
+        }
+        let reaches_end = matches!(ranges.last(), Some(range) if range.end >= 1.0);
+
+        for range in ranges {
+            if range.start > 0.0 || !self.drawing {
+                if self.drawing {
+                    pieces.output.push(PathEvent::End {
+                        last: self.current,
+                        first: self.first,
+                        close: false,
+                    });
+                }
+                self.first = pieces.sample(range.start).unwrap();
+                self.current = self.first;
+                self.drawing = true;
+                pieces.output.push(PathEvent::Begin { at: self.first });
+            }
+
+            let to = pieces.sample(range.end).unwrap();
+            pieces.range = range.clone();
+            let d0 = pieces.derivative(range.start);
+            let d1 = pieces.derivative(range.end);
+            pieces.approximate(range.clone(), (self.current, d0), (to, d1), 0);
+            self.current = to;
+
+            if range.end < 1.0 {
+                pieces.output.push(PathEvent::End {
+                    last: self.current,
+                    first: self.first,
+                    close: false,
+                });
+                self.drawing = false;
+            }
+        }
+
+        if self.drawing && !reaches_end {
+            pieces.output.push(PathEvent::End {
+                last: self.current,
+                first: self.first,
+                close: false,
+            });
+            self.drawing = false;
+        }
+    }
+}
+
+impl<I, W> Iterator for Warped<I, W>
+where
+    I: Iterator<Item = PathEvent>,
+    W: Warp<f32>,
+{
+    type Item = PathEvent;
+    fn next(&mut self) -> Option<PathEvent> {
+        loop {
+            if let Some(evt) = self.pending.pop() {
+                return Some(evt);
+            }
+
+            match self.it.next()? {
+                PathEvent::Begin { at } => {
+                    self.clipped = false;
+                    self.drawing = false;
+                    match self.warp.warp_point(at) {
+                        Some(at) => {
+                            self.first = at;
+                            self.current = at;
+                            self.drawing = true;
+                            self.pending.push(PathEvent::Begin { at });
+                        }
+                        None => {
+                            self.clipped = true;
+                        }
+                    }
+                }
+                PathEvent::Line { from, to } => {
+                    self.warp_segment(&LineSegment { from, to }, true);
+                }
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    self.warp_segment(&QuadraticBezierSegment { from, ctrl, to }, false);
+                }
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    self.warp_segment(
+                        &CubicBezierSegment {
+                            from,
+                            ctrl1,
+                            ctrl2,
+                            to,
+                        },
+                        false,
+                    );
+                }
+                PathEvent::End { last, first, close } => {
+                    // The closing edge is implicitly a straight line which may not be straight
+                    // after warping.
+                    if close && last != first {
+                        self.warp_segment(
+                            &LineSegment {
+                                from: last,
+                                to: first,
+                            },
+                            true,
+                        );
+                    }
+                    if self.drawing {
+                        // Sub-paths that are only partially warped can't be closed.
+                        self.pending.push(PathEvent::End {
+                            last: self.current,
+                            first: self.first,
+                            close: close && !self.clipped,
+                        });
+                        self.drawing = false;
+                    }
+                }
+            }
+
+            self.pending.reverse();
+        }
+    }
+}
+
+struct WarpedPieces<'l, Seg, W> {
+    segment: &'l Seg,
+    warp: &'l W,
+    tolerance: f32,
+    is_line: bool,
+    // The range of the segment being approximated, which can be warped.
+    range: std::ops::Range<f32>,
+    output: &'l mut Vec<PathEvent>,
+}
+
+impl<'l, Seg: Segment<Scalar = f32>, W: Warp<f32>> WarpedPieces<'l, Seg, W> {
+    const MAX_DEPTH: u32 = 10;
+
+    fn sample(&self, t: f32) -> Option<Point> {
+        // Use the exact endpoints so that consecutive segments stay connected.
+        let p = if t <= 0.0 {
+            self.segment.from()
+        } else if t >= 1.0 {
+            self.segment.to()
+        } else {
+            self.segment.sample(t)
+        };
+
+        self.warp.warp_point(p)
+    }
+
+    // Finite difference approximation of the derivative of the warped segment.
+    fn derivative(&self, t: f32) -> Vector {
+        let h = 1.0 / 4096.0;
+        let t0 = (t - h).max(self.range.start);
+        let t1 = (t + h).min(self.range.end);
+        match (self.sample(t0), self.sample(t1)) {
+            (Some(p0), Some(p1)) if t1 > t0 => (p1 - p0) / (t1 - t0),
+            _ => Vector::zero(),
+        }
+    }
+
+    // Ranges of the segment that can be warped, found by looking for changes between evenly
+    // spaced samples.
+    fn warpable_ranges(&self) -> Vec<std::ops::Range<f32>> {
+        const NUM_SAMPLES: u32 = 16;
+        let is_warpable = |t: f32| self.sample(t).is_some();
+
+        let mut ranges = Vec::new();
+        let mut start = if is_warpable(0.0) { Some(0.0) } else { None };
+        let mut prev_t = 0.0;
+        for i in 1..=NUM_SAMPLES {
+            let t = i as f32 / NUM_SAMPLES as f32;
+            let warpable = is_warpable(t);
+            if warpable != start.is_some() {
+                // Bisect the boundary, keeping `lo` on the side of the previous sample.
+                let (mut lo, mut hi) = (prev_t, t);
+                for _ in 0..24 {
+                    let mid = (lo + hi) * 0.5;
+                    if mid <= lo || mid >= hi {
+                        break;
+                    }
+                    if is_warpable(mid) == start.is_some() {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+
+                match start.take() {
+                    Some(start) => {
+                        if lo > start {
+                            ranges.push(start..lo);
+                        }
+                    }
+                    None => start = Some(hi),
+                }
+            }
+            prev_t = t;
+        }
+        if let Some(start) = start {
+            if start < 1.0 {
+                ranges.push(start..1.0);
+            }
+        }
+
+        ranges
+    }
+
+    fn approximate(
+        &mut self,
+        range: std::ops::Range<f32>,
+        start: (Point, Vector),
+        end: (Point, Vector),
+        depth: u32,
+    ) {
+        let dt = range.end - range.start;
+        let samples = [0.125, 0.25, 0.375, 0.5, 0.625, 0.75, 0.875];
+
+        if self.is_line {
+            let chord = LineSegment {
+                from: start.0,
+                to: end.0,
+            };
+            let straight = samples.iter().all(|&u| match self.sample(range.start + dt * u) {
+                Some(p) => chord.distance_to_point(p) <= self.tolerance,
+                None => false,
+            });
+            if straight || depth >= Self::MAX_DEPTH {
+                self.output.push(PathEvent::Line {
+                    from: start.0,
+                    to: end.0,
+                });
+                return;
+            }
+        }
+
+        let curve = CubicBezierSegment {
+            from: start.0,
+            ctrl1: start.0 + start.1 * (dt / 3.0),
+            ctrl2: end.0 - end.1 * (dt / 3.0),
+            to: end.0,
+        };
+        let within_tolerance = samples.iter().all(|&u| match self.sample(range.start + dt * u) {
+            Some(p) => (curve.sample(u) - p).square_length() <= self.tolerance * self.tolerance,
+            None => false,
+        });
+        if within_tolerance || depth >= Self::MAX_DEPTH {
+            self.output.push(PathEvent::Cubic {
+                from: curve.from,
+                ctrl1: curve.ctrl1,
+                ctrl2: curve.ctrl2,
+                to: curve.to,
+            });
+            return;
+        }
+
+        let mid_t = range.start + dt * 0.5;
+        let mid = match self.sample(mid_t) {
+            Some(p) => (p, self.derivative(mid_t)),
+            None => {
+                // Warps that fail between evenly spaced samples aren't supported, connect the
+                // points that could be warped.
+                self.output.push(PathEvent::Line {
+                    from: start.0,
+                    to: end.0,
+                });
+                return;
+            }
+        };
+        self.approximate(range.start..mid_t, start, mid, depth + 1);
+        self.approximate(mid_t..range.end, mid, end, depth + 1);
+    }
+}
+
 /// An iterator that consumes an iterator of `Point`s and produces `Event`s.
 ///
 /// # Example
@@ -463,3 +832,157 @@ fn test_from_polyline_closed() {
     );
     assert_eq!(evts.next(), None);
 }
+
+#[test]
+fn test_warped() {
+    use crate::geom::euclid::default::Transform3D;
+    use crate::Path;
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(100.0, 0.0));
+    builder.quadratic_bezier_to(point(100.0, 100.0), point(0.0, 100.0));
+    builder.end(true);
+    let path = builder.build();
+
+    // A perspective projection maps lines to lines.
+    let projection = Transform3D::rotation(0.0, 1.0, 0.0, Angle::radians(0.5))
+        .then(&Transform3D::perspective(500.0));
+    let events: Vec<PathEvent> = path.iter().warped(projection, 0.01).collect();
+    let project = |p: Point| projection.transform_point2d(p).unwrap();
+    assert_eq!(events[0], PathEvent::Begin { at: project(point(0.0, 0.0)) });
+    assert_eq!(
+        events[1],
+        PathEvent::Line {
+            from: project(point(0.0, 0.0)),
+            to: project(point(100.0, 0.0)),
+        }
+    );
+    assert_eq!(
+        events[events.len() - 2],
+        PathEvent::Line {
+            from: project(point(0.0, 100.0)),
+            to: project(point(0.0, 0.0)),
+        }
+    );
+    assert_eq!(
+        events[events.len() - 1],
+        PathEvent::End {
+            last: project(point(0.0, 0.0)),
+            first: project(point(0.0, 0.0)),
+            close: true,
+        }
+    );
+
+    // A bilinear warp of the unit square onto an arbitrary quadrilateral.
+    let corners = [
+        point(0.0, 0.0),
+        point(120.0, 20.0),
+        point(90.0, 100.0),
+        point(-10.0, 60.0),
+    ];
+    let bilinear = |p: Point| {
+        let (u, v) = (p.x / 100.0, p.y / 100.0);
+        let top = corners[0].lerp(corners[1], u);
+        let bottom = corners[3].lerp(corners[2], u);
+        top.lerp(bottom, v)
+    };
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(100.0, 100.0));
+    builder.cubic_bezier_to(point(50.0, 0.0), point(0.0, 50.0), point(0.0, 100.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let tolerance = 0.05;
+    let mut exact = Vec::new();
+    for segment in path.iter().bezier_segments() {
+        let mut prev = bilinear(segment.from());
+        for i in 1..=2000 {
+            let p = bilinear(segment.sample(i as f32 / 2000.0));
+            exact.push(LineSegment { from: prev, to: p });
+            prev = p;
+        }
+    }
+
+    let mut current = bilinear(point(0.0, 0.0));
+    let mut num_curves = 0;
+    for segment in path.iter().warped(&bilinear, tolerance).bezier_segments() {
+        assert_eq!(segment.from(), current);
+        current = segment.to();
+        if let BezierSegment::Cubic(..) = segment {
+            num_curves += 1;
+        }
+        for i in 0..=20 {
+            let p = segment.sample(i as f32 / 20.0);
+            let d = exact
+                .iter()
+                .map(|e| e.distance_to_point(p))
+                .fold(f32::MAX, f32::min);
+            assert!(d <= tolerance, "{:?} {}", p, d);
+        }
+    }
+    assert_eq!(current, bilinear(point(0.0, 100.0)));
+    assert!(num_curves >= 2);
+}
+
+#[test]
+fn test_warped_clipping() {
+    use crate::geom::euclid::default::Transform3D;
+    use crate::Path;
+
+    // The points with x <= -100 are projected on or behind the viewer.
+    let mut projection = Transform3D::identity();
+    projection.m14 = 0.01;
+    let project = |p: Point| projection.transform_point2d(p).unwrap();
+    let unproject = |p: Point| p / (1.0 - 0.01 * p.x);
+
+    let mut builder = Path::builder();
+    builder.begin(point(-150.0, -10.0));
+    builder.line_to(point(50.0, -10.0));
+    builder.line_to(point(50.0, 10.0));
+    builder.line_to(point(-150.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+
+    // The visible part of the rectangle is a single open sub-path. The edge that is fully
+    // visible is projected exactly, and the clipped ones go towards the horizon.
+    let events: Vec<PathEvent> = path.iter().warped(projection, 0.01).collect();
+    let n = events.len();
+    let (start, end) = match (events[0], events[n - 1]) {
+        (PathEvent::Begin { at }, PathEvent::End { last, first, close: false }) => {
+            assert_eq!(first, at);
+            (at, last)
+        }
+        _ => panic!("{:?}", events),
+    };
+    assert!(events.contains(&PathEvent::Line {
+        from: project(point(50.0, -10.0)),
+        to: project(point(50.0, 10.0)),
+    }));
+    let mut current = start;
+    for evt in &events[1..n - 1] {
+        match *evt {
+            PathEvent::Line { from, to } => {
+                assert_eq!(from, current);
+                // All points are projections of the visible part of the rectangle.
+                let p = unproject(to);
+                assert!(p.x > -100.1 && p.x <= 50.0, "{:?}", evt);
+                assert!((p.y.abs() - 10.0).abs() < 0.01 || p.x == 50.0, "{:?}", evt);
+                current = to;
+            }
+            _ => panic!("{:?}", events),
+        }
+    }
+    assert_eq!(current, end);
+    for p in &[start, end] {
+        assert!(p.x < -1000.0, "{:?}", p);
+    }
+    assert!(start.y < 0.0 && end.y > 0.0);
+
+    // Entirely hidden sub-paths are removed.
+    let translation = crate::math::Transform::translation(-200.0, 0.0);
+    let hidden = path.iter().transformed(&translation);
+    assert_eq!(hidden.warped(projection, 0.01).count(), 0);
+}
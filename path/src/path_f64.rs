@@ -0,0 +1,383 @@
+//! A path data structure with double precision coordinates.
+//!
+//! The tessellators and most algorithms in lyon work with 32 bits floats, which lack precision
+//! when the geometry is far from the origin (for example with map or CAD coordinates). `PathF64`
+//! stores its endpoints and control points as 64 bits floats and converts them to 32 bits floats
+//! relative to a chosen local origin when consumed, so that precision is only lost in proportion
+//! to the distance to that origin.
+//!
+//! # Examples
+//!
+//! ```
+//! use lyon_path::geom::point;
+//! use lyon_path::path_f64::PathF64;
+//!
+//! let mut builder = PathF64::builder();
+//! builder.begin(point(1_000_000.5, 2_000_000.25));
+//! builder.line_to(point(1_000_001.5, 2_000_000.25));
+//! builder.line_to(point(1_000_001.5, 2_000_001.25));
+//! builder.close();
+//! let path = builder.build();
+//!
+//! // Positions are converted to f32 relative to the origin.
+//! let local = path.to_local_path(point(1_000_000.0, 2_000_000.0));
+//! let first = local.iter().next().unwrap();
+//! assert_eq!(first.from(), lyon_path::math::point(0.5, 0.25));
+//! ```
+
+use crate::commands::{self, PathCommands, PathCommandsBuilder};
+use crate::geom::{point, Point, Rect};
+use crate::math;
+use crate::{ControlPointId, EndpointId, Event, IdEvent, PositionStore};
+
+/// A path event representing endpoints and control points as double precision positions.
+pub type PathEventF64 = Event<Point<f64>, Point<f64>>;
+
+/// A path with double precision endpoints and control points.
+///
+/// Endpoint and control point IDs refer to the same buffer of positions, which
+/// can be accessed via `points`.
+///
+/// See the [module documentation](index.html).
+#[derive(Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct PathF64 {
+    cmds: PathCommands,
+    points: Box<[Point<f64>]>,
+}
+
+impl PathF64 {
+    /// Creates a [PathF64Builder](struct.PathF64Builder.html) to build a path.
+    pub fn builder() -> PathF64Builder {
+        PathF64Builder::new()
+    }
+
+    /// Returns the endpoints and control points of the path.
+    #[inline]
+    pub fn points(&self) -> &[Point<f64>] {
+        &self.points[..]
+    }
+
+    /// Returns the commands of the path.
+    #[inline]
+    pub fn commands(&self) -> &PathCommands {
+        &self.cmds
+    }
+
+    /// Iterates over the events of the path with double precision positions.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            cmds: self.cmds.iter(),
+            points: &self.points,
+        }
+    }
+
+    /// Iterates over the events of the path with endpoint and control point IDs.
+    pub fn id_iter(&self) -> commands::Iter<'_> {
+        self.cmds.iter()
+    }
+
+    /// Returns a position store converting the positions of this path to single
+    /// precision, relative to a local origin.
+    ///
+    /// Together with `id_iter`, this can be used with the tessellators and any API
+    /// that works with a `PositionStore`.
+    pub fn local_positions(&self, origin: Point<f64>) -> LocalPositions<'_> {
+        LocalPositions {
+            points: &self.points,
+            origin,
+        }
+    }
+
+    /// Converts this path into a single precision `Path`, relative to a local origin.
+    pub fn to_local_path(&self, origin: Point<f64>) -> crate::Path {
+        let positions = self.local_positions(origin);
+        let mut builder = crate::Path::builder();
+        for event in self.id_iter() {
+            match event {
+                IdEvent::Begin { at } => {
+                    builder.begin(positions.get_endpoint(at));
+                }
+                IdEvent::Line { to, .. } => {
+                    builder.line_to(positions.get_endpoint(to));
+                }
+                IdEvent::Quadratic { ctrl, to, .. } => {
+                    builder.quadratic_bezier_to(
+                        positions.get_control_point(ctrl),
+                        positions.get_endpoint(to),
+                    );
+                }
+                IdEvent::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => {
+                    builder.cubic_bezier_to(
+                        positions.get_control_point(ctrl1),
+                        positions.get_control_point(ctrl2),
+                        positions.get_endpoint(to),
+                    );
+                }
+                IdEvent::End { close, .. } => {
+                    builder.end(close);
+                }
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Returns a rectangle containing all endpoints and control points of the path, which
+    /// is a good source of local origins.
+    pub fn control_points_bounding_rect(&self) -> Rect<f64> {
+        Rect::from_points(self.points.iter())
+    }
+}
+
+impl<'l> IntoIterator for &'l PathF64 {
+    type Item = PathEventF64;
+    type IntoIter = Iter<'l>;
+
+    fn into_iter(self) -> Iter<'l> {
+        self.iter()
+    }
+}
+
+/// Builds a [PathF64](struct.PathF64.html).
+#[derive(Clone)]
+pub struct PathF64Builder {
+    cmds: PathCommandsBuilder,
+    points: Vec<Point<f64>>,
+}
+
+impl PathF64Builder {
+    /// Creates a builder without allocating memory.
+    pub fn new() -> Self {
+        PathF64Builder {
+            cmds: PathCommandsBuilder::new(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Starts a new sub-path at a given position.
+    pub fn begin(&mut self, at: Point<f64>) -> EndpointId {
+        let id = self.add_endpoint(at);
+        self.cmds.begin(id);
+
+        id
+    }
+
+    /// Ends the current sub-path.
+    pub fn end(&mut self, close: bool) {
+        self.cmds.end(close);
+    }
+
+    /// Closes the current sub-path.
+    pub fn close(&mut self) {
+        self.end(true);
+    }
+
+    /// Adds a line segment to the current sub-path.
+    pub fn line_to(&mut self, to: Point<f64>) -> EndpointId {
+        let id = self.add_endpoint(to);
+        self.cmds.line_to(id);
+
+        id
+    }
+
+    /// Adds a quadratic bézier curve to the current sub-path.
+    pub fn quadratic_bezier_to(&mut self, ctrl: Point<f64>, to: Point<f64>) -> EndpointId {
+        let ctrl = self.add_control_point(ctrl);
+        let id = self.add_endpoint(to);
+        self.cmds.quadratic_bezier_to(ctrl, id);
+
+        id
+    }
+
+    /// Adds a cubic bézier curve to the current sub-path.
+    pub fn cubic_bezier_to(
+        &mut self,
+        ctrl1: Point<f64>,
+        ctrl2: Point<f64>,
+        to: Point<f64>,
+    ) -> EndpointId {
+        let ctrl1 = self.add_control_point(ctrl1);
+        let ctrl2 = self.add_control_point(ctrl2);
+        let id = self.add_endpoint(to);
+        self.cmds.cubic_bezier_to(ctrl1, ctrl2, id);
+
+        id
+    }
+
+    /// Consumes the builder and returns the path.
+    pub fn build(self) -> PathF64 {
+        PathF64 {
+            cmds: self.cmds.build(),
+            points: self.points.into_boxed_slice(),
+        }
+    }
+
+    fn add_endpoint(&mut self, at: Point<f64>) -> EndpointId {
+        let id = EndpointId(self.points.len() as u32);
+        self.points.push(at);
+
+        id
+    }
+
+    fn add_control_point(&mut self, at: Point<f64>) -> ControlPointId {
+        let id = ControlPointId(self.points.len() as u32);
+        self.points.push(at);
+
+        id
+    }
+}
+
+impl Default for PathF64Builder {
+    fn default() -> Self {
+        PathF64Builder::new()
+    }
+}
+
+/// An iterator of `PathEventF64`.
+#[derive(Clone)]
+pub struct Iter<'l> {
+    cmds: commands::Iter<'l>,
+    points: &'l [Point<f64>],
+}
+
+impl<'l> Iterator for Iter<'l> {
+    type Item = PathEventF64;
+
+    fn next(&mut self) -> Option<PathEventF64> {
+        let points = self.points;
+        let ep = |id: EndpointId| points[id.to_usize()];
+        let cp = |id: ControlPointId| points[id.to_usize()];
+        Some(match self.cmds.next()? {
+            IdEvent::Begin { at } => Event::Begin { at: ep(at) },
+            IdEvent::Line { from, to } => Event::Line {
+                from: ep(from),
+                to: ep(to),
+            },
+            IdEvent::Quadratic { from, ctrl, to } => Event::Quadratic {
+                from: ep(from),
+                ctrl: cp(ctrl),
+                to: ep(to),
+            },
+            IdEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => Event::Cubic {
+                from: ep(from),
+                ctrl1: cp(ctrl1),
+                ctrl2: cp(ctrl2),
+                to: ep(to),
+            },
+            IdEvent::End { last, first, close } => Event::End {
+                last: ep(last),
+                first: ep(first),
+                close,
+            },
+        })
+    }
+}
+
+/// A `PositionStore` converting the positions of a `PathF64` to single precision,
+/// relative to a local origin.
+///
+/// See [PathF64::local_positions](struct.PathF64.html#method.local_positions).
+#[derive(Copy, Clone)]
+pub struct LocalPositions<'l> {
+    points: &'l [Point<f64>],
+    origin: Point<f64>,
+}
+
+impl<'l> LocalPositions<'l> {
+    /// The origin of the local coordinate system.
+    #[inline]
+    pub fn origin(&self) -> Point<f64> {
+        self.origin
+    }
+
+    /// Converts a single precision position in the local coordinate system back to
+    /// double precision.
+    #[inline]
+    pub fn to_global(self, p: math::Point) -> Point<f64> {
+        point(self.origin.x + p.x as f64, self.origin.y + p.y as f64)
+    }
+
+    #[inline]
+    fn to_local(self, p: Point<f64>) -> math::Point {
+        (p - self.origin).cast::<f32>().to_point()
+    }
+}
+
+impl<'l> PositionStore for LocalPositions<'l> {
+    fn get_endpoint(&self, id: EndpointId) -> math::Point {
+        self.to_local(self.points[id.to_usize()])
+    }
+
+    fn get_control_point(&self, id: ControlPointId) -> math::Point {
+        self.to_local(self.points[id.to_usize()])
+    }
+}
+
+#[test]
+fn path_f64() {
+    let origin = point(123_456_789.0, -987_654_321.0);
+    let mut builder = PathF64::builder();
+    builder.begin(origin + crate::geom::vector(0.125, 0.25));
+    builder.quadratic_bezier_to(
+        point(123_456_790.0, -987_654_320.0),
+        point(123_456_791.0, -987_654_321.0),
+    );
+    builder.cubic_bezier_to(
+        point(123_456_792.0, -987_654_322.0),
+        point(123_456_793.0, -987_654_322.0),
+        point(123_456_794.0, -987_654_321.0),
+    );
+    builder.end(false);
+    let path = builder.build();
+
+    let events: Vec<PathEventF64> = path.iter().collect();
+    assert_eq!(events.len(), 4);
+    assert_eq!(
+        events[1],
+        Event::Quadratic {
+            from: point(123_456_789.125, -987_654_320.75),
+            ctrl: point(123_456_790.0, -987_654_320.0),
+            to: point(123_456_791.0, -987_654_321.0),
+        }
+    );
+    assert_eq!(
+        events[3],
+        Event::End {
+            last: point(123_456_794.0, -987_654_321.0),
+            first: point(123_456_789.125, -987_654_320.75),
+            close: false,
+        }
+    );
+
+    let positions = path.local_positions(origin);
+    let local: Vec<crate::PathEvent> = path.to_local_path(origin).iter().collect();
+    assert_eq!(
+        local[2],
+        Event::Cubic {
+            from: math::point(2.0, 0.0),
+            ctrl1: math::point(3.0, -1.0),
+            ctrl2: math::point(4.0, -1.0),
+            to: math::point(5.0, 0.0),
+        }
+    );
+    assert_eq!(
+        positions.get_endpoint(EndpointId(0)),
+        math::point(0.125, 0.25)
+    );
+    assert_eq!(
+        positions.to_global(math::point(5.0, 0.0)),
+        point(123_456_794.0, -987_654_321.0)
+    );
+
+    let rect = path.control_points_bounding_rect();
+    assert_eq!(rect.min(), point(123_456_789.125, -987_654_322.0));
+    assert_eq!(rect.max(), point(123_456_794.0, -987_654_320.0));
+}
@@ -7,6 +7,9 @@ pub use crate::geom::{CubicBezierSegment, QuadraticBezierSegment};
 pub use crate::traits::PathBuilder;
 pub use crate::EndpointId;
 
+use crate::geom::{self, Scalar};
+use std::collections::HashMap;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct DebugValidator {
     #[cfg(debug_assertions)]
@@ -87,3 +90,112 @@ pub fn flatten_cubic_bezier(
     id
 }
 
+/// Chains directed edges into closed polygons, calling `callback` with the points of each
+/// polygon.
+///
+/// Each edge is used once and polygons start at the first unused edge. When several edges
+/// leave the same point, the chain follows the first one clockwise from the edge it came
+/// from, so that polygons touching at a point don't cross. If the edges don't form closed
+/// loops, the chains end where there is no edge to follow.
+pub fn chain_edges<S: Scalar>(
+    edges: &[(geom::Point<S>, geom::Point<S>)],
+    callback: &mut dyn FnMut(&[geom::Point<S>]),
+) {
+    // Adding zero turns negative zeros into positive ones.
+    let key = |p: geom::Point<S>| {
+        let p = p.to_f64();
+        ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits())
+    };
+
+    let mut outgoing: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+    for (idx, edge) in edges.iter().enumerate() {
+        outgoing.entry(key(edge.0)).or_default().push(idx);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut points = Vec::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+
+        points.clear();
+        let start = edges[first].0;
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let (from, to) = edges[current];
+            points.push(from);
+            if to == start {
+                break;
+            }
+
+            let back = (from - to).to_f64();
+            let mut next = None;
+            let mut best_angle = f64::INFINITY;
+            let candidates = outgoing.get(&key(to)).map(|v| &v[..]).unwrap_or(&[]);
+            for &candidate in candidates {
+                if used[candidate] {
+                    continue;
+                }
+                let angle = clockwise_angle(back, (edges[candidate].1 - to).to_f64());
+                if angle < best_angle {
+                    best_angle = angle;
+                    next = Some(candidate);
+                }
+            }
+
+            match next {
+                Some(next) => current = next,
+                None => {
+                    points.push(to);
+                    break;
+                }
+            }
+        }
+
+        callback(&points);
+    }
+}
+
+/// Returns the angle to rotate `from` clockwise to get the direction of `to`, in ]0, 2π].
+fn clockwise_angle(from: geom::Vector<f64>, to: geom::Vector<f64>) -> f64 {
+    let tau = std::f64::consts::PI * 2.0;
+    let angle = (from.y.atan2(from.x) - to.y.atan2(to.x)) % tau;
+    if angle <= 0.0 {
+        angle + tau
+    } else {
+        angle
+    }
+}
+
+#[test]
+fn chain_touching_polygons() {
+    use crate::geom::point;
+
+    // Two squares touching at (1, 1), and a triangle.
+    let edges = [
+        (point(0.0f32, 0.0), point(1.0, 0.0)),
+        (point(1.0, 0.0), point(1.0, 1.0)),
+        (point(1.0, 1.0), point(2.0, 1.0)),
+        (point(2.0, 1.0), point(2.0, 2.0)),
+        (point(2.0, 2.0), point(1.0, 2.0)),
+        (point(1.0, 2.0), point(1.0, 1.0)),
+        (point(1.0, 1.0), point(0.0, 1.0)),
+        (point(0.0, 1.0), point(0.0, 0.0)),
+        (point(5.0, 0.0), point(6.0, 0.0)),
+        (point(6.0, 0.0), point(5.0, 1.0)),
+        (point(5.0, 1.0), point(5.0, 0.0)),
+    ];
+
+    let mut polygons = Vec::new();
+    chain_edges(&edges, &mut |points| polygons.push(points.to_vec()));
+    assert_eq!(
+        polygons,
+        vec![
+            vec![point(0.0, 0.0), point(1.0, 0.0), point(1.0, 1.0), point(0.0, 1.0)],
+            vec![point(1.0, 1.0), point(2.0, 1.0), point(2.0, 2.0), point(1.0, 2.0)],
+            vec![point(5.0, 0.0), point(6.0, 0.0), point(5.0, 1.0)],
+        ]
+    );
+}
@@ -1,12 +1,15 @@
 use crate::event_queue::*;
 use crate::math::*;
-use crate::geom::LineSegment;
+use crate::geom::{self, LineSegment};
 use crate::monotone::*;
+use crate::path::path_f64::PathF64;
 use crate::path::polygon::Polygon;
+use crate::path::private::chain_edges;
 use crate::path::{
     AttributeStore, EndpointId, FillRule, IdEvent, PathEvent, PathSlice, PositionStore, Winding
 };
 use crate::path::traits::{PathBuilder, Build};
+use crate::geometry_builder::NoOutput;
 use crate::{FillGeometryBuilder, Orientation, VertexId};
 use crate::{
     FillOptions, InternalError, Side, TessellationError, TessellationResult, VertexSource,
@@ -89,6 +92,7 @@ struct ActiveEdgeScan {
     merge_split_event: bool,
     above: Range<ActiveEdgeIdx>,
     winding_before_point: WindingState,
+    outline_edges: Vec<(Point, Point)>,
 }
 
 impl ActiveEdgeScan {
@@ -102,6 +106,7 @@ impl ActiveEdgeScan {
             merge_split_event: false,
             above: 0..0,
             winding_before_point: WindingState::new(),
+            outline_edges: Vec::new(),
         }
     }
 
@@ -114,6 +119,7 @@ impl ActiveEdgeScan {
         self.merge_split_event = false;
         self.above = 0..0;
         self.winding_before_point = WindingState::new();
+        self.outline_edges.clear();
     }
 }
 
@@ -491,6 +497,8 @@ pub struct FillTessellator {
     log: bool,
     assume_no_intersection: bool,
     attrib_buffer: Vec<f32>,
+    collect_outline: bool,
+    outline: Vec<(Point, Point)>,
 
     scan: ActiveEdgeScan,
     events: EventQueue,
@@ -517,6 +525,8 @@ impl FillTessellator {
             log,
             assume_no_intersection: false,
             attrib_buffer: Vec::new(),
+            collect_outline: false,
+            outline: Vec::new(),
 
             scan: ActiveEdgeScan::new(),
             events: EventQueue::new(),
@@ -589,6 +599,28 @@ impl FillTessellator {
         }
     }
 
+    /// Compute the tessellation of a double precision path.
+    ///
+    /// The positions are converted to `f32` relative to `origin`, and the generated
+    /// vertices are expressed in this local coordinate system. Picking an origin close
+    /// to the path (for example the center of its bounding rectangle) preserves
+    /// precision for geometry located far from the origin of the global coordinate system.
+    pub fn tessellate_path_f64(
+        &mut self,
+        path: &PathF64,
+        origin: geom::Point<f64>,
+        options: &FillOptions,
+        builder: &mut dyn FillGeometryBuilder,
+    ) -> TessellationResult {
+        self.tessellate_with_ids(
+            path.id_iter(),
+            &path.local_positions(origin),
+            None,
+            options,
+            builder,
+        )
+    }
+
     /// Tessellate a `Polygon`.
     pub fn tessellate_polygon(
         &mut self,
@@ -646,6 +678,61 @@ impl FillTessellator {
         builder.build()
     }
 
+    /// Compute the outline of the area filled by a path instead of its tessellation.
+    ///
+    /// The outline is written to `output` as closed sub-paths made of line segments that
+    /// don't intersect each other, so that filling them with either fill rule covers the
+    /// same area as filling the path with `options.fill_rule`. Outer contours have a
+    /// positive winding and holes a negative one.
+    ///
+    /// This can be used to remove overlaps and self-intersections from a path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lyon_tessellation::{FillTessellator, FillOptions};
+    /// use lyon_tessellation::path::Path;
+    /// use lyon_tessellation::math::point;
+    ///
+    /// // A self-intersecting bow tie.
+    /// let mut builder = Path::builder();
+    /// builder.begin(point(0.0, 0.0));
+    /// builder.line_to(point(10.0, 10.0));
+    /// builder.line_to(point(10.0, 0.0));
+    /// builder.line_to(point(0.0, 10.0));
+    /// builder.end(true);
+    /// let path = builder.build();
+    ///
+    /// let mut output = Path::builder();
+    /// let mut tessellator = FillTessellator::new();
+    /// tessellator.tessellate_outline(&path, &FillOptions::default(), &mut output).unwrap();
+    ///
+    /// // Two triangles touching at their tip.
+    /// let outline = output.build();
+    /// ```
+    pub fn tessellate_outline(
+        &mut self,
+        path: impl IntoIterator<Item = PathEvent>,
+        options: &FillOptions,
+        output: &mut impl PathBuilder,
+    ) -> Result<(), TessellationError> {
+        self.collect_outline = true;
+        let result = self.tessellate(path, options, &mut NoOutput::new());
+        self.collect_outline = false;
+        result?;
+
+        if self.orientation == Orientation::Horizontal {
+            for edge in &mut self.outline {
+                *edge = (reorient(edge.0), reorient(edge.1));
+            }
+        }
+
+        build_outline(&self.outline, output);
+        self.outline.clear();
+
+        Ok(())
+    }
+
     /// Tessellate directly from a sequence of `PathBuilder` commands, without
     /// creating an intermediate path data structure.
     ///
@@ -1148,8 +1235,18 @@ impl FillTessellator {
                     scan.spans_to_end.push(winding.span_index);
                 }
 
+                let was_in = winding.is_in;
                 winding.update(self.fill_rule, active_edge.winding);
 
+                if self.collect_outline && was_in != winding.is_in {
+                    // Orient the outline edges so that the inside is on their left.
+                    scan.outline_edges.push(if was_in {
+                        (active_edge.from, self.current_position)
+                    } else {
+                        (self.current_position, active_edge.from)
+                    });
+                }
+
                 tess_log!(
                     self,
                     " x span: {} in: {}",
@@ -1294,6 +1391,8 @@ impl FillTessellator {
         scan: &mut ActiveEdgeScan,
         output: &mut dyn FillGeometryBuilder,
     ) {
+        self.outline.extend_from_slice(&scan.outline_edges);
+
         for &(span_index, side) in &scan.vertex_events {
             tess_log!(
                 self,
@@ -2012,6 +2111,7 @@ impl FillTessellator {
         self.active.edges.clear();
         self.edges_below.clear();
         self.fill.spans.clear();
+        self.outline.clear();
     }
 }
 
@@ -2055,6 +2155,17 @@ fn reorient(p: Point) -> Point {
     point(p.y, -p.x)
 }
 
+/// Chains outline edges into closed sub-paths.
+fn build_outline(edges: &[(Point, Point)], output: &mut impl PathBuilder) {
+    chain_edges(edges, &mut |points| {
+        output.begin(points[0]);
+        for p in &points[1..] {
+            output.line_to(*p);
+        }
+        output.end(true);
+    });
+}
+
 /// Extra vertex information from the `FillTessellator`, accessible when building vertices.
 pub struct FillVertex<'l> {
     position: Point,
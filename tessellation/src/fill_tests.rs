@@ -2404,3 +2404,128 @@ fn low_tolerance_01() {
         &mut simple_builder(&mut buffers),
     ).unwrap();
 }
+
+#[test]
+fn test_path_f64() {
+    use crate::geom;
+    use crate::path::path_f64::PathF64;
+
+    // Coordinates that can't be represented with 32 bits floats.
+    let origin = geom::point(10_000_000.0, 20_000_000.0);
+    let shape = [(0.0, 0.0), (5.25, 0.0), (5.25, 5.5), (2.0, 3.0), (0.0, 5.5)];
+
+    let mut builder = PathF64::builder();
+    builder.begin(origin + geom::vector(shape[0].0, shape[0].1));
+    for &(x, y) in &shape[1..] {
+        builder.line_to(origin + geom::vector(x, y));
+    }
+    builder.close();
+    let path_f64 = builder.build();
+
+    let mut builder = Path::builder();
+    builder.begin(point(shape[0].0 as f32, shape[0].1 as f32));
+    for &(x, y) in &shape[1..] {
+        builder.line_to(point(x as f32, y as f32));
+    }
+    builder.close();
+    let path = builder.build();
+
+    let mut tess = FillTessellator::new();
+    let mut expected: VertexBuffers<Point, u16> = VertexBuffers::new();
+    tess.tessellate_path(&path, &FillOptions::default(), &mut simple_builder(&mut expected))
+        .unwrap();
+
+    let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+    tess.tessellate_path_f64(
+        &path_f64,
+        origin,
+        &FillOptions::default(),
+        &mut simple_builder(&mut buffers),
+    )
+    .unwrap();
+
+    assert_eq!(buffers.vertices, expected.vertices);
+    assert_eq!(buffers.indices, expected.indices);
+}
+
+#[test]
+fn test_outline() {
+    use crate::path::{PathEvent, Winding};
+    use crate::Orientation;
+
+    // Returns the vertices of each sub-path, starting from the smallest one.
+    fn outline(path: &Path, options: &FillOptions) -> Vec<Vec<(f32, f32)>> {
+        let mut builder = Path::builder();
+        FillTessellator::new()
+            .tessellate_outline(path, options, &mut builder)
+            .unwrap();
+
+        let mut polygons = Vec::new();
+        for evt in builder.build().iter() {
+            match evt {
+                PathEvent::Begin { at } => polygons.push(vec![(at.x, at.y)]),
+                PathEvent::Line { to, .. } => polygons.last_mut().unwrap().push((to.x, to.y)),
+                PathEvent::End { close: true, .. } => {}
+                _ => panic!(),
+            }
+        }
+        for polygon in &mut polygons {
+            let min = (0..polygon.len())
+                .min_by(|a, b| polygon[*a].partial_cmp(&polygon[*b]).unwrap())
+                .unwrap();
+            polygon.rotate_left(min);
+        }
+        polygons.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        polygons
+    }
+
+    // A bow tie is split into two triangles with the same orientation.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(10.0, 10.0));
+    builder.line_to(point(10.0, 0.0));
+    builder.line_to(point(0.0, 10.0));
+    builder.end(true);
+    let path = builder.build();
+    assert_eq!(
+        outline(&path, &FillOptions::default()),
+        vec![
+            vec![(0.0, 0.0), (5.0, 5.0), (0.0, 10.0)],
+            vec![(5.0, 5.0), (10.0, 0.0), (10.0, 10.0)],
+        ]
+    );
+
+    // Overlapping squares are merged with the non-zero fill rule, and holes have the
+    // opposite orientation.
+    let mut builder = Path::builder();
+    builder.add_rectangle(&rect(0.0, 0.0, 10.0, 10.0), Winding::Positive);
+    builder.add_rectangle(&rect(5.0, 5.0, 10.0, 10.0), Winding::Positive);
+    builder.add_rectangle(&rect(1.0, 1.0, 2.0, 2.0), Winding::Negative);
+    let path = builder.build();
+    assert_eq!(
+        outline(&path, &FillOptions::non_zero()),
+        vec![
+            vec![
+                (0.0, 0.0), (10.0, 0.0), (10.0, 5.0), (15.0, 5.0),
+                (15.0, 15.0), (5.0, 15.0), (5.0, 10.0), (0.0, 10.0),
+            ],
+            vec![(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)],
+        ]
+    );
+
+    // With the even-odd fill rule the overlap is removed, leaving two L shapes that touch
+    // at two corners.
+    let even_odd_outline = vec![
+        vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0), (5.0, 5.0), (5.0, 10.0), (0.0, 10.0)],
+        vec![(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)],
+        vec![(5.0, 10.0), (10.0, 10.0), (10.0, 5.0), (15.0, 5.0), (15.0, 15.0), (5.0, 15.0)],
+    ];
+    let even_odd = FillOptions::even_odd();
+    assert_eq!(outline(&path, &even_odd), even_odd_outline);
+
+    // The sweep orientation doesn't change the result: the vertices are mapped back
+    // to the original coordinate space.
+    let horizontal = even_odd.with_sweep_orientation(Orientation::Horizontal);
+    assert_eq!(outline(&path, &horizontal), even_odd_outline);
+}
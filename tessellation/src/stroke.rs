@@ -1,12 +1,15 @@
 use crate::math::*;
 use crate::geom::utils::{directed_angle, normalized_tangent, tangent};
-use crate::geom::{CubicBezierSegment, QuadraticBezierSegment, Line, LineSegment};
+use crate::geom::{self, CubicBezierSegment, QuadraticBezierSegment, Line, LineSegment};
 use crate::math_utils::compute_normal;
 use crate::path::builder::{Build, PathBuilder};
 use crate::path::private::DebugValidator;
 use crate::path::{AttributeStore, EndpointId, IdEvent, PathEvent, PathSlice, PositionStore, Winding};
+use crate::path::path_f64::PathF64;
 use crate::path::polygon::Polygon;
-use crate::{StrokeGeometryBuilder, VertexId};
+use crate::path::Path;
+use crate::geometry_builder::{BuffersBuilder, Positions, VertexBuffers};
+use crate::{FillOptions, FillRule, FillTessellator, StrokeGeometryBuilder, VertexId};
 use crate::{
     LineCap, LineJoin, Side, StrokeOptions, TessellationError, TessellationResult,
     VertexSource,
@@ -26,7 +29,8 @@ const EPSILON: f32 = 1e-4;
 /// be the desired behavior. This needs to be kept in mind when rendering transparent
 /// SVG strokes since the spec mandates that each point along a semi-transparent path
 /// is shaded once no matter how many times the path overlaps with itself at this
-/// location.
+/// location. In this case, [`tessellate_outline`](#method.tessellate_outline) can be
+/// used to compute the outline of the stroke and fill it with the `FillTessellator`.
 ///
 /// `StrokeTessellator` exposes a similar interface to its
 /// [fill equivalent](struct.FillTessellator.html).
@@ -158,6 +162,28 @@ impl StrokeTessellator {
         }
     }
 
+    /// Compute the tessellation of a double precision path.
+    ///
+    /// The positions are converted to `f32` relative to `origin`, and the generated
+    /// vertices are expressed in this local coordinate system. Picking an origin close
+    /// to the path (for example the center of its bounding rectangle) preserves
+    /// precision for geometry located far from the origin of the global coordinate system.
+    pub fn tessellate_path_f64(
+        &mut self,
+        path: &PathF64,
+        origin: geom::Point<f64>,
+        options: &StrokeOptions,
+        builder: &mut dyn StrokeGeometryBuilder,
+    ) -> TessellationResult {
+        self.tessellate_with_ids(
+            path.id_iter(),
+            &path.local_positions(origin),
+            None,
+            options,
+            builder,
+        )
+    }
+
     /// Tessellate directly from a sequence of `PathBuilder` commands, without
     /// creating an intermediate path data structure.
     ///
@@ -259,6 +285,79 @@ impl StrokeTessellator {
 
         builder.build()
     }
+
+    /// Compute the outline of the stroke of a path instead of its tessellation.
+    ///
+    /// The outline, including the caps and joins, is written to `output` as closed
+    /// sub-paths made of line segments that don't intersect each other. Unlike the
+    /// tessellation, it doesn't overlap with itself where the path does, so filling it
+    /// covers each point of the stroke once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lyon_tessellation::{FillOptions, FillTessellator, StrokeOptions, StrokeTessellator};
+    /// use lyon_tessellation::geometry_builder::{simple_builder, VertexBuffers};
+    /// use lyon_tessellation::path::Path;
+    /// use lyon_tessellation::math::{point, Point};
+    ///
+    /// let mut builder = Path::builder();
+    /// builder.begin(point(0.0, 0.0));
+    /// builder.line_to(point(10.0, 10.0));
+    /// builder.line_to(point(10.0, 0.0));
+    /// builder.line_to(point(0.0, 10.0));
+    /// builder.end(false);
+    /// let path = builder.build();
+    ///
+    /// let mut outline = Path::builder();
+    /// let mut stroke_tessellator = StrokeTessellator::new();
+    /// stroke_tessellator.tessellate_outline(
+    ///     &path,
+    ///     &StrokeOptions::default().with_line_width(2.0),
+    ///     &mut outline,
+    /// ).unwrap();
+    ///
+    /// // The outline can be filled to render the stroke without overlapping triangles.
+    /// let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+    /// let mut fill_tessellator = FillTessellator::new();
+    /// fill_tessellator.tessellate_path(
+    ///     &outline.build(),
+    ///     &FillOptions::non_zero(),
+    ///     &mut simple_builder(&mut buffers),
+    /// ).unwrap();
+    /// ```
+    pub fn tessellate_outline(
+        &mut self,
+        input: impl IntoIterator<Item = PathEvent>,
+        options: &StrokeOptions,
+        output: &mut impl PathBuilder,
+    ) -> Result<(), TessellationError> {
+        let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+        self.tessellate(input, options, &mut BuffersBuilder::new(&mut buffers, Positions))?;
+
+        // Merge the triangles with a consistent orientation.
+        let mut triangles = Path::builder();
+        for triangle in buffers.indices.chunks(3) {
+            let a = buffers.vertices[triangle[0] as usize];
+            let mut b = buffers.vertices[triangle[1] as usize];
+            let mut c = buffers.vertices[triangle[2] as usize];
+            let area = (b - a).cross(c - a);
+            if area == 0.0 {
+                continue;
+            }
+            if area < 0.0 {
+                std::mem::swap(&mut b, &mut c);
+            }
+            triangles.begin(a);
+            triangles.line_to(b);
+            triangles.line_to(c);
+            triangles.end(true);
+        }
+
+        let fill_options = FillOptions::tolerance(options.tolerance)
+            .with_fill_rule(FillRule::NonZero);
+        FillTessellator::new().tessellate_outline(&triangles.build(), &fill_options, output)
+    }
 }
 
 /// A builder that tessellates a stroke directly without allocating any intermediate data structure.
@@ -1431,8 +1530,6 @@ fn angle_diff(a: Angle, b: Angle) -> Angle {
 
 #[cfg(test)]
 use crate::geometry_builder::*;
-#[cfg(test)]
-use crate::path::Path;
 
 #[cfg(test)]
 fn test_path(path: PathSlice, options: &StrokeOptions, expected_triangle_count: Option<u32>) {
@@ -1756,3 +1853,74 @@ fn stroke_vertex_source_01() {
         }
     }
 }
+
+#[test]
+fn test_path_f64() {
+    use crate::geometry_builder::{simple_builder, VertexBuffers};
+    use crate::path::Path;
+
+    let origin = geom::point(-30_000_000.0, 10_000_000.0);
+
+    let mut builder = PathF64::builder();
+    builder.begin(origin + geom::vector(0.0, 0.0));
+    builder.quadratic_bezier_to(origin + geom::vector(5.5, 10.0), origin + geom::vector(10.25, 0.0));
+    builder.end(false);
+    let path_f64 = builder.build();
+
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.quadratic_bezier_to(point(5.5, 10.0), point(10.25, 0.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let options = StrokeOptions::tolerance(0.01).with_line_width(2.0);
+    let mut tess = StrokeTessellator::new();
+    let mut expected: VertexBuffers<Point, u16> = VertexBuffers::new();
+    tess.tessellate_path(&path, &options, &mut simple_builder(&mut expected))
+        .unwrap();
+
+    let mut buffers: VertexBuffers<Point, u16> = VertexBuffers::new();
+    tess.tessellate_path_f64(&path_f64, origin, &options, &mut simple_builder(&mut buffers))
+        .unwrap();
+
+    assert!(!buffers.indices.is_empty());
+    assert_eq!(buffers.vertices, expected.vertices);
+    assert_eq!(buffers.indices, expected.indices);
+}
+
+#[test]
+fn test_outline() {
+    // Two overlapping strokes forming a cross.
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 5.0));
+    builder.line_to(point(10.0, 5.0));
+    builder.end(false);
+    builder.begin(point(5.0, 0.0));
+    builder.line_to(point(5.0, 10.0));
+    builder.end(false);
+    let path = builder.build();
+
+    let mut outline = Path::builder();
+    StrokeTessellator::new()
+        .tessellate_outline(&path, &StrokeOptions::default().with_line_width(2.0), &mut outline)
+        .unwrap();
+    let outline = outline.build();
+
+    let mut num_sub_paths = 0;
+    let mut num_edges = 0;
+    let mut area = 0.0;
+    for evt in outline.iter() {
+        match evt {
+            PathEvent::Begin { .. } => num_sub_paths += 1,
+            PathEvent::Line { from, to } | PathEvent::End { last: from, first: to, .. } => {
+                num_edges += 1;
+                area += from.to_vector().cross(to.to_vector()) * 0.5;
+            }
+            _ => panic!(),
+        }
+    }
+
+    assert_eq!(num_sub_paths, 1);
+    assert_eq!(num_edges, 12);
+    assert_eq!(area, 36.0);
+}